@@ -4,6 +4,8 @@ use clap::{Arg, ArgMatches, Command};
 use colored::{Colorize, control};
 use dirs;
 use serde_json::Value;
+use is_terminal::IsTerminal;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command as ProcessCommand;
@@ -12,6 +14,19 @@ use crate::errors::CIError;
 use crate::helpers::path::get_ci_root;
 use crate::helpers::agent_autoload::AgentAutoload;
 use crate::helpers::agent_colors;
+use crate::helpers::agent_launcher::{LaunchRequest, configured_launcher};
+use crate::helpers::agent_manifest::{AgentMetadata, validate_agent_dir};
+use crate::helpers::memory_history::{DiffSide, MemoryHistory};
+use crate::helpers::agent_matcher::{rank_agents, suggested_team, print_matches};
+use crate::helpers::agent_bundle::{export_agent, open_bundle, install_bundle, ConflictStrategy, BUNDLE_EXTENSION};
+use crate::helpers::agent_stats::{collect_usage, parse_since, sync_usage_metadata};
+use crate::helpers::agent_supervisor::format_duration_secs;
+use crate::helpers::agent_templates::{
+    find_template, list_templates, load_vars_file, parse_var, undeclared_variables, user_templates_dir,
+    AgentTemplate, TemplateSource, DEFAULT_TEMPLATE,
+};
+use crate::helpers::CommandHelpers;
+use crate::tools::memory_inheritance::{MemoryResolver, MIXINS_DIR};
 use crate::config::ci_config::{CIConfig, AutoAcceptConfig};
 
 pub fn create_command() -> Command {
@@ -60,8 +75,10 @@ pub fn create_command() -> Command {
                         .short('t')
                         .long("template")
                         .value_name("TEMPLATE")
-                        .help("Create from template")
+                        .help("Create from template (default: basic)")
                 )
+                .arg(template_var_arg())
+                .arg(template_vars_file_arg())
                 .arg(
                     Arg::new("enable")
                         .long("enable")
@@ -123,11 +140,12 @@ pub fn create_command() -> Command {
         )
         .subcommand(
             Command::new("template")
-                .about("Create an agent from a template")
+                .about("Manage agent templates, or create an agent from a template")
+                .args_conflicts_with_subcommands(true)
+                .arg_required_else_help(true)
                 .arg(
                     Arg::new("template_name")
                         .help("Name of the template")
-                        .required(true)
                         .index(1)
                 )
                 .arg(
@@ -135,6 +153,45 @@ pub fn create_command() -> Command {
                         .help("Name for the new agent")
                         .index(2)
                 )
+                .arg(template_var_arg())
+                .arg(template_vars_file_arg())
+                .subcommand(
+                    Command::new("list")
+                        .about("List built-in and user templates")
+                )
+                .subcommand(
+                    Command::new("show")
+                        .about("Show a template's variables and files")
+                        .arg(
+                            Arg::new("template_name")
+                                .help("Name of the template")
+                                .required(true)
+                                .index(1)
+                        )
+                )
+                .subcommand(
+                    Command::new("new")
+                        .about("Create a user template to customize")
+                        .arg(
+                            Arg::new("template_name")
+                                .help("Name of the new template")
+                                .required(true)
+                                .index(1)
+                        )
+                        .arg(
+                            Arg::new("from")
+                                .long("from")
+                                .value_name("TEMPLATE")
+                                .default_value(DEFAULT_TEMPLATE)
+                                .help("Template to start from")
+                        )
+                        .arg(
+                            Arg::new("description")
+                                .short('d')
+                                .long("description")
+                                .help("Template description")
+                        )
+                )
         )
         .subcommand(
             Command::new("deploy")
@@ -166,6 +223,158 @@ pub fn create_command() -> Command {
                         .index(1)
                 )
         )
+        .subcommand(
+            Command::new("validate")
+                .about("Validate agent directories and their agent.toml manifests")
+                .arg(
+                    Arg::new("agent_name")
+                        .help("Name of the agent to validate")
+                        .index(1)
+                        .required_unless_present("all")
+                )
+                .arg(
+                    Arg::new("all")
+                        .long("all")
+                        .action(clap::ArgAction::SetTrue)
+                        .conflicts_with("agent_name")
+                        .help("Validate every agent")
+                )
+        )
+        .subcommand(
+            Command::new("render")
+                .about("Print an agent's memory with extends/mixins resolved")
+                .arg(
+                    Arg::new("agent_name")
+                        .help("Name of the agent to render")
+                        .required(true)
+                        .index(1)
+                )
+        )
+        .subcommand(
+            Command::new("memory")
+                .about("Inspect and restore versions of an agent's memory files")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("log")
+                        .about("List recorded memory snapshots")
+                        .arg(Arg::new("agent_name").help("Name of the agent").required(true).index(1))
+                        .arg(
+                            Arg::new("limit")
+                                .long("limit")
+                                .short('n')
+                                .value_parser(clap::value_parser!(usize))
+                                .help("Show only the most recent N snapshots")
+                        )
+                )
+                .subcommand(
+                    Command::new("diff")
+                        .about("Show a unified diff between snapshots (default: latest vs working copy)")
+                        .arg(Arg::new("agent_name").help("Name of the agent").required(true).index(1))
+                        .arg(Arg::new("from").help("Snapshot to diff from").index(2))
+                        .arg(Arg::new("to").help("Snapshot to diff to (default: working copy)").index(3))
+                )
+                .subcommand(
+                    Command::new("revert")
+                        .about("Restore the memory files of a snapshot")
+                        .arg(Arg::new("agent_name").help("Name of the agent").required(true).index(1))
+                        .arg(Arg::new("snapshot").help("Snapshot id (or unique prefix)").required(true).index(2))
+                        .arg(
+                            Arg::new("yes")
+                                .long("yes")
+                                .short('y')
+                                .action(clap::ArgAction::SetTrue)
+                                .help("Skip the confirmation prompt")
+                        )
+                )
+                .subcommand(
+                    Command::new("snapshot")
+                        .about("Record the current memory files now")
+                        .arg(Arg::new("agent_name").help("Name of the agent").required(true).index(1))
+                )
+        )
+        .subcommand(
+            Command::new("suggest")
+                .about("Rank agents by how well their capabilities match a task")
+                .arg(
+                    Arg::new("task")
+                        .help("Task description")
+                        .required(true)
+                        .num_args(1..)
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("top")
+                        .long("top")
+                        .value_parser(clap::value_parser!(usize))
+                        .default_value("5")
+                        .help("Number of agents to show")
+                )
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Export an agent as a portable bundle")
+                .arg(
+                    Arg::new("agent_name")
+                        .help("Name of the agent to export")
+                        .required(true)
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("output")
+                        .long("output")
+                        .short('o')
+                        .help("Bundle path (default: ./<Name>.ciagent.tar.gz)")
+                )
+                .arg(
+                    Arg::new("include-sessions")
+                        .long("include-sessions")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Include the agent's Sessions directory")
+                )
+        )
+        .subcommand(
+            Command::new("import")
+                .about("Import an agent bundle and register it in AGENTS.md")
+                .arg(
+                    Arg::new("bundle")
+                        .help("Path to the bundle")
+                        .required(true)
+                        .index(1)
+                )
+                .arg(
+                    Arg::new("on-conflict")
+                        .long("on-conflict")
+                        .value_parser(["rename", "overwrite", "merge"])
+                        .help("What to do when the agent already exists")
+                )
+                .arg(
+                    Arg::new("as")
+                        .long("as")
+                        .help("Install the agent under a different name")
+                )
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Import even if the bundled agent fails validation")
+                )
+        )
+        .subcommand(
+            Command::new("stats")
+                .about("Show agent usage statistics from recorded sessions")
+                .arg(
+                    Arg::new("since")
+                        .long("since")
+                        .value_parser(parse_since)
+                        .help("Only count sessions started after a date (YYYY-MM-DD) or age (12h, 7d, 4w)")
+                )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Print statistics as JSON")
+                )
+        )
         .subcommand(
             Command::new("voice")
                 .about("Launch agent with voice mode (auto-accept enabled)")
@@ -178,6 +387,22 @@ pub fn create_command() -> Command {
         )
 }
 
+fn template_var_arg() -> Arg {
+    Arg::new("var")
+        .long("var")
+        .value_name("KEY=VALUE")
+        .value_parser(parse_var)
+        .action(clap::ArgAction::Append)
+        .help("Set a template variable (repeatable)")
+}
+
+fn template_vars_file_arg() -> Arg {
+    Arg::new("vars-file")
+        .long("vars-file")
+        .value_name("FILE")
+        .help("Read template variables from a TOML file")
+}
+
 pub fn execute(matches: &ArgMatches) -> Result<()> {
     match matches.subcommand() {
         Some(("list", sub_matches)) => list_agents(sub_matches),
@@ -187,11 +412,18 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         Some(("disable", sub_matches)) => agent_disable(sub_matches),
         Some(("activate", sub_matches)) => agent_activate(sub_matches),
         Some(("load", sub_matches)) => agent_load(sub_matches),
-        Some(("template", sub_matches)) => create_agent_from_template(sub_matches),
+        Some(("template", sub_matches)) => agent_template(sub_matches),
         Some(("deploy", sub_matches)) => deploy_ci_globally(sub_matches),
         Some(("reset-color", _)) => agent_reset_color(),
         Some(("switch", sub_matches)) => agent_switch(sub_matches),
         Some(("voice", sub_matches)) => agent_voice(sub_matches),
+        Some(("validate", sub_matches)) => agent_validate(sub_matches),
+        Some(("render", sub_matches)) => agent_render(sub_matches),
+        Some(("memory", sub_matches)) => agent_memory(sub_matches),
+        Some(("suggest", sub_matches)) => agent_suggest(sub_matches),
+        Some(("stats", sub_matches)) => agent_stats(sub_matches),
+        Some(("export", sub_matches)) => agent_export(sub_matches),
+        Some(("import", sub_matches)) => agent_import(sub_matches),
         _ => {
             eprintln!("{}", "No valid subcommand provided".red());
             std::process::exit(1);
@@ -313,8 +545,9 @@ pub fn list_agents(matches: &ArgMatches) -> Result<()> {
                     
                     let agent_path = entry.path();
                     let readme_path = agent_path.join("README.md");
+                    let manifest = AgentMetadata::from_manifest(&agent_path).ok().flatten();
                     
-                    if readme_path.exists() {
+                    if readme_path.exists() || manifest.is_some() {
                         let status = if enabled_agents.contains(&agent_name.to_string()) {
                             "enabled"
                         } else if disabled_agents.contains(&agent_name.to_string()) {
@@ -327,7 +560,10 @@ pub fn list_agents(matches: &ArgMatches) -> Result<()> {
                             continue;
                         }
                         
-                        let description = extract_agent_description(&readme_path).unwrap_or_default();
+                        let description = match manifest.filter(|m| !m.description.is_empty()) {
+                            Some(manifest) => manifest.description,
+                            None => extract_agent_description(&readme_path).unwrap_or_default(),
+                        };
                         let session_count = count_sessions(&agent_path).unwrap_or(0);
                         
                         agent_info.push((agent_name.to_string(), status, description, session_count));
@@ -490,9 +726,40 @@ fn show_agent_info(matches: &ArgMatches) -> Result<()> {
     println!("{}: {}", "Path".bold(), agent_dir.display().to_string().dimmed());
     println!();
     
+    // Prefer the agent.toml manifest over README scraping
+    let manifest = match AgentMetadata::from_manifest(&agent_dir) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            println!("{} {:#}", "⚠".yellow(), e);
+            println!("{} Run 'ci agent validate {}' for details", "💡".blue(), agent_name);
+            println!();
+            None
+        }
+    };
+    
+    if let Some(manifest) = &manifest {
+        println!("{}", "Description:".bold());
+        println!("  {}", manifest.description);
+        println!();
+        println!("{}: {}", "Version".bold(), manifest.version);
+        if !manifest.capabilities.is_empty() {
+            println!("{}: {}", "Capabilities".bold(), manifest.capabilities.join(", "));
+        }
+        println!("{}: {}", "Memory file".bold(), manifest.memory_path.dimmed());
+        if let Some(learning_path) = &manifest.learning_path {
+            println!("{}: {}", "Learning file".bold(), learning_path.dimmed());
+        }
+        let mut attributes: Vec<_> = manifest.attributes.iter().collect();
+        attributes.sort();
+        for (key, value) in attributes {
+            println!("{}: {}", key.bold(), value);
+        }
+        println!();
+    }
+    
     // Show README content
     let readme_path = agent_dir.join("README.md");
-    if readme_path.exists() {
+    if manifest.is_none() && readme_path.exists() {
         println!("{}", "Description:".bold());
         if let Ok(content) = fs::read_to_string(&readme_path) {
             let lines: Vec<&str> = content.lines().collect();
@@ -572,104 +839,467 @@ fn show_agent_info(matches: &ArgMatches) -> Result<()> {
     Ok(())
 }
 
-fn create_agent(matches: &ArgMatches) -> Result<()> {
+fn agent_validate(matches: &ArgMatches) -> Result<()> {
+    let agents_dir = get_agents_dir()?;
+    
+    let agent_dirs: Vec<PathBuf> = if matches.get_flag("all") {
+        let mut dirs: Vec<PathBuf> = fs::read_dir(&agents_dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| !n.starts_with('.') && !n.starts_with("parallel_session_") && n != MIXINS_DIR)
+                    .unwrap_or(false)
+            })
+            .collect();
+        dirs.sort();
+        dirs
+    } else {
+        let agent_name = matches.get_one::<String>("agent_name").unwrap();
+        let agent_dir = agents_dir.join(agent_name);
+        if !agent_dir.exists() {
+            return Err(CIError::NotFound(format!(
+                "Agent '{}' not found. Use 'ci agent list' to see available agents.",
+                agent_name
+            )).into());
+        }
+        vec![agent_dir]
+    };
+    
+    println!("{}", "Agent Validation".cyan().bold());
+    println!("{}", "=".repeat(50).cyan());
+    println!();
+    
+    let mut invalid = 0;
+    for agent_dir in &agent_dirs {
+        let mut report = validate_agent_dir(agent_dir);
+        if let Err(e) = MemoryResolver::new(&agents_dir).resolve_agent(&report.agent_name) {
+            if report.errors.is_empty() {
+                report.errors.push(format!("{:#}", e));
+            }
+        }
+        
+        let source = if report.has_manifest { "agent.toml" } else { "no manifest" };
+        if report.is_valid() {
+            println!("{} {} {}", "✓".green(), report.agent_name.bold(), format!("({})", source).dimmed());
+        } else {
+            invalid += 1;
+            println!("{} {} {}", "✗".red(), report.agent_name.bold(), format!("({})", source).dimmed());
+        }
+        
+        for error in &report.errors {
+            println!("    {} {}", "error:".red(), error);
+        }
+        for warning in &report.warnings {
+            println!("    {} {}", "warning:".yellow(), warning);
+        }
+    }
+    
+    println!();
+    println!("{} checked, {} valid, {} invalid",
+        agent_dirs.len(),
+        (agent_dirs.len() - invalid).to_string().green(),
+        invalid.to_string().red()
+    );
+    
+    if invalid > 0 {
+        return Err(CIError::Agent(format!("{} agent(s) failed validation", invalid)).into());
+    }
+    
+    Ok(())
+}
+
+fn agent_render(matches: &ArgMatches) -> Result<()> {
     let agent_name = matches.get_one::<String>("agent_name").unwrap();
-    let _template = matches.get_one::<String>("template");
-    let enable_after = matches.get_flag("enable");
+    let agents_dir = get_agents_dir()?;
+    
+    let mut resolver = MemoryResolver::new(&agents_dir);
+    let memory = resolver.resolve_agent(agent_name)
+        .map_err(|e| CIError::Agent(format!("{:#}", e)))?;
+    
+    // Keep stdout clean so the rendered memory can be piped or redirected
+    eprintln!("{} {}", "Inheritance chain:".dimmed(), resolver.chain().join(" → ").dimmed());
+    print!("{}", memory);
+    
+    Ok(())
+}
+
+fn agent_stats(matches: &ArgMatches) -> Result<()> {
+    let agents_dir = get_agents_dir()?;
+    let since = matches.get_one::<chrono::DateTime<chrono::Utc>>("since").copied();
+    let stats = collect_usage(&agents_dir, since)?;
+    
+    // Unfiltered statistics are authoritative for the stored usage counters
+    if since.is_none() {
+        sync_usage_metadata(&agents_dir, &stats)?;
+    }
+    
+    if matches.get_flag("json") {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+    
+    println!("{}", "Agent Usage".cyan().bold());
+    println!("{}", "=".repeat(96).cyan());
+    if let Some(since) = since {
+        println!("Since {}", since.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"));
+    }
     
+    if stats.agents.is_empty() {
+        println!("No recorded sessions yet. Sessions are recorded by 'ci load'.");
+        return Ok(());
+    }
+    
+    println!("{:<20} {:>8} {:>9} {:>10} {:>10} {:<12} {}",
+        "AGENT".bold(), "LAUNCHES".bold(), "PARALLEL".bold(), "TOTAL".bold(), "MEDIAN".bold(), "LAST USED".bold(), "OFTEN WITH".bold());
+    
+    for agent in &stats.agents {
+        let co_loaded = agent.co_loaded.iter()
+            .map(|(name, count)| format!("{} ({})", name, count))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("{:<20} {:>8} {:>9} {:>10} {:>10} {:<12} {}",
+            agent.name.cyan(),
+            agent.launches,
+            agent.parallel_instances,
+            format_duration_secs(agent.total_duration_secs),
+            agent.median_duration_secs.map(format_duration_secs).unwrap_or_else(|| "-".to_string()),
+            agent.last_used.map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            co_loaded.dimmed()
+        );
+    }
+    
+    println!();
+    println!("{} sessions across {} agents", stats.sessions, stats.agents.len());
+    
+    Ok(())
+}
+
+fn agent_export(matches: &ArgMatches) -> Result<()> {
+    let agent_name = matches.get_one::<String>("agent_name").unwrap();
     let agents_dir = get_agents_dir()?;
     let agent_dir = agents_dir.join(agent_name);
     
-    if agent_dir.exists() {
-        return Err(CIError::AlreadyExists(format!(
-            "Agent '{}' already exists. Use 'ci agent info {}' to view details.",
-            agent_name, agent_name
+    if !agent_dir.is_dir() {
+        return Err(CIError::NotFound(format!(
+            "Agent '{}' not found. Use 'ci agent list' to see available agents.",
+            agent_name
         )).into());
     }
     
-    println!("{}", format!("Creating Agent: {}", agent_name).cyan().bold());
-    println!("{}", "=".repeat(30).cyan());
+    let output = matches.get_one::<String>("output")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(format!("{}.{}", agent_name, BUNDLE_EXTENSION)));
     
-    // Create directory structure
-    fs::create_dir_all(&agent_dir)?;
-    fs::create_dir_all(agent_dir.join("Sessions"))?;
-    println!("{} Created agent directory structure", "✓".green());
+    let report = validate_agent_dir(&agent_dir);
+    for error in &report.errors {
+        println!("{} {}", "warning:".yellow(), error);
+    }
     
-    // Create README.md
-    let readme_content = format!(
-        r#"# {}
-
-This agent is part of the Collaborative Intelligence system.
-
-## Capabilities
-
-- Add agent capabilities here
-
-## Usage
-
-To use this agent, type `{}` in a Claude Code session.
-
-## Sessions
-
-Session records are stored in the Sessions directory.
-"#,
-        agent_name, agent_name
-    );
+    let manifest = export_agent(&agent_dir, &output, matches.get_flag("include-sessions"))?;
     
-    fs::write(agent_dir.join("README.md"), readme_content)?;
-    println!("{} Created README.md documentation", "✓".green());
+    println!("{} Exported {} ({} files) to {}",
+        "✓".green(),
+        agent_name.bold(),
+        manifest.files.len(),
+        output.display().to_string().cyan()
+    );
+    println!("  checksum: {}", manifest.checksum.dimmed());
+    println!("Import it with: ci agent import {}", output.display());
     
-    // Create MEMORY.md
-    let memory_content = format!(
-        r#"# {} Memory
-
-This file stores the long-term memory for the {} agent.
-
-## Core Knowledge
-
-- Agent Name: {}
-- Creation Date: {}
-- Primary Function: [Define primary function]
-
-## System Integration
-
-The {} agent is part of the Collaborative Intelligence ecosystem and follows the standard agent communication protocols.
+    Ok(())
+}
 
-## Expertise
+fn agent_import(matches: &ArgMatches) -> Result<()> {
+    let bundle_path = PathBuf::from(matches.get_one::<String>("bundle").unwrap());
+    let agents_dir = get_agents_dir()?;
+    
+    let bundle = open_bundle(&bundle_path)
+        .map_err(|e| CIError::InvalidArgument(format!("{:#}", e)))?;
+    let manifest = &bundle.manifest;
+    
+    println!("{} {} {}", "Importing".cyan().bold(), manifest.agent.bold(),
+        format!("(v{}, {} files, exported {})", manifest.version, manifest.files.len(), manifest.created_at).dimmed());
+    println!("{} Checksum verified", "✓".green());
+    
+    for warning in &bundle.validation.warnings {
+        println!("    {} {}", "warning:".yellow(), warning);
+    }
+    if !bundle.validation.is_valid() {
+        for error in &bundle.validation.errors {
+            println!("    {} {}", "error:".red(), error);
+        }
+        if !matches.get_flag("force") {
+            return Err(CIError::Agent(format!(
+                "Bundled agent '{}' failed validation (use --force to import anyway)", manifest.agent
+            )).into());
+        }
+    }
+    
+    let target_name = matches.get_one::<String>("as").cloned().unwrap_or_else(|| manifest.agent.clone());
+    let mut strategy = matches.get_one::<String>("as").map(|name| ConflictStrategy::Rename(name.clone()));
+    
+    if agents_dir.join(&target_name).exists() {
+        let choice = match matches.get_one::<String>("on-conflict") {
+            Some(choice) => choice.clone(),
+            None => CommandHelpers::prompt_input(
+                &format!("Agent '{}' already exists: [r]ename, [o]verwrite, [m]erge or [c]ancel", target_name),
+                Some("c")
+            )?,
+        };
+        
+        strategy = match choice.to_lowercase().as_str() {
+            "r" | "rename" => {
+                let new_name = CommandHelpers::prompt_input("New agent name", Some(&format!("{}Imported", target_name)))?;
+                Some(ConflictStrategy::Rename(new_name))
+            },
+            "o" | "overwrite" => Some(ConflictStrategy::Overwrite),
+            "m" | "merge" => Some(ConflictStrategy::Merge),
+            _ => {
+                println!("Import cancelled");
+                return Ok(());
+            }
+        };
+        
+        // Keep the local memory recoverable before it is replaced or merged into
+        if matches!(strategy, Some(ConflictStrategy::Overwrite | ConflictStrategy::Merge)) {
+            MemoryHistory::new(&agents_dir).snapshot(&target_name, "before import")?;
+        }
+    }
+    
+    let outcome = install_bundle(&bundle, &agents_dir, strategy.as_ref())
+        .map_err(|e| CIError::AlreadyExists(format!("{:#}", e)))?;
+    
+    if !outcome.added.is_empty() {
+        println!("{} Added {} file(s)", "✓".green(), outcome.added.len());
+    }
+    if !outcome.replaced.is_empty() {
+        println!("{} Replaced {} file(s)", "✓".green(), outcome.replaced.len());
+    }
+    for conflict in &outcome.conflicts {
+        println!("{} {} differs locally; incoming version saved as {}.incoming", "!".yellow(), conflict, conflict);
+    }
+    
+    let description = if manifest.description.is_empty() {
+        "Imported agent".to_string()
+    } else {
+        manifest.description.clone()
+    };
+    if register_agent_in_agents_md(&outcome.agent_name, &description, &manifest.capabilities)? {
+        println!("{} Registered {} in AGENTS.md", "✓".green(), outcome.agent_name.bold());
+    }
+    
+    println!();
+    println!("{} Agent '{}' imported to {}", "✓".green().bold(), outcome.agent_name, outcome.agent_dir.display());
+    
+    Ok(())
+}
 
-[Define areas of expertise]
-"#,
-        agent_name,
-        agent_name,
-        agent_name,
-        chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
-        agent_name
+/// Add an entry for an agent to the AGENTS.md registry.
+/// Returns false when AGENTS.md is missing or already lists the agent.
+fn register_agent_in_agents_md(agent_name: &str, role: &str, capabilities: &[String]) -> Result<bool> {
+    let agents_md = get_ci_root()?.join("AGENTS.md");
+    if !agents_md.exists() {
+        return Ok(false);
+    }
+    
+    let content = fs::read_to_string(&agents_md)?;
+    let already_listed = content.lines()
+        .filter_map(|line| line.strip_prefix("### "))
+        .any(|heading| heading.split(" - ").next().unwrap_or_default().trim().eq_ignore_ascii_case(agent_name));
+    if already_listed {
+        return Ok(false);
+    }
+    
+    let capabilities = if capabilities.is_empty() {
+        "Add agent capabilities here".to_string()
+    } else {
+        capabilities.join(", ")
+    };
+    let entry = format!(
+        "### {}\n- **Role**: {}\n- **Capabilities**: {}\n- **Usage**: Type `{}` in Claude Code\n\n",
+        agent_name, role, capabilities, agent_name
     );
     
-    fs::write(agent_dir.join("MEMORY.md"), memory_content)?;
-    println!("{} Created MEMORY.md file", "✓".green());
+    // Keep the closing "Using Agents" section last
+    let updated = match content.find("## Using Agents") {
+        Some(index) => format!("{}{}{}", &content[..index], entry, &content[index..]),
+        None => format!("{}\n\n{}", content.trim_end(), entry.trim_end()) + "\n",
+    };
     
-    // Create ContinuousLearning.md
-    let learning_content = format!(
-        r#"# {} Continuous Learning
+    fs::write(&agents_md, updated)?;
+    Ok(true)
+}
 
-This file documents the learning progress of the {} agent.
+fn agent_suggest(matches: &ArgMatches) -> Result<()> {
+    let task = matches.get_many::<String>("task")
+        .unwrap()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let top = *matches.get_one::<usize>("top").unwrap();
+    let agents_dir = get_agents_dir()?;
+    
+    let ranked = rank_agents(&agents_dir, &task)?;
+    if ranked.is_empty() {
+        println!("No agent matches \"{}\". Add capabilities to agent.toml manifests to improve matching.", task);
+        return Ok(());
+    }
+    
+    println!("{} {}", "Agents for:".cyan().bold(), task);
+    println!("{}", "=".repeat(50).cyan());
+    print_matches(&ranked, top);
+    
+    println!();
+    println!("Suggested team: {}", suggested_team(&ranked, 3).join(", ").green().bold());
+    println!("Load it with: {}", format!("ci load --for \"{}\"", task).cyan());
+    
+    Ok(())
+}
 
-## Learning Record
+fn agent_memory(matches: &ArgMatches) -> Result<()> {
+    let (action, sub_matches) = matches.subcommand()
+        .ok_or_else(|| CIError::InvalidArgument("No memory subcommand provided".to_string()))?;
+    let agent_name = sub_matches.get_one::<String>("agent_name").unwrap();
+    let agents_dir = get_agents_dir()?;
+    
+    if !agents_dir.join(agent_name).is_dir() {
+        return Err(CIError::NotFound(format!(
+            "Agent '{}' not found. Use 'ci agent list' to see available agents.",
+            agent_name
+        )).into());
+    }
+    
+    let history = MemoryHistory::new(&agents_dir);
+    
+    match action {
+        "log" => {
+            let snapshots = history.log(agent_name)?;
+            if snapshots.is_empty() {
+                println!("No memory snapshots for {} yet. They are recorded on 'ci load' and when sessions end.", agent_name.bold());
+                return Ok(());
+            }
+            
+            let limit = sub_matches.get_one::<usize>("limit").copied().unwrap_or(snapshots.len());
+            println!("{} {}", "Memory history:".cyan().bold(), agent_name.bold());
+            println!("{}", "=".repeat(50).cyan());
+            for snapshot in snapshots.iter().rev().take(limit) {
+                let timestamp = chrono::DateTime::parse_from_rfc3339(&snapshot.timestamp)
+                    .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|_| snapshot.timestamp.clone());
+                println!("{}  {}  {}  {}",
+                    snapshot.id.yellow(),
+                    timestamp,
+                    snapshot.reason,
+                    format!("({})", snapshot.files.keys().cloned().collect::<Vec<_>>().join(", ")).dimmed()
+                );
+            }
+        },
+        "diff" => {
+            let snapshots = history.log(agent_name)?;
+            let from = match sub_matches.get_one::<String>("from") {
+                Some(id) => history.find(agent_name, id)?,
+                None => snapshots.last().cloned().ok_or_else(|| CIError::NotFound(
+                    format!("No memory snapshots for {} yet", agent_name)
+                ))?,
+            };
+            let to = sub_matches.get_one::<String>("to")
+                .map(|id| history.find(agent_name, id))
+                .transpose()?;
+            let to_side = to.as_ref().map(DiffSide::Snapshot).unwrap_or(DiffSide::WorkingCopy);
+            
+            let diff = history.diff(agent_name, DiffSide::Snapshot(&from), to_side)?;
+            if diff.is_empty() {
+                println!("No differences");
+            }
+            for line in diff.lines() {
+                if line.starts_with("+++") || line.starts_with("---") {
+                    println!("{}", line.bold());
+                } else if line.starts_with('+') {
+                    println!("{}", line.green());
+                } else if line.starts_with('-') {
+                    println!("{}", line.red());
+                } else if line.starts_with("@@") {
+                    println!("{}", line.cyan());
+                } else {
+                    println!("{}", line);
+                }
+            }
+        },
+        "revert" => {
+            let snapshot = history.find(agent_name, sub_matches.get_one::<String>("snapshot").unwrap())?;
+            if !sub_matches.get_flag("yes") && !CommandHelpers::prompt_confirmation(&format!(
+                "Restore {} memory files from snapshot {}?", agent_name, snapshot.id
+            )) {
+                println!("Revert cancelled");
+                return Ok(());
+            }
+            
+            let restored = history.revert(agent_name, &snapshot)?;
+            if restored.is_empty() {
+                println!("{} Memory files already match snapshot {}", "✓".green(), snapshot.id);
+            } else {
+                println!("{} Restored {} from snapshot {}", "✓".green(), restored.join(", ").bold(), snapshot.id.yellow());
+                println!("  The previous version was recorded and can be restored from 'ci agent memory log {}'", agent_name);
+            }
+        },
+        "snapshot" => match history.snapshot(agent_name, "manual")? {
+            Some(snapshot) => println!("{} Recorded snapshot {}", "✓".green(), snapshot.id.yellow()),
+            None => println!("No changes since the last snapshot"),
+        },
+        _ => unreachable!(),
+    }
+    
+    Ok(())
+}
 
-### {}
+fn create_agent(matches: &ArgMatches) -> Result<()> {
+    let agent_name = matches.get_one::<String>("agent_name").unwrap();
+    let template_name = matches.get_one::<String>("template")
+        .map(String::as_str)
+        .unwrap_or(DEFAULT_TEMPLATE);
+    
+    create_agent_with_template(agent_name, template_name, matches, matches.get_flag("enable"))
+}
 
-- Agent created
-- Initial memory structure established
-- Basic capabilities defined
-"#,
-        agent_name,
-        agent_name,
-        chrono::Utc::now().format("%Y-%m-%d")
-    );
+/// Scaffold a new agent from a template; `matches` supplies `--var` and `--vars-file`
+fn create_agent_with_template(agent_name: &str, template_name: &str, matches: &ArgMatches, enable_after: bool) -> Result<()> {
+    let agents_dir = get_agents_dir()?;
+    let agent_dir = agents_dir.join(agent_name);
+    
+    if agent_dir.exists() {
+        return Err(CIError::AlreadyExists(format!(
+            "Agent '{}' already exists. Use 'ci agent info {}' to view details.",
+            agent_name, agent_name
+        )).into());
+    }
+    
+    let template = find_template(template_name)?;
+    let vars = resolve_template_vars(&template, matches)?;
+    let files = template.render(agent_name, &vars)?;
+    
+    println!("{}", format!("Creating Agent: {}", agent_name).cyan().bold());
+    println!("{}", "=".repeat(30).cyan());
+    if template.name != DEFAULT_TEMPLATE {
+        println!("Template: {}", template.name.cyan());
+    }
+    
+    // Create directory structure
+    fs::create_dir_all(&agent_dir)?;
+    fs::create_dir_all(agent_dir.join("Sessions"))?;
+    println!("{} Created agent directory structure", "✓".green());
     
-    fs::write(agent_dir.join("ContinuousLearning.md"), learning_content)?;
-    println!("{} Created ContinuousLearning.md file", "✓".green());
+    for (path, content) in &files {
+        let target = agent_dir.join(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, content)
+            .with_context(|| format!("Failed to write {}", target.display()))?;
+        println!("{} Created {}", "✓".green(), path.display());
+    }
     
     println!();
     println!("{} Agent '{}' created successfully", "✓".green().bold(), agent_name);
@@ -691,6 +1321,43 @@ This file documents the learning progress of the {} agent.
     Ok(())
 }
 
+/// Collect template variables: --vars-file, then --var, then prompts for anything still missing
+fn resolve_template_vars(template: &AgentTemplate, matches: &ArgMatches) -> Result<HashMap<String, String>> {
+    let mut vars = HashMap::new();
+    if let Some(path) = matches.get_one::<String>("vars-file") {
+        vars.extend(load_vars_file(Path::new(path))?);
+    }
+    if let Some(pairs) = matches.get_many::<(String, String)>("var") {
+        vars.extend(pairs.cloned());
+    }
+    
+    let interactive = std::io::stdin().is_terminal();
+    for variable in template.missing_variables(&vars) {
+        if !interactive {
+            if variable.required && variable.default.is_none() {
+                return Err(CIError::InvalidArgument(format!(
+                    "Template '{}' needs variable '{}'. Pass --var {}=<value> or --vars-file.",
+                    template.name, variable.name, variable.name
+                )).into());
+            }
+            continue;
+        }
+        
+        let prompt = if variable.description.is_empty() {
+            variable.name.clone()
+        } else {
+            format!("{} ({})", variable.description, variable.name)
+        };
+        let value = CommandHelpers::prompt_input(&prompt, variable.default.as_deref())?;
+        if value.is_empty() && variable.required {
+            return Err(CIError::InvalidArgument(format!("Template variable '{}' is required", variable.name)).into());
+        }
+        vars.insert(variable.name.clone(), value);
+    }
+    
+    Ok(vars)
+}
+
 fn agent_enable(matches: &ArgMatches) -> Result<()> {
     let agent_name = matches.get_one::<String>("agent_name").unwrap();
     let agents_dir = get_agents_dir()?;
@@ -1008,10 +1675,7 @@ fn display_loaded_agent_memory_with_mode(agent_name: &str, memory_content: &str,
 }
 
 fn launch_claude_code_with_agent(agent_name: &str, memory_content: &str, free_mode: bool) {
-    use crate::helpers::CommandHelpers;
-    use std::process::Command;
-    
-    // Ask user if they want to launch Claude Code (skip if free mode)
+    // Ask user if they want to launch the assistant (skip if free mode)
     let should_launch = if free_mode {
         true // Always launch in free mode
     } else {
@@ -1020,14 +1684,22 @@ fn launch_claude_code_with_agent(agent_name: &str, memory_content: &str, free_mo
         false
     };
     
+    let launcher = match configured_launcher() {
+        Ok(launcher) => launcher,
+        Err(e) => {
+            println!("{} {}", "✗".red(), e);
+            return;
+        }
+    };
+    
     if should_launch {
-        // Check if claude CLI is available
-        if has_claude_cli() {
+        // Check if the configured assistant is available
+        if launcher.is_available() {
             if free_mode {
-                println!("Launching Claude Code in FREE mode with {}...", agent_name.cyan().bold());
+                println!("Launching {} in FREE mode with {}...", launcher.display_name(), agent_name.cyan().bold());
                 println!("{} Permissions will be bypassed for streamlined workflow", "⚡".yellow());
             } else {
-                println!("Launching Claude Code with {}...", agent_name.cyan().bold());
+                println!("Launching {} with {}...", launcher.display_name(), agent_name.cyan().bold());
             }
             
             // Create a temporary file with the memory content
@@ -1036,59 +1708,22 @@ fn launch_claude_code_with_agent(agent_name: &str, memory_content: &str, free_mo
             
             match std::fs::write(&temp_file, memory_content) {
                 Ok(_) => {
-                    let status = if free_mode {
-                        // Launch with auto-accept in free mode
-                        Command::new("cat")
-                            .arg(&temp_file)
-                            .stdout(std::process::Stdio::piped())
-                            .spawn()
-                            .and_then(|output| {
-                                let mut cmd = Command::new("claude");
-                                cmd.arg("code")
-                                   .arg("--dangerously-skip-permissions")
-                                   .stdin(output.stdout.unwrap());
-                                
-                                // Pass through BRAIN environment variables
-                                if let Ok(brain_path) = std::env::var("CI_BRAIN_PATH") {
-                                    cmd.env("CI_BRAIN_PATH", brain_path);
-                                }
-                                if let Ok(brain_available) = std::env::var("CI_BRAIN_AVAILABLE") {
-                                    cmd.env("CI_BRAIN_AVAILABLE", brain_available);
-                                }
-                                
-                                cmd.status()
-                            })
-                    } else {
-                        // Normal launch without auto-accept
-                        Command::new("cat")
-                            .arg(&temp_file)
-                            .stdout(std::process::Stdio::piped())
-                            .spawn()
-                            .and_then(|output| {
-                                let mut cmd = Command::new("claude");
-                                cmd.arg("code")
-                                   .stdin(output.stdout.unwrap());
-                                
-                                // Pass through BRAIN environment variables
-                                if let Ok(brain_path) = std::env::var("CI_BRAIN_PATH") {
-                                    cmd.env("CI_BRAIN_PATH", brain_path);
-                                }
-                                if let Ok(brain_available) = std::env::var("CI_BRAIN_AVAILABLE") {
-                                    cmd.env("CI_BRAIN_AVAILABLE", brain_available);
-                                }
-                                
-                                cmd.status()
-                            })
+                    let request = LaunchRequest {
+                        agent_name: agent_name.to_string(),
+                        memory_file: Some(temp_file.clone()),
+                        memory_via_stdin: true,
+                        bypass_permissions: free_mode,
+                        ..Default::default()
                     };
-                        
-                    match status {
-                        Ok(exit_status) => {
-                            if !exit_status.success() {
-                                println!("{} Claude Code exited with a non-zero status", "⚠".yellow());
+                    
+                    match launcher.launch(&request) {
+                        Ok(outcome) => {
+                            if !outcome.success {
+                                println!("{} {} exited with a non-zero status", "⚠".yellow(), launcher.display_name());
                             }
                         }
                         Err(e) => {
-                            println!("{} Failed to launch Claude Code: {}", "⚠".yellow(), e);
+                            println!("{} {}", "⚠".yellow(), e);
                             println!("{} Try running: ci load {}", "💡".blue(), agent_name);
                         }
                     }
@@ -1102,39 +1737,117 @@ fn launch_claude_code_with_agent(agent_name: &str, memory_content: &str, free_mo
                 }
             }
         } else {
-            println!("{} Claude CLI not found. Install it first or try: ci load {}", "⚠".yellow(), agent_name);
+            println!("{} {} not found. Install it first or try: ci load {}", "⚠".yellow(), launcher.display_name(), agent_name);
         }
     } else {
-        println!("{} To launch Claude Code later, run: ci load {}", "💡".blue(), agent_name);
+        println!("{} To launch {} later, run: ci load {}", "💡".blue(), launcher.display_name(), agent_name);
     }
 }
 
-fn has_claude_cli() -> bool {
-    std::process::Command::new("which")
-        .arg("claude")
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+fn agent_template(matches: &ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("list", _)) => template_list(),
+        Some(("show", sub_matches)) => template_show(sub_matches.get_one::<String>("template_name").unwrap()),
+        Some(("new", sub_matches)) => template_new(sub_matches),
+        _ => create_agent_from_template(matches),
+    }
 }
 
-fn create_agent_from_template(matches: &ArgMatches) -> Result<()> {
-    let template_name = matches.get_one::<String>("template_name").unwrap();
-    let agent_name = matches.get_one::<String>("agent_name");
+fn template_list() -> Result<()> {
+    let templates = list_templates()?;
+    
+    println!("{}", "Agent Templates".cyan().bold());
+    println!("{}", "=".repeat(30).cyan());
+    for template in &templates {
+        let source = match &template.source {
+            TemplateSource::BuiltIn => "built-in".dimmed(),
+            TemplateSource::User(_) => "user".yellow(),
+        };
+        println!("  {:<14} {:<9} {}", template.name.green(), source, template.description);
+    }
+    
+    if let Some(dir) = user_templates_dir() {
+        println!();
+        println!("User templates: {}", dir.display().to_string().dimmed());
+    }
+    println!("Create an agent: ci agent create <name> --template <template> [--var key=value]");
+    
+    Ok(())
+}
+
+fn template_show(name: &str) -> Result<()> {
+    let template = find_template(name)?;
+    
+    println!("{}", format!("Template: {}", template.name).cyan().bold());
+    println!("{}", "=".repeat(30).cyan());
+    println!("{}", template.description);
+    match &template.source {
+        TemplateSource::BuiltIn => println!("Source: built-in"),
+        TemplateSource::User(path) => println!("Source: {}", path.display()),
+    }
     
-    println!("{}", format!("Creating Agent from Template: {}", template_name).cyan().bold());
+    println!();
+    println!("{}", "Variables:".bold());
+    println!("  {:<14} {}", "agent_name", "Name of the new agent (always set)".dimmed());
+    for variable in &template.variables {
+        let detail = match (&variable.default, variable.required) {
+            (Some(default), _) => format!("default: {:?}", default),
+            (None, true) => "required".to_string(),
+            (None, false) => "optional".to_string(),
+        };
+        println!("  {:<14} {} {}", variable.name, variable.description, format!("({})", detail).dimmed());
+    }
     
-    // For now, just create a basic agent
-    // In the future, this would use actual templates
-    let name = agent_name.map(|s| s.as_str()).unwrap_or(template_name);
+    let undeclared = undeclared_variables(&template);
+    if !undeclared.is_empty() {
+        println!("  {} Used but not declared: {}", "⚠".yellow(), undeclared.join(", "));
+    }
     
-    // Create a basic agent (this would be enhanced with actual template system)
-    println!("Template system not yet implemented.");
-    println!("Creating basic agent instead: {}", name);
+    println!();
+    println!("{}", "Files:".bold());
+    for (path, content) in &template.files {
+        println!("  {} {}", path, format!("({} lines)", content.lines().count()).dimmed());
+    }
     
-    // This would call create_agent with the template applied
     Ok(())
 }
 
+fn template_new(matches: &ArgMatches) -> Result<()> {
+    let name = matches.get_one::<String>("template_name").unwrap();
+    let from = matches.get_one::<String>("from").unwrap();
+    
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err(CIError::InvalidArgument(format!("Invalid template name '{}'", name)).into());
+    }
+    
+    let templates_dir = user_templates_dir()
+        .ok_or_else(|| CIError::Configuration("Could not determine config directory".to_string()))?;
+    let template_dir = templates_dir.join(name);
+    if template_dir.exists() {
+        return Err(CIError::AlreadyExists(format!("Template '{}' already exists at {}", name, template_dir.display())).into());
+    }
+    
+    let base = find_template(from)?;
+    let description = matches.get_one::<String>("description")
+        .cloned()
+        .unwrap_or_else(|| format!("Custom template based on {}", base.name));
+    base.write_to(&template_dir, &description)?;
+    
+    println!("{} Created template '{}' from '{}'", "✓".green(), name, base.name);
+    println!("  {}", template_dir.display());
+    println!();
+    println!("Edit {} to declare variables, and use {{{{variable}}}} or {{{{#if variable}}}}...{{{{/if}}}} in files.", "template.toml".cyan());
+    
+    Ok(())
+}
+
+fn create_agent_from_template(matches: &ArgMatches) -> Result<()> {
+    let template_name = matches.get_one::<String>("template_name").unwrap();
+    let agent_name = matches.get_one::<String>("agent_name").unwrap_or(template_name);
+    
+    create_agent_with_template(agent_name, template_name, matches, false)
+}
+
 fn deploy_ci_globally(matches: &ArgMatches) -> Result<()> {
     let force = matches.get_flag("force");
     let backup = matches.get_flag("backup");
@@ -1423,16 +2136,22 @@ fn display_voice_mode_agent_memory(agent_name: &str, memory_content: &str) {
 }
 
 fn launch_claude_code_voice_mode(agent_name: &str, memory_content: &str) {
-    use std::process::Command;
+    let launcher = match configured_launcher() {
+        Ok(launcher) => launcher,
+        Err(e) => {
+            println!("{} {}", "✗".red(), e);
+            return;
+        }
+    };
     
-    // Check if claude CLI is available
-    if !has_claude_cli() {
-        println!("{} Claude CLI not found. Please install it first.", "✗".red());
+    // Check if the configured assistant is available
+    if !launcher.is_available() {
+        println!("{} {} not found. Please install it first.", "✗".red(), launcher.display_name());
         println!("{} Install with: npm install -g @anthropic-ai/claude-cli", "💡".blue());
         return;
     }
     
-    println!("{} Launching Claude Code in VOICE MODE...", "🚀".green().bold());
+    println!("{} Launching {} in VOICE MODE...", "🚀".green().bold(), launcher.display_name());
     
     // Create a temporary file with the memory content
     let temp_dir = std::env::temp_dir();
@@ -1441,35 +2160,22 @@ fn launch_claude_code_voice_mode(agent_name: &str, memory_content: &str) {
     match std::fs::write(&temp_file, memory_content) {
         Ok(_) => {
             // Launch with auto-accept directly, no confirmation
-            let status = Command::new("cat")
-                .arg(&temp_file)
-                .stdout(std::process::Stdio::piped())
-                .spawn()
-                .and_then(|output| {
-                    let mut cmd = Command::new("claude");
-                    cmd.arg("code")
-                       .arg("--dangerously-skip-permissions")
-                       .stdin(output.stdout.unwrap());
-                    
-                    // Pass through BRAIN environment variables
-                    if let Ok(brain_path) = std::env::var("CI_BRAIN_PATH") {
-                        cmd.env("CI_BRAIN_PATH", brain_path);
-                    }
-                    if let Ok(brain_available) = std::env::var("CI_BRAIN_AVAILABLE") {
-                        cmd.env("CI_BRAIN_AVAILABLE", brain_available);
-                    }
-                    
-                    cmd.status()
-                });
-                
-            match status {
-                Ok(exit_status) => {
-                    if !exit_status.success() {
-                        println!("{} Claude Code exited with a non-zero status", "⚠".yellow());
+            let request = LaunchRequest {
+                agent_name: agent_name.to_string(),
+                memory_file: Some(temp_file.clone()),
+                memory_via_stdin: true,
+                bypass_permissions: true,
+                ..Default::default()
+            };
+            
+            match launcher.launch(&request) {
+                Ok(outcome) => {
+                    if !outcome.success {
+                        println!("{} {} exited with a non-zero status", "⚠".yellow(), launcher.display_name());
                     }
                 }
                 Err(e) => {
-                    println!("{} Failed to launch Claude Code: {}", "✗".red(), e);
+                    println!("{} {}", "✗".red(), e);
                 }
             }
             
@@ -1480,4 +2186,4 @@ fn launch_claude_code_voice_mode(agent_name: &str, memory_content: &str) {
             println!("{} Failed to create temporary file: {}", "✗".red(), e);
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,96 @@
+//! Coordination commands for parallel agent sessions
+//!
+//! `ci coord send|read|board <session>` exchange typed messages between the
+//! instances of a `ci load --parallel` session and the people supervising it.
+
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::helpers::coordination::{CoordMessage, Mailboxes, MessageKind, BOARD_FILE};
+use crate::helpers::CommandHelpers;
+use crate::CoordCommands;
+
+pub async fn handle_coord_command(command: &CoordCommands, config: &Config) -> Result<()> {
+    match command {
+        CoordCommands::Send { session_id, kind, message, from, to, paths, resolves } => {
+            let mailboxes = Mailboxes::new(&coordination_dir(session_id, config)?);
+            let mut coord_message = CoordMessage::new(*kind, from, message);
+            coord_message.to = to.clone();
+            coord_message.paths = paths.clone();
+            coord_message.resolves = resolves.clone();
+
+            let recipients = mailboxes.send(&coord_message)?;
+            CommandHelpers::print_success(&format!(
+                "Sent {} {} to {}",
+                kind.to_string().cyan(),
+                coord_message.id.dimmed(),
+                if recipients.is_empty() { "nobody (no other instances)".to_string() } else { recipients.join(", ") }
+            ));
+            Ok(())
+        },
+        CoordCommands::Read { session_id, instance, all, json } => {
+            let mailboxes = Mailboxes::new(&coordination_dir(session_id, config)?);
+            let messages = mailboxes.read(instance, *all)?;
+
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&messages)?);
+                return Ok(());
+            }
+            if messages.is_empty() {
+                CommandHelpers::print_info(&format!("No {}messages for {}", if *all { "" } else { "new " }, instance));
+                return Ok(());
+            }
+            for message in &messages {
+                print_message(message);
+            }
+            Ok(())
+        },
+        CoordCommands::Board { session_id, json } => {
+            let coordination_dir = coordination_dir(session_id, config)?;
+            let board = Mailboxes::new(&coordination_dir).board()?;
+
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&board)?);
+            } else {
+                println!("{}", board.render());
+                println!("{}", format!("Agents read this board at {}", coordination_dir.join(BOARD_FILE).display()).dimmed());
+            }
+            Ok(())
+        },
+    }
+}
+
+fn coordination_dir(session_id: &str, config: &Config) -> Result<PathBuf> {
+    let dir = config.ci_path.join("AGENTS").join(session_id);
+    if !dir.is_dir() {
+        return Err(anyhow::anyhow!("Parallel session not found: {}", session_id));
+    }
+    Ok(dir)
+}
+
+fn print_message(message: &CoordMessage) {
+    let kind = match message.kind {
+        MessageKind::Blocker => message.kind.as_str().red().bold(),
+        MessageKind::Done => message.kind.as_str().green().bold(),
+        MessageKind::Claim | MessageKind::Handoff => message.kind.as_str().yellow().bold(),
+        MessageKind::Progress | MessageKind::Finding => message.kind.as_str().cyan().bold(),
+    };
+    println!("{} {} {} {}{}",
+        message.sent_at.format("%H:%M:%S").to_string().dimmed(),
+        kind,
+        message.from.bold(),
+        message.to.as_ref().map(|to| format!("→ {} ", to)).unwrap_or_default(),
+        format!("[{}]", message.id).dimmed()
+    );
+    if !message.body.is_empty() {
+        println!("   {}", message.body);
+    }
+    if !message.paths.is_empty() {
+        println!("   {} {}", "paths:".dimmed(), message.paths.join(", "));
+    }
+    if let Some(blocker) = &message.resolves {
+        println!("   {} {}", "resolves:".dimmed(), blocker);
+    }
+}
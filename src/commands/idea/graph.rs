@@ -0,0 +1,444 @@
+//! Idea relationship graph
+//!
+//! Ideas link to each other with typed edges stored on the source idea:
+//! `depends-on` (directed, must stay acyclic), `duplicates` and `relates`
+//! (both read as undirected). The legacy `related_ideas` list is shown as
+//! `relates` edges. The graph renders as a terminal dependency tree,
+//! Mermaid, Graphviz DOT or an HTML page through the visualize exporter.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use super::formatters::short_id;
+use super::Idea;
+use crate::helpers::parallel_report::escape_html;
+
+/// Kind of relationship between two ideas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkKind {
+    /// The source idea needs the target done first
+    DependsOn,
+    /// Both ideas describe the same thing
+    Duplicates,
+    /// Loosely connected ideas
+    Relates,
+}
+
+impl LinkKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LinkKind::DependsOn => "depends-on",
+            LinkKind::Duplicates => "duplicates",
+            LinkKind::Relates => "relates",
+        }
+    }
+
+    /// Whether `a kind b` also means `b kind a`
+    pub fn is_symmetric(&self) -> bool {
+        !matches!(self, LinkKind::DependsOn)
+    }
+}
+
+impl FromStr for LinkKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "depends-on" | "depends_on" | "dependson" | "depends" => Ok(LinkKind::DependsOn),
+            "duplicates" | "duplicate" | "dup" => Ok(LinkKind::Duplicates),
+            "relates" | "related" | "relates-to" => Ok(LinkKind::Relates),
+            _ => Err(format!("Unknown link kind '{}' (use depends-on, duplicates or relates)", value)),
+        }
+    }
+}
+
+/// Typed edge from the idea holding it to `target`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdeaLink {
+    pub kind: LinkKind,
+    pub target: String,
+}
+
+/// Output of `ci idea graph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Tree,
+    Mermaid,
+    Dot,
+    Html,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "tree" | "terminal" => Ok(GraphFormat::Tree),
+            "mermaid" | "mmd" => Ok(GraphFormat::Mermaid),
+            "dot" | "graphviz" => Ok(GraphFormat::Dot),
+            "html" | "web" => Ok(GraphFormat::Html),
+            _ => Err(format!("Unknown graph format '{}' (use tree, mermaid, dot or html)", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: LinkKind,
+}
+
+/// Ideas and the links between them; edges to unknown ideas are dropped
+#[derive(Debug, Clone)]
+pub struct IdeaGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Chain of `depends-on` edges leading from `from` to `to`, if any
+pub fn dependency_path(ideas: &[Idea], from: &str, to: &str) -> Option<Vec<String>> {
+    let deps: HashMap<&str, Vec<&str>> = ideas.iter()
+        .map(|idea| (idea.id.as_str(), idea.links.iter()
+            .filter(|link| link.kind == LinkKind::DependsOn)
+            .map(|link| link.target.as_str())
+            .collect()))
+        .collect();
+
+    fn walk<'a>(deps: &HashMap<&'a str, Vec<&'a str>>, current: &'a str, to: &str,
+                seen: &mut HashSet<&'a str>, path: &mut Vec<String>) -> bool {
+        path.push(current.to_string());
+        if current == to {
+            return true;
+        }
+        if seen.insert(current) {
+            for next in deps.get(current).into_iter().flatten() {
+                if walk(deps, next, to, seen, path) {
+                    return true;
+                }
+            }
+        }
+        path.pop();
+        false
+    }
+
+    let mut path = Vec::new();
+    walk(&deps, from, to, &mut HashSet::new(), &mut path).then_some(path)
+}
+
+impl IdeaGraph {
+    pub fn build(ideas: &[Idea]) -> Self {
+        let known: HashSet<&str> = ideas.iter().map(|idea| idea.id.as_str()).collect();
+        let mut edges: Vec<GraphEdge> = Vec::new();
+
+        for idea in ideas {
+            let legacy = idea.related_ideas.iter().map(|target| IdeaLink { kind: LinkKind::Relates, target: target.clone() });
+            for link in idea.links.iter().cloned().chain(legacy) {
+                if !known.contains(link.target.as_str()) || link.target == idea.id {
+                    continue;
+                }
+                // Symmetric links stored on both ideas are drawn once
+                let duplicate = edges.iter().any(|edge| edge.kind == link.kind && (
+                    (edge.from == idea.id && edge.to == link.target) ||
+                    (link.kind.is_symmetric() && edge.from == link.target && edge.to == idea.id)));
+                if !duplicate {
+                    edges.push(GraphEdge { from: idea.id.clone(), to: link.target, kind: link.kind });
+                }
+            }
+        }
+
+        let nodes = ideas.iter()
+            .map(|idea| GraphNode { id: idea.id.clone(), title: idea.title.clone(), status: format!("{:?}", idea.status) })
+            .collect();
+        Self { nodes, edges }
+    }
+
+    fn node(&self, id: &str) -> Option<&GraphNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Dependency tree: ideas nothing depends on at the top, their
+    /// dependencies below, other links as annotations
+    pub fn render_tree(&self) -> String {
+        let linked: HashSet<&str> = self.edges.iter()
+            .flat_map(|edge| [edge.from.as_str(), edge.to.as_str()])
+            .collect();
+        let depended_on: HashSet<&str> = self.edges.iter()
+            .filter(|edge| edge.kind == LinkKind::DependsOn)
+            .map(|edge| edge.to.as_str())
+            .collect();
+
+        let mut out = String::new();
+        let mut printed = HashSet::new();
+        for node in self.nodes.iter().filter(|n| linked.contains(n.id.as_str()) && !depended_on.contains(n.id.as_str())) {
+            self.tree_node(&node.id, "", None, &mut printed, &mut out);
+        }
+        // Ideas caught in a dependency cycle have no root above them
+        for node in self.nodes.iter().filter(|n| linked.contains(n.id.as_str())) {
+            if !printed.contains(&node.id) {
+                self.tree_node(&node.id, "", None, &mut printed, &mut out);
+            }
+        }
+
+        let unlinked = self.nodes.len() - linked.len();
+        if unlinked > 0 {
+            out.push_str(&format!("({} unlinked ideas not shown)\n", unlinked));
+        }
+        out
+    }
+
+    fn tree_node(&self, id: &str, prefix: &str, branch: Option<bool>, printed: &mut HashSet<String>, out: &mut String) {
+        let Some(node) = self.node(id) else { return };
+        let (connector, child_prefix) = match branch {
+            None => (String::new(), String::new()),
+            Some(last) => (
+                format!("{}{}", prefix, if last { "└── " } else { "├── " }),
+                format!("{}{}", prefix, if last { "    " } else { "│   " }),
+            ),
+        };
+        let repeated = !printed.insert(id.to_string());
+        out.push_str(&format!("{}{} {} [{}]{}\n", connector, short_id(id), node.title, node.status,
+            if repeated { " (see above)" } else { "" }));
+        if repeated {
+            return;
+        }
+
+        for edge in self.edges.iter().filter(|e| e.kind != LinkKind::DependsOn && (e.from == id || e.to == id)) {
+            let other = if edge.from == id { &edge.to } else { &edge.from };
+            let title = self.node(other).map(|n| n.title.as_str()).unwrap_or("");
+            out.push_str(&format!("{}  {} {} {} {}\n", child_prefix,
+                if edge.kind == LinkKind::Duplicates { "≡" } else { "~" }, edge.kind.label(), short_id(other), title));
+        }
+
+        let deps: Vec<&GraphEdge> = self.edges.iter().filter(|e| e.kind == LinkKind::DependsOn && e.from == id).collect();
+        for (i, edge) in deps.iter().enumerate() {
+            self.tree_node(&edge.to, &child_prefix, Some(i + 1 == deps.len()), printed, out);
+        }
+    }
+
+    fn mermaid_id(id: &str) -> String {
+        format!("idea_{}", id.replace(|c: char| !c.is_ascii_alphanumeric(), "_"))
+    }
+
+    pub fn render_mermaid(&self) -> String {
+        let mut out = String::from("graph TD\n");
+        for node in &self.nodes {
+            out.push_str(&format!("    {}[\"{}<br/>{}\"]\n", Self::mermaid_id(&node.id),
+                node.title.replace('"', "#quot;"), node.status));
+        }
+        for edge in &self.edges {
+            let arrow = match edge.kind {
+                LinkKind::DependsOn => "-->|depends on|",
+                LinkKind::Duplicates => "-.-|duplicates|",
+                LinkKind::Relates => "---|relates|",
+            };
+            out.push_str(&format!("    {} {} {}\n", Self::mermaid_id(&edge.from), arrow, Self::mermaid_id(&edge.to)));
+        }
+        out
+    }
+
+    pub fn render_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut out = String::from("digraph ideas {\n    rankdir=LR;\n    node [shape=box, style=rounded];\n");
+        for node in &self.nodes {
+            out.push_str(&format!("    {} [label={}];\n", quote(&node.id),
+                quote(&format!("{}\n[{}]", node.title, node.status)).replace('\n', "\\n")));
+        }
+        for edge in &self.edges {
+            let style = match edge.kind {
+                LinkKind::DependsOn => "label=\"depends on\"",
+                LinkKind::Duplicates => "label=\"duplicates\", style=dashed, dir=none",
+                LinkKind::Relates => "label=\"relates\", style=dotted, dir=none",
+            };
+            out.push_str(&format!("    {} -> {} [{}];\n", quote(&edge.from), quote(&edge.to), style));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Inline SVG drawing, one column per dependency depth: an idea sits left
+    /// of the ideas it depends on, so the deepest dependencies end up on the right
+    pub fn render_svg(&self) -> String {
+        const WIDTH: usize = 220;
+        const HEIGHT: usize = 48;
+        const COLUMN: usize = 280;
+        const ROW: usize = 72;
+        const MARGIN: usize = 20;
+
+        // Longest depends-on chain above each idea; bounded by the node count in case of cycles
+        let mut depth: HashMap<&str, usize> = self.nodes.iter().map(|node| (node.id.as_str(), 0)).collect();
+        for _ in 0..self.nodes.len() {
+            let mut changed = false;
+            for edge in self.edges.iter().filter(|edge| edge.kind == LinkKind::DependsOn) {
+                let next = depth[edge.from.as_str()] + 1;
+                if next > depth[edge.to.as_str()] && next < self.nodes.len() {
+                    depth.insert(edge.to.as_str(), next);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut rows: HashMap<usize, usize> = HashMap::new();
+        let position: HashMap<&str, (usize, usize)> = self.nodes.iter()
+            .map(|node| {
+                let column = depth[node.id.as_str()];
+                let row = rows.entry(column).or_insert(0);
+                *row += 1;
+                (node.id.as_str(), (MARGIN + column * COLUMN, MARGIN + (*row - 1) * ROW))
+            })
+            .collect();
+        let width = MARGIN * 2 + rows.keys().max().map_or(0, |c| c * COLUMN) + WIDTH;
+        let height = MARGIN * 2 + rows.values().max().map_or(0, |r| (r - 1) * ROW) + HEIGHT;
+
+        let mut out = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n", width, height, width, height);
+        out.push_str("  <defs>\n");
+        out.push_str("    <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\"><path d=\"M0,0 L10,5 L0,10 z\" fill=\"#555\"/></marker>\n");
+        out.push_str("    <style>\n");
+        out.push_str("      .idea { fill: #f8f9fa; stroke: #555; stroke-width: 1.5; }\n");
+        out.push_str("      .title { font-family: 'Courier New', monospace; font-size: 13px; font-weight: bold; fill: #333; }\n");
+        out.push_str("      .status { font-family: 'Courier New', monospace; font-size: 11px; fill: #777; }\n");
+        out.push_str("      .link { stroke: #555; stroke-width: 1.5; fill: none; }\n");
+        out.push_str("    </style>\n");
+        out.push_str("  </defs>\n");
+
+        for edge in &self.edges {
+            let ((fx, fy), (tx, ty)) = (position[edge.from.as_str()], position[edge.to.as_str()]);
+            let (fy, ty) = (fy + HEIGHT / 2, ty + HEIGHT / 2);
+            let path = if fx == tx {
+                // Same column: bow out to the left of both boxes
+                format!("M{},{} C{},{} {},{} {},{}", fx, fy, fx.saturating_sub(40), fy, tx.saturating_sub(40), ty, tx, ty)
+            } else if fx < tx {
+                format!("M{},{} L{},{}", fx + WIDTH, fy, tx, ty)
+            } else {
+                format!("M{},{} L{},{}", fx, fy, tx + WIDTH, ty)
+            };
+            let style = match edge.kind {
+                LinkKind::DependsOn => " marker-end=\"url(#arrow)\"",
+                LinkKind::Duplicates => " stroke-dasharray=\"6,4\"",
+                LinkKind::Relates => " stroke-dasharray=\"2,3\"",
+            };
+            out.push_str(&format!("  <path class=\"link\" d=\"{}\"{}><title>{}</title></path>\n", path, style, edge.kind.label()));
+        }
+
+        for node in &self.nodes {
+            let (x, y) = position[node.id.as_str()];
+            let title: String = if node.title.chars().count() > 26 {
+                format!("{}…", node.title.chars().take(25).collect::<String>())
+            } else {
+                node.title.clone()
+            };
+            out.push_str(&format!("  <rect class=\"idea\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"6\"><title>{}</title></rect>\n",
+                x, y, WIDTH, HEIGHT, escape_html(&node.title)));
+            out.push_str(&format!("  <text class=\"title\" x=\"{}\" y=\"{}\">{}</text>\n", x + 10, y + 20, escape_html(&title)));
+            out.push_str(&format!("  <text class=\"status\" x=\"{}\" y=\"{}\">{} · {}</text>\n", x + 10, y + 38, short_id(&node.id), node.status));
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Body of the visualize HTML page: the graph as inline SVG and a table
+    /// of every idea's links
+    pub fn html_body(&self) -> String {
+        let mut out = String::from("<h2>Relationship Graph</h2>\n");
+        out.push_str(&format!("<div style=\"overflow-x: auto\">\n{}</div>\n", self.render_svg()));
+
+        out.push_str("<h2>Ideas</h2>\n<table>\n<tr><th>ID</th><th>Title</th><th>Status</th><th>Depends on</th><th>Duplicates</th><th>Relates</th></tr>\n");
+        for node in &self.nodes {
+            let cell = |kind: LinkKind| {
+                self.edges.iter()
+                    .filter(|e| e.kind == kind && (e.from == node.id || (kind.is_symmetric() && e.to == node.id)))
+                    .map(|e| if e.from == node.id { &e.to } else { &e.from })
+                    .map(|other| escape_html(self.node(other).map(|n| n.title.as_str()).unwrap_or(other)))
+                    .collect::<Vec<_>>()
+                    .join("<br/>")
+            };
+            out.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                short_id(&node.id), escape_html(&node.title), node.status,
+                cell(LinkKind::DependsOn), cell(LinkKind::Duplicates), cell(LinkKind::Relates)));
+        }
+        out.push_str("</table>\n");
+        out
+    }
+}
+
+/// Full id of the idea whose id starts with `prefix`
+pub fn resolve_id(ideas: &[Idea], prefix: &str) -> Result<String> {
+    let matches: Vec<&Idea> = ideas.iter().filter(|idea| idea.id.starts_with(prefix)).collect();
+    match matches.as_slice() {
+        [idea] => Ok(idea.id.clone()),
+        [] => Err(anyhow!("Idea not found with ID: {}", prefix)),
+        _ => Err(anyhow!("ID prefix '{}' matches {} ideas; use more characters", prefix, matches.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::idea::test_idea;
+
+    fn idea(id: &str, links: &[(LinkKind, &str)]) -> Idea {
+        Idea {
+            links: links.iter().map(|(kind, target)| IdeaLink { kind: *kind, target: target.to_string() }).collect(),
+            ..test_idea(id)
+        }
+    }
+
+    #[test]
+    fn test_dependency_path_detects_cycles() {
+        let ideas = vec![
+            idea("a", &[(LinkKind::DependsOn, "b")]),
+            idea("b", &[(LinkKind::DependsOn, "c"), (LinkKind::Relates, "a")]),
+            idea("c", &[]),
+        ];
+        // Linking c depends-on a would close a → b → c → a
+        assert_eq!(dependency_path(&ideas, "a", "c"), Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]));
+        assert_eq!(dependency_path(&ideas, "c", "a"), None);
+        assert!(resolve_id(&ideas, "b").is_ok());
+        assert!(resolve_id(&ideas, "z").is_err());
+    }
+
+    #[test]
+    fn test_renderers() {
+        let ideas = vec![
+            idea("a", &[(LinkKind::DependsOn, "b"), (LinkKind::Duplicates, "c"), (LinkKind::Relates, "gone")]),
+            idea("b", &[]),
+            idea("c", &[(LinkKind::Duplicates, "a")]),
+            idea("d", &[]),
+        ];
+        let graph = IdeaGraph::build(&ideas);
+        // The dangling link is dropped and the mirrored duplicate drawn once
+        assert_eq!(graph.edges.len(), 2);
+
+        let tree = graph.render_tree();
+        assert!(tree.starts_with("a Idea a [New]\n"));
+        assert!(tree.contains("└── b Idea b [New]"));
+        assert!(tree.contains("≡ duplicates c"));
+        assert!(tree.ends_with("(1 unlinked ideas not shown)\n"));
+
+        assert!(graph.render_mermaid().contains("idea_a -->|depends on| idea_b"));
+        assert!(graph.render_dot().contains("\"a\" -> \"c\" [label=\"duplicates\", style=dashed, dir=none];"));
+        let html = graph.html_body();
+        assert!(html.contains("<td>Idea c</td>"));
+        // Self-contained: drawn inline, nothing fetched when the page is opened
+        assert!(html.contains("<svg") && !html.contains("<script") && !html.contains("https://"));
+
+        // a depends on b, so b is drawn one column to the right
+        let svg = graph.render_svg();
+        assert!(svg.contains("<rect class=\"idea\" x=\"20\" y=\"20\""));
+        assert!(svg.contains("<rect class=\"idea\" x=\"300\" y=\"20\""));
+        assert!(svg.contains("marker-end=\"url(#arrow)\""));
+    }
+}
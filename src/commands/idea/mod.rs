@@ -0,0 +1,1320 @@
+//! Idea Management commands for CI
+//!
+//! This module provides commands for managing ideas, concepts, and inspirations,
+//! allowing users to capture, organize, and track creative thoughts.
+
+pub mod graph;
+mod scoring;
+mod store;
+mod sync;
+
+use anyhow::{Result, Context, anyhow};
+use chrono::{DateTime, Utc, Local};
+use colored::*;
+use serde::{Serialize, Deserialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::io::{self, Write};
+use uuid::Uuid;
+
+use crate::config::{CIConfig, Config, IdeaConfig, IdeaView, find_nearest_config};
+use crate::VisualizationTheme;
+use graph::{GraphFormat, IdeaGraph, IdeaLink, LinkKind};
+pub use scoring::ScoreInputs;
+use scoring::{RankFormat, ScoringModel};
+use store::{IdeaStore, StoreBackend, StoreScope};
+use sync::SyncState;
+
+/// Structure to represent an idea
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Idea {
+    /// Unique identifier for the idea
+    id: String,
+    
+    /// Title of the idea
+    title: String,
+    
+    /// Detailed description of the idea
+    description: String,
+    
+    /// Category or domain of the idea
+    category: String,
+    
+    /// Tags or keywords associated with the idea
+    tags: Vec<String>,
+    
+    /// Current status of the idea
+    status: IdeaStatus,
+    
+    /// Priority level of the idea
+    priority: IdeaPriority,
+    
+    /// Creation timestamp
+    created_at: DateTime<Utc>,
+    
+    /// Last update timestamp
+    updated_at: DateTime<Utc>,
+    
+    /// Related ideas (by ID)
+    related_ideas: Vec<String>,
+    
+    /// Typed links to other ideas
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    links: Vec<IdeaLink>,
+    
+    /// Optional scoring inputs (reach, impact, confidence, effort)
+    #[serde(flatten)]
+    scores: ScoreInputs,
+    
+    /// Notes or additional information
+    notes: String,
+}
+
+/// Idea with placeholder fields, for tests to adjust with struct update syntax
+#[cfg(test)]
+pub(super) fn test_idea(id: &str) -> Idea {
+    Idea {
+        id: id.to_string(),
+        title: format!("Idea {}", id),
+        description: String::new(),
+        category: "General".to_string(),
+        tags: Vec::new(),
+        status: IdeaStatus::New,
+        priority: IdeaPriority::Medium,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        related_ideas: Vec::new(),
+        links: Vec::new(),
+        scores: ScoreInputs::default(),
+        notes: String::new(),
+    }
+}
+
+/// Enumeration of possible idea statuses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdeaStatus {
+    /// New idea, not yet evaluated
+    New,
+    
+    /// Idea is being explored
+    Exploring,
+    
+    /// Idea is in development
+    InDevelopment,
+    
+    /// Idea is implemented
+    Implemented,
+    
+    /// Idea is on hold
+    OnHold,
+    
+    /// Idea is archived
+    Archived,
+    
+    /// Idea is rejected
+    Rejected,
+}
+
+impl IdeaStatus {
+    /// Whether the idea still belongs in the backlog
+    fn is_open(&self) -> bool {
+        !matches!(self, IdeaStatus::Implemented | IdeaStatus::Archived | IdeaStatus::Rejected)
+    }
+}
+
+/// Enumeration of possible idea priorities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum IdeaPriority {
+    /// Low priority
+    Low,
+    
+    /// Medium priority
+    Medium,
+    
+    /// High priority
+    High,
+    
+    /// Critical priority
+    Critical,
+}
+
+/// Implementation for idea management
+struct IdeaManager {
+    /// Whether this is the project or the global store
+    scope: StoreScope,
+    
+    /// Directory holding the idea store (`.ci` or the global data dir)
+    ideas_dir: PathBuf,
+
+    /// Backend the ideas are read from and written to
+    store: Box<dyn IdeaStore>,
+
+    /// Project config recording the store backend, when in a configured project
+    config_file: Option<PathBuf>,
+    
+    /// Idea settings of the project config
+    settings: IdeaConfig,
+}
+
+/// Whether `dir` is inside a git work tree
+fn in_git_repo(dir: &Path) -> bool {
+    std::process::Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(dir)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Directory of the user's global idea store
+fn global_ideas_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("ci")
+}
+
+impl IdeaManager {
+    /// Create a new idea manager
+    fn new(_config: &Config) -> Result<Self> {
+        // Determine ideas store location - prefer project-specific location if in a project
+        let current_dir = std::env::current_dir()?;
+        let scope = if in_git_repo(&current_dir) { StoreScope::Project } else { StoreScope::Global };
+        Self::open(scope, &current_dir)
+    }
+    
+    /// Manager of a specific store, as seen from the current directory
+    fn for_scope(scope: StoreScope) -> Result<Self> {
+        let current_dir = std::env::current_dir()?;
+        if scope == StoreScope::Project && !in_git_repo(&current_dir) {
+            return Err(anyhow!("Not inside a git repository, so there is no project idea store here"));
+        }
+        Self::open(scope, &current_dir)
+    }
+    
+    fn open(scope: StoreScope, current_dir: &Path) -> Result<Self> {
+        let ideas_dir = match scope {
+            StoreScope::Project => current_dir.join(".ci"),
+            StoreScope::Global => global_ideas_dir(),
+        };
+        
+        // Ensure the directory exists
+        fs::create_dir_all(&ideas_dir)?;
+        
+        // Projects may pin the backend in their config
+        let project_config = if scope == StoreScope::Project { find_nearest_config(current_dir) } else { None };
+        let settings = project_config.as_ref().map(|(_, config)| config.ideas.clone()).unwrap_or_default();
+        let backend = StoreBackend::select(&ideas_dir, settings.store.as_deref())?;
+        
+        Ok(Self {
+            scope,
+            store: backend.open(&ideas_dir),
+            ideas_dir,
+            config_file: project_config.map(|(path, _)| path),
+            settings,
+        })
+    }
+    
+    /// Sync state shared by this project's store and the global one
+    fn sync_state_path() -> Result<PathBuf> {
+        Ok(SyncState::path(&global_ideas_dir(), &std::env::current_dir()?))
+    }
+    
+    /// Move one idea from this store into `target`, returning it
+    fn move_idea(&self, id: &str, target: &IdeaManager) -> Result<Idea> {
+        let mut ideas = self.load_ideas()?;
+        let id = graph::resolve_id(&ideas, id)?;
+        let position = ideas.iter().position(|i| i.id == id).expect("resolved idea");
+        let idea = ideas.remove(position);
+        
+        let mut target_ideas = target.load_ideas()?;
+        target_ideas.retain(|i| i.id != id);
+        target_ideas.push(idea.clone());
+        target.save_ideas(&target_ideas)?;
+        self.save_ideas(&ideas)?;
+        
+        // Pin the idea to its new store so sync neither copies it back nor deletes it
+        let state_path = Self::sync_state_path()?;
+        let mut state = SyncState::load(&state_path)?;
+        state.versions.remove(&id);
+        state.pinned.insert(id, target.scope);
+        state.save(&state_path)?;
+        
+        Ok(idea)
+    }
+    
+    /// List all ideas
+    fn list_ideas(&self, filter: Option<&str>, category: Option<&str>, status: Option<IdeaStatus>) -> Result<Vec<Idea>> {
+        let ideas = self.load_ideas()?;
+        
+        let filtered_ideas = ideas.into_iter()
+            .filter(|idea| {
+                // Apply filters if provided
+                let matches_filter = filter.map_or(true, |f| {
+                    idea.title.to_lowercase().contains(&f.to_lowercase()) ||
+                    idea.description.to_lowercase().contains(&f.to_lowercase()) ||
+                    idea.tags.iter().any(|tag| tag.to_lowercase().contains(&f.to_lowercase()))
+                });
+                
+                let matches_category = category.map_or(true, |c| {
+                    idea.category.to_lowercase() == c.to_lowercase()
+                });
+                
+                let matches_status = status.map_or(true, |s| {
+                    idea.status == s
+                });
+                
+                matches_filter && matches_category && matches_status
+            })
+            .collect();
+            
+        Ok(filtered_ideas)
+    }
+    
+    /// Add a new idea
+    fn add_idea(&self, title: &str, description: &str, category: &str, tags: Vec<String>, scores: ScoreInputs) -> Result<Idea> {
+        scores.validate()?;
+        let mut ideas = self.load_ideas()?;
+        
+        // Create new idea
+        let now = Utc::now();
+        let new_idea = Idea {
+            id: Uuid::new_v4().to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            category: category.to_string(),
+            tags,
+            status: IdeaStatus::New,
+            priority: IdeaPriority::Medium,
+            created_at: now,
+            updated_at: now,
+            related_ideas: Vec::new(),
+            links: Vec::new(),
+            scores,
+            notes: String::new(),
+        };
+        
+        // Add to list
+        ideas.push(new_idea.clone());
+        
+        // Save ideas
+        self.save_ideas(&ideas)?;
+        
+        Ok(new_idea)
+    }
+    
+    /// View a specific idea
+    fn view_idea(&self, id: &str) -> Result<Idea> {
+        let ideas = self.load_ideas()?;
+        
+        // Find the idea
+        let idea = ideas.iter()
+            .find(|i| i.id == id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Idea not found with ID: {}", id))?;
+            
+        Ok(idea)
+    }
+    
+    /// Update an existing idea
+    fn update_idea(&self, id: &str, updates: IdeaUpdates) -> Result<Idea> {
+        let mut ideas = self.load_ideas()?;
+        
+        // Find the idea
+        let idea_index = ideas.iter()
+            .position(|i| i.id == id)
+            .ok_or_else(|| anyhow!("Idea not found with ID: {}", id))?;
+            
+        // Update the idea
+        let mut idea = ideas[idea_index].clone();
+        
+        if let Some(title) = updates.title {
+            idea.title = title;
+        }
+        
+        if let Some(description) = updates.description {
+            idea.description = description;
+        }
+        
+        if let Some(category) = updates.category {
+            idea.category = category;
+        }
+        
+        if let Some(tags) = updates.tags {
+            idea.tags = tags;
+        }
+        
+        if let Some(status) = updates.status {
+            idea.status = status;
+        }
+        
+        if let Some(priority) = updates.priority {
+            idea.priority = priority;
+        }
+        
+        if let Some(related_ideas) = updates.related_ideas {
+            idea.related_ideas = related_ideas;
+        }
+        
+        if let Some(notes) = updates.notes {
+            idea.notes = notes;
+        }
+        
+        if !updates.scores.is_empty() {
+            idea.scores.merge(updates.scores);
+            idea.scores.validate()?;
+        }
+        
+        // Update timestamps
+        idea.updated_at = Utc::now();
+        
+        // Replace in list
+        ideas[idea_index] = idea.clone();
+        
+        // Save ideas
+        self.save_ideas(&ideas)?;
+        
+        Ok(idea)
+    }
+    
+    /// Delete an idea
+    fn delete_idea(&self, id: &str) -> Result<()> {
+        let mut ideas = self.load_ideas()?;
+        
+        // Find the idea
+        let initial_count = ideas.len();
+        ideas.retain(|i| i.id != id);
+        
+        if ideas.len() == initial_count {
+            return Err(anyhow!("Idea not found with ID: {}", id));
+        }
+        
+        // Drop links pointing at the deleted idea
+        for idea in &mut ideas {
+            idea.links.retain(|link| link.target != id);
+            idea.related_ideas.retain(|related| related != id);
+        }
+        
+        // Save ideas
+        self.save_ideas(&ideas)?;
+        
+        Ok(())
+    }
+    
+    /// Link idea `from` to idea `to`; both may be given as unique ID prefixes
+    fn link_ideas(&self, from: &str, to: &str, kind: LinkKind) -> Result<(Idea, Idea)> {
+        let mut ideas = self.load_ideas()?;
+        let from = graph::resolve_id(&ideas, from)?;
+        let to = graph::resolve_id(&ideas, to)?;
+        
+        if from == to {
+            return Err(anyhow!("An idea cannot be linked to itself"));
+        }
+        
+        let linked = |ideas: &[Idea], a: &str, b: &str| ideas.iter()
+            .any(|i| i.id == a && i.links.iter().any(|l| l.kind == kind && l.target == b));
+        if linked(&ideas, &from, &to) || (kind.is_symmetric() && linked(&ideas, &to, &from)) {
+            return Err(anyhow!("Ideas {} and {} are already linked with {}", formatters::short_id(&from), formatters::short_id(&to), kind.label()));
+        }
+        
+        // A dependency back onto `from` would close a cycle
+        if kind == LinkKind::DependsOn {
+            if let Some(path) = graph::dependency_path(&ideas, &to, &from) {
+                let chain: Vec<&str> = path.iter().map(|id| formatters::short_id(id)).collect();
+                return Err(anyhow!("Linking would create a dependency cycle: {} → {}", formatters::short_id(&from), chain.join(" → ")));
+            }
+        }
+        
+        let source = ideas.iter_mut().find(|i| i.id == from).expect("resolved idea");
+        source.links.push(IdeaLink { kind, target: to.clone() });
+        source.updated_at = Utc::now();
+        self.save_ideas(&ideas)?;
+        
+        let find = |id: &str| ideas.iter().find(|i| i.id == id).cloned().expect("resolved idea");
+        Ok((find(&from), find(&to)))
+    }
+    
+    /// Remove the links between two ideas (of one kind, or all), returning how many were removed
+    fn unlink_ideas(&self, a: &str, b: &str, kind: Option<LinkKind>) -> Result<usize> {
+        let mut ideas = self.load_ideas()?;
+        let a = graph::resolve_id(&ideas, a)?;
+        let b = graph::resolve_id(&ideas, b)?;
+        let mut removed = 0;
+        
+        for idea in ideas.iter_mut().filter(|i| i.id == a || i.id == b) {
+            let other = if idea.id == a { &b } else { &a };
+            let before = idea.links.len() + idea.related_ideas.len();
+            // Directed links only count from `a` to `b`
+            idea.links.retain(|link| !(&link.target == other
+                && kind.is_none_or(|k| k == link.kind)
+                && (link.kind.is_symmetric() || idea.id == a)));
+            if kind.is_none_or(|k| k == LinkKind::Relates) {
+                idea.related_ideas.retain(|related| related != other);
+            }
+            let count = before - idea.links.len() - idea.related_ideas.len();
+            if count > 0 {
+                idea.updated_at = Utc::now();
+                removed += count;
+            }
+        }
+        
+        if removed == 0 {
+            return Err(anyhow!("No {} link found between {} and {}",
+                kind.map_or("", |k| k.label()), formatters::short_id(&a), formatters::short_id(&b)));
+        }
+        
+        self.save_ideas(&ideas)?;
+        Ok(removed)
+    }
+    
+    /// Saved view by name
+    fn view(&self, name: &str) -> Result<IdeaView> {
+        self.settings.views.get(name).cloned().ok_or_else(|| {
+            let known: Vec<&str> = self.settings.views.keys().map(|k| k.as_str()).collect();
+            anyhow!("No saved view '{}' (saved views: {})", name,
+                if known.is_empty() { "none".to_string() } else { known.join(", ") })
+        })
+    }
+    
+    /// Store a view in the project config, replacing one of the same name
+    fn save_view(&self, name: &str, view: IdeaView) -> Result<PathBuf> {
+        let path = self.config_file.clone()
+            .ok_or_else(|| anyhow!("Saving views needs a project .ci-config.json; run this inside a CI project"))?;
+        let mut config = CIConfig::from_file(&path)?;
+        config.ideas.views.insert(name.to_string(), view);
+        config.to_file(&path)?;
+        Ok(path)
+    }
+    
+    /// Load ideas from storage
+    fn load_ideas(&self) -> Result<Vec<Idea>> {
+        self.store.load()
+    }
+    
+    /// Save ideas to storage
+    fn save_ideas(&self, ideas: &[Idea]) -> Result<()> {
+        self.store.save(ideas)
+    }
+    
+    /// Move every idea to another backend, keeping the old store as a backup
+    fn migrate_store(&self, target: StoreBackend) -> Result<(usize, Option<PathBuf>)> {
+        if self.store.backend() == target {
+            return Err(anyhow!("Ideas are already stored as {}", target.label()));
+        }
+        
+        let target_store = target.open(&self.ideas_dir);
+        if target_store.location().exists() && !target_store.load()?.is_empty() {
+            return Err(anyhow!("{} already holds ideas; move it away before migrating",
+                target_store.location().display()));
+        }
+        
+        let count = store::copy_ideas(self.store.as_ref(), target_store.as_ref())?;
+        
+        // Set the old store aside rather than deleting it
+        let source = self.store.location();
+        let backup = if source.exists() {
+            let name = source.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let backup = source.with_file_name(format!("{}.bak-{}", name, Local::now().format("%Y%m%d%H%M%S")));
+            fs::rename(source, &backup)
+                .with_context(|| format!("Failed to move {} aside", source.display()))?;
+            Some(backup)
+        } else {
+            None
+        };
+        
+        // Pin the new backend for the project
+        if let Some(path) = &self.config_file {
+            let mut config = CIConfig::from_file(path)?;
+            config.ideas.store = Some(target.label().to_string());
+            config.to_file(path)?;
+        }
+        
+        Ok((count, backup))
+    }
+    
+    /// Get categories from existing ideas
+    fn get_categories(&self) -> Result<Vec<String>> {
+        let ideas = self.load_ideas()?;
+        
+        let mut categories: Vec<String> = ideas.iter()
+            .map(|i| i.category.clone())
+            .collect();
+            
+        // Remove duplicates
+        categories.sort();
+        categories.dedup();
+        
+        Ok(categories)
+    }
+    
+    /// Get tags from existing ideas
+    fn get_tags(&self) -> Result<Vec<String>> {
+        let ideas = self.load_ideas()?;
+        
+        let mut tags: Vec<String> = ideas.iter()
+            .flat_map(|i| i.tags.clone())
+            .collect();
+            
+        // Remove duplicates
+        tags.sort();
+        tags.dedup();
+        
+        Ok(tags)
+    }
+}
+
+/// Structure for idea updates
+struct IdeaUpdates {
+    title: Option<String>,
+    description: Option<String>,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+    status: Option<IdeaStatus>,
+    priority: Option<IdeaPriority>,
+    related_ideas: Option<Vec<String>>,
+    notes: Option<String>,
+    scores: ScoreInputs,
+}
+
+/// Helper functions to format idea data for display
+mod formatters {
+    use super::*;
+    
+    /// First eight characters of an id; hand-written ids may be shorter or not ASCII
+    pub fn short_id(id: &str) -> &str {
+        id.char_indices().nth(8).map_or(id, |(end, _)| &id[..end])
+    }
+    
+    pub fn format_status(status: IdeaStatus) -> String {
+        match status {
+            IdeaStatus::New => "New".blue().to_string(),
+            IdeaStatus::Exploring => "Exploring".cyan().to_string(),
+            IdeaStatus::InDevelopment => "In Development".yellow().to_string(),
+            IdeaStatus::Implemented => "Implemented".green().to_string(),
+            IdeaStatus::OnHold => "On Hold".magenta().to_string(),
+            IdeaStatus::Archived => "Archived".white().to_string(),
+            IdeaStatus::Rejected => "Rejected".red().to_string(),
+        }
+    }
+    
+    pub fn format_priority(priority: IdeaPriority) -> String {
+        match priority {
+            IdeaPriority::Low => "Low".normal().to_string(),
+            IdeaPriority::Medium => "Medium".yellow().to_string(),
+            IdeaPriority::High => "High".red().to_string(),
+            IdeaPriority::Critical => "Critical".red().bold().to_string(),
+        }
+    }
+    
+    pub fn format_date(date: DateTime<Utc>) -> String {
+        let local_time: DateTime<Local> = date.into();
+        local_time.format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+    
+    pub fn format_idea_short(idea: &Idea) -> String {
+        format!("{} [{}] {} - {}",
+            short_id(&idea.id).blue(),
+            format_status(idea.status),
+            idea.title.white().bold(),
+            idea.description.chars().take(50).collect::<String>()
+        )
+    }
+    
+    pub fn format_idea_detail(idea: &Idea) -> String {
+        let mut output = String::new();
+        
+        output.push_str(&format!("\n{} {}\n", "ID:".blue().bold(), idea.id));
+        output.push_str(&format!("{} {}\n", "Title:".blue().bold(), idea.title.white().bold()));
+        output.push_str(&format!("{} {}\n", "Category:".blue().bold(), idea.category.green()));
+        output.push_str(&format!("{} {}\n", "Status:".blue().bold(), format_status(idea.status)));
+        output.push_str(&format!("{} {}\n", "Priority:".blue().bold(), format_priority(idea.priority)));
+        output.push_str(&format!("{} {}\n", "Created:".blue().bold(), format_date(idea.created_at)));
+        output.push_str(&format!("{} {}\n", "Updated:".blue().bold(), format_date(idea.updated_at)));
+        
+        if !idea.tags.is_empty() {
+            output.push_str(&format!("{} {}\n", "Tags:".blue().bold(), 
+                idea.tags.iter().map(|t| t.yellow().to_string()).collect::<Vec<_>>().join(", ")
+            ));
+        }
+        
+        output.push_str(&format!("\n{}\n{}\n", "Description:".blue().bold(), idea.description));
+        
+        if !idea.notes.is_empty() {
+            output.push_str(&format!("\n{}\n{}\n", "Notes:".blue().bold(), idea.notes));
+        }
+        
+        if !idea.related_ideas.is_empty() {
+            output.push_str(&format!("\n{}\n", "Related Ideas:".blue().bold()));
+            for related in &idea.related_ideas {
+                output.push_str(&format!("  - {}\n", short_id(related).blue()));
+            }
+        }
+        
+        if !idea.scores.is_empty() {
+            let fmt = |label: &str, value: Option<f64>, unit: &str| value.map(|v| format!("{} {}{}", label, v, unit));
+            let parts: Vec<String> = [
+                fmt("reach", idea.scores.reach, ""),
+                fmt("impact", idea.scores.impact, ""),
+                fmt("confidence", idea.scores.confidence, "%"),
+                fmt("effort", idea.scores.effort, " pm"),
+            ].into_iter().flatten().collect();
+            output.push_str(&format!("{} {}\n", "Scoring:".blue().bold(), parts.join(" · ")));
+            for model in [ScoringModel::Rice, ScoringModel::Ice] {
+                if let Some(score) = model.score(&idea.scores) {
+                    output.push_str(&format!("{} {}\n", format!("{} score:", model.label()).blue().bold(), score.to_string().green().bold()));
+                }
+            }
+        }
+        
+        if !idea.links.is_empty() {
+            output.push_str(&format!("\n{}\n", "Links:".blue().bold()));
+            for link in &idea.links {
+                output.push_str(&format!("  - {} {}\n", link.kind.label().cyan(), short_id(&link.target).blue()));
+            }
+        }
+        
+        output
+    }
+}
+
+/// Parse idea status from string
+fn parse_status(status: &str) -> Result<IdeaStatus> {
+    match status.to_lowercase().as_str() {
+        "new" => Ok(IdeaStatus::New),
+        "exploring" => Ok(IdeaStatus::Exploring),
+        "development" | "indevelopment" | "in-development" | "in_development" => Ok(IdeaStatus::InDevelopment),
+        "implemented" | "complete" | "completed" | "done" => Ok(IdeaStatus::Implemented),
+        "onhold" | "on-hold" | "on_hold" | "hold" => Ok(IdeaStatus::OnHold),
+        "archived" | "archive" => Ok(IdeaStatus::Archived),
+        "rejected" | "reject" => Ok(IdeaStatus::Rejected),
+        _ => Err(anyhow!("Invalid status: {}. Valid options: new, exploring, development, implemented, onhold, archived, rejected", status)),
+    }
+}
+
+/// Parse idea priority from string
+fn parse_priority(priority: &str) -> Result<IdeaPriority> {
+    match priority.to_lowercase().as_str() {
+        "low" | "l" => Ok(IdeaPriority::Low),
+        "medium" | "med" | "m" => Ok(IdeaPriority::Medium),
+        "high" | "h" => Ok(IdeaPriority::High),
+        "critical" | "crit" | "c" => Ok(IdeaPriority::Critical),
+        _ => Err(anyhow!("Invalid priority: {}. Valid options: low, medium, high, critical", priority)),
+    }
+}
+
+/// Flags of `ci idea`; each subcommand reads the ones it needs
+#[derive(Debug, Default, Clone)]
+pub struct IdeaOptions<'a> {
+    /// Positional idea IDs or unique ID prefixes (for link, unlink)
+    pub ids: &'a [String],
+    pub title: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub category: Option<&'a str>,
+    pub tags: Option<&'a str>,
+    pub id: Option<&'a str>,
+    pub status: Option<&'a str>,
+    pub priority: Option<&'a str>,
+    pub filter: Option<&'a str>,
+    /// Target store backend (for migrate-store) or store scope (for move)
+    pub to: Option<&'a str>,
+    /// Link kind (for link, unlink)
+    pub kind: Option<&'a str>,
+    /// Graph output format (for graph)
+    pub format: Option<&'a str>,
+    /// Output file (for graph)
+    pub output: Option<&'a str>,
+    /// Color theme of the HTML graph
+    pub theme: Option<VisualizationTheme>,
+    /// Scoring inputs (for add, update)
+    pub scores: ScoreInputs,
+    /// Scoring model (for rank)
+    pub model: Option<&'a str>,
+    /// Saved view to apply (for list, rank)
+    pub view: Option<&'a str>,
+    /// Name to save the given filters under (for list, rank)
+    pub save_view: Option<&'a str>,
+    /// Maximum number of ranked ideas
+    pub limit: Option<usize>,
+    /// List both the project and the global store
+    pub all_stores: bool,
+    /// Report what sync would change without writing
+    pub dry_run: bool,
+    /// Pull ideas from other projects on sync as well
+    pub all: bool,
+}
+
+/// Idea management command entry point
+pub async fn idea(subcmd: &str, options: IdeaOptions<'_>, _config: &Config) -> Result<()> {
+    let IdeaOptions {
+        ids, title, description, category, tags, id, status, priority, filter, to, kind, format, output, theme,
+        scores, model, view, save_view, limit, all_stores, dry_run, all,
+    } = options;
+    
+    // Machine-readable output on stdout stays free of the banner
+    let raw_stdout = output.is_none() && match subcmd {
+        "graph" => format.is_some_and(|f| !f.eq_ignore_ascii_case("tree")),
+        "rank" => format.is_some_and(|f| !f.eq_ignore_ascii_case("table")),
+        _ => false,
+    };
+    
+    if !raw_stdout {
+        // Direct formatting to match CI format exactly
+        println!("{}", "💡 Idea Management".blue().bold());
+        println!("{}", "================".blue());
+        println!();
+        println!("🔍 {}", format!("Operation: {}", subcmd).cyan().bold());
+        println!();
+    }
+    
+    let idea_manager = IdeaManager::new(_config)?;
+    
+    // A saved view fills in the filters not given on the command line
+    let saved_view = match view {
+        Some(name) if matches!(subcmd, "list" | "rank") => idea_manager.view(name)?,
+        _ => IdeaView::default(),
+    };
+    let (category, status, filter) = if matches!(subcmd, "list" | "rank") {
+        (category.or(saved_view.category.as_deref()),
+         status.or(saved_view.status.as_deref()),
+         filter.or(saved_view.filter.as_deref()))
+    } else {
+        (category, status, filter)
+    };
+    let model = model.or(saved_view.model.as_deref());
+    let limit = limit.or(saved_view.limit);
+    
+    if let Some(name) = save_view.filter(|_| matches!(subcmd, "list" | "rank")) {
+        let new_view = IdeaView {
+            category: category.map(str::to_string),
+            status: status.map(str::to_string),
+            filter: filter.map(str::to_string),
+            model: model.map(str::to_string),
+            limit,
+        };
+        let path = idea_manager.save_view(name, new_view)?;
+        if !raw_stdout {
+            println!("💾 {}", format!("Saved view '{}' to {}", name, path.display()).green());
+            println!();
+        }
+    }
+    
+    match subcmd {
+        "list" => {
+            // Parse status filter if provided
+            let status_filter = if let Some(s) = status {
+                Some(parse_status(s)?)
+            } else {
+                None
+            };
+            
+            let managers = if all_stores {
+                let mut managers = vec![IdeaManager::for_scope(StoreScope::Global)?];
+                if in_git_repo(&std::env::current_dir()?) {
+                    managers.insert(0, IdeaManager::for_scope(StoreScope::Project)?);
+                }
+                managers
+            } else {
+                vec![idea_manager]
+            };
+            
+            // Ideas present in both stores get flagged
+            let stored: Vec<Vec<Idea>> = managers.iter().map(|m| m.load_ideas()).collect::<Result<_>>()?;
+            let in_every_store = |id: &str| stored.len() > 1 && stored.iter().all(|ideas| ideas.iter().any(|i| i.id == id));
+            
+            let mut idea_count = 0;
+            for manager in &managers {
+                // List ideas with optional filters
+                let ideas = manager.list_ideas(filter, category, status_filter)?;
+                
+                if all_stores {
+                    let icon = if manager.scope == StoreScope::Project { "📁" } else { "🌐" };
+                    println!("{} {} {}", icon, format!("{} store:", manager.scope.label()).blue().bold(),
+                        manager.store.location().display().to_string().dimmed());
+                    println!();
+                    if ideas.is_empty() {
+                        println!("  {}", "No ideas found".dimmed());
+                    }
+                } else if ideas.is_empty() {
+                    println!("{} {}", "ℹ️".blue(), "No ideas found".blue());
+                    return Ok(());
+                } else {
+                    // Group by category if requested
+                    println!("{}", "📋 Ideas Collection:".blue().bold());
+                    println!();
+                }
+                
+                idea_count += ideas.len();
+                
+                for idea in ideas {
+                    let marker = if in_every_store(&idea.id) { " (in both stores)".yellow().to_string() } else { String::new() };
+                    println!("  {}{}", formatters::format_idea_short(&idea), marker);
+                }
+                
+                if all_stores {
+                    println!();
+                }
+            }
+            
+            if !all_stores {
+                println!();
+            }
+            println!("{} {}", "✅".green(), format!("Listed {} ideas", idea_count).green().bold());
+        },
+        "add" => {
+            // Validate required parameters
+            let title = title.ok_or_else(|| anyhow!("Title is required for adding an idea"))?;
+            let description = description.unwrap_or("");
+            let category = category.unwrap_or("Uncategorized");
+            
+            println!("📝 {}", "Adding new idea...".yellow());
+            
+            // Parse tags
+            let tags_vec = if let Some(t) = tags {
+                t.split(',')
+                    .map(|tag| tag.trim().to_string())
+                    .filter(|tag| !tag.is_empty())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            
+            // Add the idea
+            let new_idea = idea_manager.add_idea(title, description, category, tags_vec, scores)?;
+            
+            println!();
+            println!("{}", "📋 Idea Details:".blue().bold());
+            println!("{}", formatters::format_idea_detail(&new_idea));
+            println!();
+            println!("{} {}", "✅".green(), format!("Added new idea: {}", title).green().bold());
+        },
+        "view" => {
+            // Validate ID
+            let id = id.ok_or_else(|| anyhow!("ID is required for viewing an idea"))?;
+            
+            println!("🔍 {}", format!("Retrieving idea {}...", formatters::short_id(id).blue()).yellow());
+            
+            // View the idea
+            let idea = idea_manager.view_idea(id)?;
+            
+            println!();
+            println!("{}", "📋 Idea Details:".blue().bold());
+            println!("{}", formatters::format_idea_detail(&idea));
+            println!();
+            println!("{} {}", "✅".green(), "Idea details displayed".green().bold());
+        },
+        "update" => {
+            // Validate ID
+            let id = id.ok_or_else(|| anyhow!("ID is required for updating an idea"))?;
+            
+            println!("🔄 {}", format!("Updating idea {}...", formatters::short_id(id).blue()).yellow());
+            
+            // Build updates
+            let mut updates = IdeaUpdates {
+                title: title.map(|s| s.to_string()),
+                description: description.map(|s| s.to_string()),
+                category: category.map(|s| s.to_string()),
+                tags: None,
+                status: None,
+                priority: None,
+                related_ideas: None,
+                notes: None,
+                scores,
+            };
+            
+            // Parse tags if provided
+            if let Some(t) = tags {
+                updates.tags = Some(
+                    t.split(',')
+                        .map(|tag| tag.trim().to_string())
+                        .filter(|tag| !tag.is_empty())
+                        .collect()
+                );
+            }
+            
+            // Parse status if provided
+            if let Some(s) = status {
+                updates.status = Some(parse_status(s)?);
+            }
+            
+            // Parse priority if provided
+            if let Some(p) = priority {
+                updates.priority = Some(parse_priority(p)?);
+            }
+            
+            // Update the idea
+            let updated_idea = idea_manager.update_idea(id, updates)?;
+            
+            println!();
+            println!("{}", "📋 Updated Idea:".blue().bold());
+            println!("{}", formatters::format_idea_detail(&updated_idea));
+            println!();
+            println!("{} {}", "✅".green(), format!("Updated idea: {}", updated_idea.title).green().bold());
+        },
+        "delete" => {
+            // Validate ID
+            let id = id.ok_or_else(|| anyhow!("ID is required for deleting an idea"))?;
+            
+            println!("⚠️  {}", format!("Preparing to delete idea {}...", formatters::short_id(id).blue()).yellow());
+            
+            // Confirm deletion
+            print!("{}️ Are you sure you want to delete this idea? (y/N): ", "⚠️".yellow());
+            io::stdout().flush().ok();
+            
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            
+            if !input.trim().eq_ignore_ascii_case("y") && !input.trim().eq_ignore_ascii_case("yes") {
+                println!("{} {}", "ℹ️".blue(), "Deletion cancelled".blue());
+                return Ok(());
+            }
+            
+            println!("🗑️  {}", "Deleting idea...".yellow());
+            
+            // Delete the idea
+            idea_manager.delete_idea(id)?;
+            
+            println!();
+            println!("{} {}", "✅".green(), format!("Deleted idea with ID: {}", id).green().bold());
+        },
+        "categories" => {
+            // List all categories
+            println!("🔍 {}", "Retrieving idea categories...".yellow());
+            
+            let categories = idea_manager.get_categories()?;
+            
+            if categories.is_empty() {
+                println!("{} {}", "ℹ️".blue(), "No categories found");
+                return Ok(());
+            }
+            
+            println!();
+            println!("{}", "📊 Categories:".blue().bold());
+            println!();
+            
+            let category_count = categories.len();
+            
+            for category in categories {
+                println!("  - {}", category.green());
+            }
+            
+            println!();
+            println!("{} {}", "✅".green(), format!("Listed {} categories", category_count).green().bold());
+        },
+        "tags" => {
+            // List all tags
+            println!("🔍 {}", "Retrieving idea tags...".yellow());
+            
+            let tags = idea_manager.get_tags()?;
+            
+            if tags.is_empty() {
+                println!("{} {}", "ℹ️".blue(), "No tags found");
+                return Ok(());
+            }
+            
+            println!();
+            println!("{}", "🏷️  Tags:".blue().bold());
+            println!();
+            
+            let tag_count = tags.len();
+            
+            for tag in tags {
+                println!("  - {}", tag.yellow());
+            }
+            
+            println!();
+            println!("{} {}", "✅".green(), format!("Listed {} tags", tag_count).green().bold());
+        },
+        "link" | "unlink" => {
+            let [from, target] = ids else {
+                return Err(anyhow!("Usage: ci idea {} <id> <id> [--kind depends-on|duplicates|relates]", subcmd));
+            };
+            let kind = kind.map(|k| k.parse::<LinkKind>().map_err(|e| anyhow!(e))).transpose()?;
+            
+            if subcmd == "link" {
+                let kind = kind.unwrap_or(LinkKind::Relates);
+                println!("🔗 {}", format!("Linking {} {} {}...", from.blue(), kind.label(), target.blue()).yellow());
+                
+                let (source, dest) = idea_manager.link_ideas(from, target, kind)?;
+                
+                println!();
+                println!("  {} {} {}", source.title.white().bold(), kind.label().cyan(), dest.title.white().bold());
+                println!();
+                println!("{} {}", "✅".green(), "Ideas linked".green().bold());
+            } else {
+                println!("✂️  {}", format!("Unlinking {} and {}...", from.blue(), target.blue()).yellow());
+                
+                let removed = idea_manager.unlink_ideas(from, target, kind)?;
+                
+                println!();
+                println!("{} {}", "✅".green(), format!("Removed {} links", removed).green().bold());
+            }
+        },
+        "graph" => {
+            let format: GraphFormat = format.unwrap_or("tree").parse().map_err(|e: String| anyhow!(e))?;
+            let graph = IdeaGraph::build(&idea_manager.load_ideas()?);
+            
+            let rendered = match format {
+                GraphFormat::Tree => graph.render_tree(),
+                GraphFormat::Mermaid => graph.render_mermaid(),
+                GraphFormat::Dot => graph.render_dot(),
+                GraphFormat::Html => crate::commands::visualize::WebExporter::new(theme.unwrap_or(VisualizationTheme::Light))
+                    .generate_idea_graph_html(&graph),
+            };
+            
+            match output {
+                Some(path) => {
+                    fs::write(path, &rendered)
+                        .with_context(|| format!("Failed to write idea graph to {}", path))?;
+                    println!("{} {}", "✅".green(), format!("Wrote idea graph to {}", path).green().bold());
+                },
+                None if format == GraphFormat::Tree && graph.edges.is_empty() => {
+                    println!("{} {}", "ℹ️".blue(), "No linked ideas; use ci idea link <id> <id> --kind ...".blue());
+                },
+                None if format != GraphFormat::Tree => print!("{}", rendered),
+                None => {
+                    println!("{}", "🕸️  Idea Graph:".blue().bold());
+                    println!();
+                    print!("{}", rendered);
+                },
+            }
+        },
+        "rank" => {
+            let model: ScoringModel = model.or(idea_manager.settings.scoring.as_deref()).unwrap_or("rice")
+                .parse().map_err(|e: String| anyhow!(e))?;
+            let format: RankFormat = format.unwrap_or("table").parse().map_err(|e: String| anyhow!(e))?;
+            let status_filter = status.map(parse_status).transpose()?;
+            
+            let mut ideas = idea_manager.list_ideas(filter, category, status_filter)?;
+            // The backlog leaves closed ideas out unless a status is asked for
+            if status_filter.is_none() {
+                ideas.retain(|idea| idea.status.is_open());
+            }
+            
+            let mut ranked = scoring::rank(&ideas, model);
+            if let Some(limit) = limit {
+                ranked.truncate(limit);
+            }
+            
+            let rendered = match format {
+                RankFormat::Table => None,
+                RankFormat::Csv => Some(scoring::render_csv(&ranked)),
+                RankFormat::Json => Some(scoring::render_json(&ranked)?),
+            };
+            
+            match (rendered, output) {
+                (Some(rendered), Some(path)) => {
+                    fs::write(path, rendered)
+                        .with_context(|| format!("Failed to write ranked ideas to {}", path))?;
+                    println!("{} {}", "✅".green(), format!("Wrote {} ranked ideas to {}", ranked.len(), path).green().bold());
+                },
+                (Some(rendered), None) => print!("{}", rendered),
+                (None, Some(_)) => return Err(anyhow!("--output needs --format csv or json")),
+                (None, None) if ranked.is_empty() => {
+                    println!("{} {}", "ℹ️".blue(), "No open ideas found".blue());
+                },
+                (None, None) => {
+                    println!("{}", format!("📈 Backlog by {} score:", model.label()).blue().bold());
+                    println!();
+                    println!("  {}", format!("{:>3}  {:<8}  {:<32} {:>8} {:>6} {:>5} {:>6} {:>9}",
+                        "#", "ID", "Title", "Reach", "Impact", "Conf", "Effort", model.label()).bold());
+                    
+                    let number = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string());
+                    for row in &ranked {
+                        let title: String = if row.title.chars().count() > 32 {
+                            format!("{}…", row.title.chars().take(31).collect::<String>())
+                        } else {
+                            row.title.clone()
+                        };
+                        let score = row.score.map(|s| s.to_string().green().bold().to_string())
+                            .unwrap_or_else(|| "unscored".dimmed().to_string());
+                        println!("  {:>3}  {:<8}  {:<32} {:>8} {:>6} {:>5} {:>6} {:>9}",
+                            row.rank.map(|r| r.to_string()).unwrap_or_else(|| "-".to_string()),
+                            formatters::short_id(&row.id), title,
+                            number(row.inputs.reach), number(row.inputs.impact),
+                            number(row.inputs.confidence.map(|c| c.round())), number(row.inputs.effort), score);
+                    }
+                    
+                    println!();
+                    match model {
+                        ScoringModel::Rice => println!("  {}", "RICE = reach × impact × confidence% ÷ effort".dimmed()),
+                        ScoringModel::Ice => println!("  {}", "ICE = impact × confidence% ÷ effort".dimmed()),
+                    }
+                    println!();
+                    println!("{} {}", "✅".green(), format!("Ranked {} ideas", ranked.len()).green().bold());
+                },
+            }
+        },
+        "views" => {
+            if idea_manager.settings.views.is_empty() {
+                println!("{} {}", "ℹ️".blue(), "No saved views; save one with ci idea list|rank ... --save-view <name>".blue());
+                return Ok(());
+            }
+            
+            println!("{}", "👁️  Saved Views:".blue().bold());
+            println!();
+            for (name, saved) in &idea_manager.settings.views {
+                let parts: Vec<String> = [
+                    saved.category.as_ref().map(|c| format!("category={}", c)),
+                    saved.status.as_ref().map(|s| format!("status={}", s)),
+                    saved.filter.as_ref().map(|f| format!("filter={}", f)),
+                    saved.model.as_ref().map(|m| format!("model={}", m)),
+                    saved.limit.map(|l| format!("limit={}", l)),
+                ].into_iter().flatten().collect();
+                println!("  - {}: {}", name.cyan(), if parts.is_empty() { "(all ideas)".to_string() } else { parts.join(", ") });
+            }
+            println!();
+            println!("{} {}", "✅".green(), format!("Listed {} views", idea_manager.settings.views.len()).green().bold());
+        },
+        "move" => {
+            let id = ids.first().map(|s| s.as_str()).or(id)
+                .ok_or_else(|| anyhow!("Usage: ci idea move <id> --to global|project"))?;
+            let target: StoreScope = to
+                .ok_or_else(|| anyhow!("--to is required for moving an idea (global, project)"))?
+                .parse()
+                .map_err(|e: String| anyhow!(e))?;
+            
+            let source = IdeaManager::for_scope(target.other())?;
+            let destination = IdeaManager::for_scope(target)?;
+            
+            println!("🚚 {}", format!("Moving idea {} from the {} store to the {} store...",
+                id.blue(), source.scope.label(), target.label()).yellow());
+            
+            let moved = source.move_idea(id, &destination)?;
+            
+            println!();
+            println!("  {}: {}", "Idea".bold(), moved.title.white().bold());
+            println!("  {}: {}", "Now in".bold(), destination.store.location().display());
+            println!();
+            println!("{} {}", "✅".green(), format!("Moved idea to the {} store", target.label()).green().bold());
+        },
+        "sync" => {
+            let project = IdeaManager::for_scope(StoreScope::Project)?;
+            let global = IdeaManager::for_scope(StoreScope::Global)?;
+            let state_path = IdeaManager::sync_state_path()?;
+            let base = SyncState::load(&state_path)?;
+            
+            println!("🔄 {}", format!("Syncing {} with {}...",
+                project.store.location().display(), global.store.location().display()).yellow());
+            if let Some(synced_at) = base.synced_at {
+                println!("   {}", format!("Last synced {}", formatters::format_date(synced_at)).dimmed());
+            }
+            println!();
+            
+            let outcome = sync::merge(&project.load_ideas()?, &global.load_ideas()?, &base, all);
+            
+            for (label, count) in [
+                ("Added to project", outcome.added_to_project),
+                ("Added to global", outcome.added_to_global),
+                ("Updated in project", outcome.updated_project),
+                ("Updated in global", outcome.updated_global),
+                ("Deleted from project", outcome.deleted_from_project),
+                ("Deleted from global", outcome.deleted_from_global),
+            ] {
+                if count > 0 {
+                    println!("  {}: {}", label.bold(), count);
+                }
+            }
+            if outcome.left_in_global > 0 {
+                println!("  {}", format!("{} ideas from other projects stay in the global store; use --all to pull them",
+                    outcome.left_in_global).dimmed());
+            }
+            
+            if !outcome.conflicts.is_empty() {
+                println!();
+                println!("{}", format!("⚠️  {} conflicts (changed in both stores; the later edit was kept):", outcome.conflicts.len()).yellow().bold());
+                let stamp = |date: Option<DateTime<Utc>>| date.map(formatters::format_date).unwrap_or_else(|| "deleted".to_string());
+                for conflict in &outcome.conflicts {
+                    println!("  - {} {}", formatters::short_id(&conflict.id).blue(), conflict.title.white().bold());
+                    println!("      project: {}  global: {}  kept: {}",
+                        stamp(conflict.project_updated), stamp(conflict.global_updated), conflict.kept.label().green());
+                    println!("      {}", conflict.detail.dimmed());
+                }
+            }
+            
+            println!();
+            if dry_run {
+                println!("{} {}", "ℹ️".blue(), "Dry run: no store was changed".blue());
+            } else {
+                project.save_ideas(&outcome.project)?;
+                global.save_ideas(&outcome.global)?;
+                outcome.state(&std::env::current_dir()?).save(&state_path)?;
+                
+                if outcome.is_noop() {
+                    println!("{} {}", "✅".green(), "Stores already in sync".green().bold());
+                } else {
+                    println!("{} {}", "✅".green(), format!("Synced {} ideas", outcome.project.len().max(outcome.global.len())).green().bold());
+                }
+            }
+        },
+        "migrate-store" => {
+            let target: StoreBackend = to
+                .ok_or_else(|| anyhow!("--to is required for migrating the idea store (json, markdown)"))?
+                .parse()
+                .map_err(|e: String| anyhow!(e))?;
+            
+            println!("📦 {}", format!("Migrating ideas from {} to {}...",
+                idea_manager.store.backend().label(), target.label()).yellow());
+            
+            let (count, backup) = idea_manager.migrate_store(target)?;
+            
+            println!();
+            println!("  {}: {}", "Store".bold(), target.open(&idea_manager.ideas_dir).location().display());
+            if let Some(backup) = backup {
+                println!("  {}: {}", "Previous store kept at".bold(), backup.display());
+            }
+            if let Some(config_file) = &idea_manager.config_file {
+                println!("  {}: {}", "Backend recorded in".bold(), config_file.display());
+            }
+            println!();
+            println!("{} {}", "✅".green(), format!("Migrated {} ideas to the {} store", count, target.label()).green().bold());
+        },
+        _ => {
+            println!("{}", "❌ Unknown Subcommand".red().bold());
+            println!("{}", "===================".red());
+            println!();
+            println!("The subcommand '{}' is not recognized.", subcmd.red().bold());
+            println!();
+            println!("{} {}:", "💡".cyan(), "Available subcommands".bold());
+            println!("  - {}: List all ideas", "list".cyan());
+            println!("  - {}: Add a new idea", "add".cyan());
+            println!("  - {}: View idea details", "view".cyan());
+            println!("  - {}: Update an existing idea", "update".cyan());
+            println!("  - {}: Delete an idea", "delete".cyan());
+            println!("  - {}: List idea categories", "categories".cyan());
+            println!("  - {}: List idea tags", "tags".cyan());
+            println!("  - {}: Move ideas to another store backend (--to json|markdown)", "migrate-store".cyan());
+            println!("  - {}: Link two ideas (--kind depends-on|duplicates|relates)", "link".cyan());
+            println!("  - {}: Remove links between two ideas", "unlink".cyan());
+            println!("  - {}: Show the idea graph (--format tree|mermaid|dot|html)", "graph".cyan());
+            println!("  - {}: Rank ideas by RICE/ICE score (--format table|csv|json)", "rank".cyan());
+            println!("  - {}: List saved filter views", "views".cyan());
+            println!("  - {}: Move an idea to the other store (--to global|project)", "move".cyan());
+            println!("  - {}: Merge the project and global stores", "sync".cyan());
+            
+            return Err(anyhow!("Unknown idea subcommand: {}", subcmd));
+        }
+    }
+    
+    Ok(())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_short_ids_are_listed_without_truncation() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join(store::MARKDOWN_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("x9.md"), "---\nid: x9\ntitle: Dark mode\nstatus: New\npriority: Low\ncategory: UI\ntags: []\n\
+            created_at: 2024-05-01T10:00:00Z\nupdated_at: 2024-05-01T10:00:00Z\nrelated_ideas: [y1]\nnotes: ''\n---\n\nFollow the OS setting.\n").unwrap();
+
+        let ideas = StoreBackend::Markdown.open(temp_dir.path()).load().unwrap();
+        let short = formatters::format_idea_short(&ideas[0]);
+        assert!(short.contains("x9"));
+        assert!(short.contains("Dark mode"));
+        assert!(formatters::format_idea_detail(&ideas[0]).contains("y1"));
+        assert_eq!(formatters::short_id("0123456789abcdef"), "01234567");
+        assert_eq!(formatters::short_id("überidée-2024"), "überidée");
+    }
+}
@@ -0,0 +1,249 @@
+//! Idea scoring and ranking
+//!
+//! Ideas may carry RICE inputs: reach (people or events per period), impact
+//! (0.25 minimal, 0.5 low, 1 medium, 2 high, 3 massive), confidence (percent)
+//! and effort (person-months). RICE is reach × impact × confidence / effort;
+//! ICE leaves reach out for ideas whose audience is hard to size. `ci idea
+//! rank` orders ideas by score, and ideas missing an input of the chosen
+//! model follow the scored ones, ordered by priority.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::Idea;
+
+/// Optional scoring inputs stored on an idea
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreInputs {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reach: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impact: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effort: Option<f64>,
+}
+
+impl ScoreInputs {
+    pub fn is_empty(&self) -> bool {
+        *self == ScoreInputs::default()
+    }
+
+    /// Take every input set in `other`
+    pub fn merge(&mut self, other: ScoreInputs) {
+        self.reach = other.reach.or(self.reach);
+        self.impact = other.impact.or(self.impact);
+        self.confidence = other.confidence.or(self.confidence);
+        self.effort = other.effort.or(self.effort);
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if [self.reach, self.impact, self.confidence, self.effort].into_iter().flatten().any(|v| !v.is_finite()) {
+            return Err(anyhow!("Scoring inputs must be finite numbers"));
+        }
+        if self.reach.is_some_and(|r| r < 0.0) {
+            return Err(anyhow!("Reach cannot be negative"));
+        }
+        if self.impact.is_some_and(|i| i <= 0.0) {
+            return Err(anyhow!("Impact must be positive (0.25 minimal … 3 massive)"));
+        }
+        if self.confidence.is_some_and(|c| !(0.0..=100.0).contains(&c)) {
+            return Err(anyhow!("Confidence is a percentage between 0 and 100"));
+        }
+        if self.effort.is_some_and(|e| e <= 0.0) {
+            return Err(anyhow!("Effort must be positive (person-months)"));
+        }
+        Ok(())
+    }
+}
+
+/// Formula turning inputs into a score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoringModel {
+    Rice,
+    Ice,
+}
+
+impl ScoringModel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScoringModel::Rice => "RICE",
+            ScoringModel::Ice => "ICE",
+        }
+    }
+
+    /// Score of `inputs`, or None when an input of the model is missing
+    pub fn score(&self, inputs: &ScoreInputs) -> Option<f64> {
+        let base = inputs.impact? * inputs.confidence? / 100.0 / inputs.effort?;
+        let score = match self {
+            ScoringModel::Rice => inputs.reach? * base,
+            ScoringModel::Ice => base,
+        };
+        Some((score * 100.0).round() / 100.0)
+    }
+}
+
+impl FromStr for ScoringModel {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "rice" => Ok(ScoringModel::Rice),
+            "ice" => Ok(ScoringModel::Ice),
+            _ => Err(format!("Unknown scoring model '{}' (use rice or ice)", value)),
+        }
+    }
+}
+
+/// Output of `ci idea rank`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for RankFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "table" | "terminal" => Ok(RankFormat::Table),
+            "csv" => Ok(RankFormat::Csv),
+            "json" => Ok(RankFormat::Json),
+            _ => Err(format!("Unknown rank format '{}' (use table, csv or json)", value)),
+        }
+    }
+}
+
+/// One row of the ranked backlog
+#[derive(Debug, Clone, Serialize)]
+pub struct RankedIdea {
+    /// Position among scored ideas; unscored ideas have none
+    pub rank: Option<usize>,
+    pub id: String,
+    pub title: String,
+    pub category: String,
+    pub status: String,
+    pub priority: String,
+    #[serde(flatten)]
+    pub inputs: ScoreInputs,
+    pub model: &'static str,
+    pub score: Option<f64>,
+}
+
+/// Ideas by descending score, ties and unscored ideas by priority
+pub fn rank(ideas: &[Idea], model: ScoringModel) -> Vec<RankedIdea> {
+    let mut scored: Vec<(&Idea, Option<f64>)> = ideas.iter()
+        .map(|idea| (idea, model.score(&idea.scores)))
+        .collect();
+    scored.sort_by(|(a, sa), (b, sb)| {
+        sb.is_some().cmp(&sa.is_some())
+            .then_with(|| sb.unwrap_or(0.0).total_cmp(&sa.unwrap_or(0.0)))
+            .then_with(|| b.priority.cmp(&a.priority))
+            .then_with(|| a.created_at.cmp(&b.created_at))
+    });
+
+    scored.into_iter().enumerate()
+        .map(|(i, (idea, score))| RankedIdea {
+            rank: score.map(|_| i + 1),
+            id: idea.id.clone(),
+            title: idea.title.clone(),
+            category: idea.category.clone(),
+            status: format!("{:?}", idea.status),
+            priority: format!("{:?}", idea.priority),
+            inputs: idea.scores,
+            model: model.label(),
+            score,
+        })
+        .collect()
+}
+
+pub fn render_csv(ranked: &[RankedIdea]) -> String {
+    let field = |value: &str| {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    };
+    let number = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+    let mut out = String::from("rank,id,title,category,status,priority,reach,impact,confidence,effort,model,score\n");
+    for row in ranked {
+        out.push_str(&[
+            row.rank.map(|r| r.to_string()).unwrap_or_default(),
+            row.id.clone(),
+            field(&row.title),
+            field(&row.category),
+            row.status.clone(),
+            row.priority.clone(),
+            number(row.inputs.reach),
+            number(row.inputs.impact),
+            number(row.inputs.confidence),
+            number(row.inputs.effort),
+            row.model.to_string(),
+            number(row.score),
+        ].join(","));
+        out.push('\n');
+    }
+    out
+}
+
+pub fn render_json(ranked: &[RankedIdea]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(ranked)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::idea::{test_idea, IdeaPriority};
+
+    fn idea(id: &str, priority: IdeaPriority, scores: ScoreInputs) -> Idea {
+        Idea { title: format!("Idea, \"{}\"", id), priority, scores, ..test_idea(id) }
+    }
+
+    fn inputs(reach: Option<f64>, impact: f64, confidence: f64, effort: f64) -> ScoreInputs {
+        ScoreInputs { reach, impact: Some(impact), confidence: Some(confidence), effort: Some(effort) }
+    }
+
+    #[test]
+    fn test_scoring_models() {
+        let full = inputs(Some(500.0), 2.0, 80.0, 3.0);
+        assert_eq!(ScoringModel::Rice.score(&full), Some(266.67));
+        assert_eq!(ScoringModel::Ice.score(&full), Some(0.53));
+        assert_eq!(ScoringModel::Rice.score(&inputs(None, 2.0, 80.0, 3.0)), None);
+        assert!(inputs(None, 1.0, 120.0, 1.0).validate().is_err());
+        assert!(inputs(Some(10.0), 0.25, 50.0, 0.5).validate().is_ok());
+        assert!(inputs(Some(f64::INFINITY), 1.0, 50.0, 1.0).validate().is_err());
+        assert!(inputs(None, f64::NAN, 50.0, 1.0).validate().is_err());
+
+        let mut merged = full;
+        merged.merge(ScoreInputs { effort: Some(1.0), ..Default::default() });
+        assert_eq!(merged.effort, Some(1.0));
+        assert_eq!(merged.reach, Some(500.0));
+    }
+
+    #[test]
+    fn test_rank_orders_and_exports() {
+        let ideas = vec![
+            idea("low", IdeaPriority::Low, inputs(Some(100.0), 1.0, 50.0, 1.0)),
+            idea("unscored", IdeaPriority::Critical, ScoreInputs::default()),
+            idea("high", IdeaPriority::Low, inputs(Some(1000.0), 2.0, 80.0, 2.0)),
+        ];
+        let ranked = rank(&ideas, ScoringModel::Rice);
+        let order: Vec<&str> = ranked.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(order, vec!["high", "low", "unscored"]);
+        assert_eq!(ranked[2].rank, None);
+
+        let csv = render_csv(&ranked);
+        assert!(csv.contains("1,high,\"Idea, \"\"high\"\"\",General,New,Low,1000,2,80,2,RICE,800\n"));
+        assert!(csv.ends_with(",RICE,\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&render_json(&ranked).unwrap()).unwrap();
+        assert_eq!(json[0]["score"], 800.0);
+        assert_eq!(json[0]["reach"], 1000.0);
+    }
+}
@@ -0,0 +1,431 @@
+//! Idea storage backends
+//!
+//! Ideas are kept either in a single JSON file (`ideas.json`) or in a
+//! directory with one Markdown file per idea (`ideas/<id>.md`). A Markdown
+//! idea carries its fields in YAML frontmatter and its description as the
+//! body, so teammates adding ideas on different branches touch different
+//! files instead of conflicting in one. Projects choose the backend with
+//! `ideas.store` in `.ci-config.json`.
+//!
+//! ```markdown
+//! ---
+//! id: 9b1f0c2e-...
+//! title: Offline mode
+//! status: Exploring
+//! priority: High
+//! category: Product
+//! tags: ["mobile","sync"]
+//! created_at: 2024-05-01T10:00:00Z
+//! notes: "Ask support for the top requests\nCheck storage limits"
+//! ...
+//! ---
+//!
+//! Let the app queue changes while offline.
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::Idea;
+
+/// File of the JSON backend inside the ideas directory
+pub const JSON_FILE: &str = "ideas.json";
+
+/// Directory of the Markdown backend inside the ideas directory
+pub const MARKDOWN_DIR: &str = "ideas";
+
+/// Frontmatter keys written first, in this order; other fields follow alphabetically
+const FRONTMATTER_ORDER: &[&str] = &["id", "title", "status", "priority", "category", "tags"];
+
+/// Field kept in the Markdown body instead of the frontmatter
+const BODY_FIELD: &str = "description";
+
+/// Storage format of an idea store
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreBackend {
+    Json,
+    Markdown,
+}
+
+impl StoreBackend {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StoreBackend::Json => "json",
+            StoreBackend::Markdown => "markdown",
+        }
+    }
+
+    /// The configured backend, otherwise the one whose store exists in `dir`
+    /// (JSON when there is none yet)
+    pub fn select(dir: &Path, configured: Option<&str>) -> Result<Self> {
+        if let Some(name) = configured {
+            return name.parse().map_err(|e: String| anyhow!(e));
+        }
+        if dir.join(MARKDOWN_DIR).is_dir() && !dir.join(JSON_FILE).exists() {
+            Ok(StoreBackend::Markdown)
+        } else {
+            Ok(StoreBackend::Json)
+        }
+    }
+
+    /// Store of this backend inside the ideas directory `dir`
+    pub fn open(&self, dir: &Path) -> Box<dyn IdeaStore> {
+        match self {
+            StoreBackend::Json => Box::new(JsonStore { file: dir.join(JSON_FILE) }),
+            StoreBackend::Markdown => Box::new(MarkdownStore { dir: dir.join(MARKDOWN_DIR) }),
+        }
+    }
+}
+
+impl FromStr for StoreBackend {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "json" => Ok(StoreBackend::Json),
+            "markdown" | "md" => Ok(StoreBackend::Markdown),
+            _ => Err(format!("Unknown idea store '{}' (use json or markdown)", value)),
+        }
+    }
+}
+
+/// Which of the two idea stores: the project's `.ci` or the user's global one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreScope {
+    Project,
+    Global,
+}
+
+impl StoreScope {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StoreScope::Project => "project",
+            StoreScope::Global => "global",
+        }
+    }
+
+    pub fn other(&self) -> Self {
+        match self {
+            StoreScope::Project => StoreScope::Global,
+            StoreScope::Global => StoreScope::Project,
+        }
+    }
+}
+
+impl FromStr for StoreScope {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "project" | "local" => Ok(StoreScope::Project),
+            "global" | "user" => Ok(StoreScope::Global),
+            _ => Err(format!("Unknown idea store '{}' (use project or global)", value)),
+        }
+    }
+}
+
+/// Where ideas are persisted
+pub trait IdeaStore {
+    fn backend(&self) -> StoreBackend;
+
+    /// File or directory holding the ideas
+    fn location(&self) -> &Path;
+
+    /// Every stored idea
+    fn load(&self) -> Result<Vec<Idea>>;
+
+    /// Replace the stored ideas with `ideas`
+    fn save(&self, ideas: &[Idea]) -> Result<()>;
+}
+
+/// All ideas in one pretty-printed JSON array
+pub struct JsonStore {
+    file: PathBuf,
+}
+
+impl IdeaStore for JsonStore {
+    fn backend(&self) -> StoreBackend {
+        StoreBackend::Json
+    }
+
+    fn location(&self) -> &Path {
+        &self.file
+    }
+
+    fn load(&self) -> Result<Vec<Idea>> {
+        if !self.file.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&self.file)
+            .with_context(|| format!("Failed to read ideas file: {}", self.file.display()))?;
+
+        if content.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        serde_json::from_str(&content)
+            .with_context(|| "Failed to parse ideas file")
+    }
+
+    fn save(&self, ideas: &[Idea]) -> Result<()> {
+        let content = serde_json::to_string_pretty(ideas)
+            .with_context(|| "Failed to serialize ideas")?;
+
+        fs::write(&self.file, content)
+            .with_context(|| format!("Failed to write ideas file: {}", self.file.display()))
+    }
+}
+
+/// One `<id>.md` file per idea
+pub struct MarkdownStore {
+    dir: PathBuf,
+}
+
+impl IdeaStore for MarkdownStore {
+    fn backend(&self) -> StoreBackend {
+        StoreBackend::Markdown
+    }
+
+    fn location(&self) -> &Path {
+        &self.dir
+    }
+
+    fn load(&self) -> Result<Vec<Idea>> {
+        let mut ideas = Vec::new();
+        for path in self.files() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read idea file: {}", path.display()))?;
+            ideas.push(parse_markdown(&content)
+                .with_context(|| format!("Failed to parse idea file: {}", path.display()))?);
+        }
+        ideas.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(ideas)
+    }
+
+    fn save(&self, ideas: &[Idea]) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create ideas directory: {}", self.dir.display()))?;
+
+        // Unchanged ideas keep their files untouched
+        for idea in ideas {
+            let path = self.dir.join(format!("{}.md", idea.id));
+            let content = render_markdown(idea)?;
+            if fs::read_to_string(&path).ok().as_deref() != Some(content.as_str()) {
+                fs::write(&path, content)
+                    .with_context(|| format!("Failed to write idea file: {}", path.display()))?;
+            }
+        }
+
+        for path in self.files() {
+            let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            if !ideas.iter().any(|idea| idea.id == stem) {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove idea file: {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MarkdownStore {
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.dir).into_iter()
+            .flat_map(|entries| entries.flatten())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+            .collect();
+        files.sort();
+        files
+    }
+}
+
+/// Copy every idea of `from` into `to` and check that the copy reads back identically
+pub fn copy_ideas(from: &dyn IdeaStore, to: &dyn IdeaStore) -> Result<usize> {
+    let ideas = from.load()?;
+    to.save(&ideas)?;
+
+    let by_id = |mut ideas: Vec<Idea>| -> Result<Value> {
+        ideas.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(serde_json::to_value(ideas)?)
+    };
+    if by_id(to.load()?)? != by_id(ideas.clone())? {
+        return Err(anyhow!("Ideas copied to {} do not read back identically", to.location().display()));
+    }
+    Ok(ideas.len())
+}
+
+/// Markdown document of an idea: every field but the description in the
+/// frontmatter, the description as body
+pub fn render_markdown(idea: &Idea) -> Result<String> {
+    let Value::Object(mut fields) = serde_json::to_value(idea)? else {
+        return Err(anyhow!("Idea {} does not serialize to an object", idea.id));
+    };
+    let body = match fields.remove(BODY_FIELD) {
+        Some(Value::String(body)) => body,
+        _ => String::new(),
+    };
+
+    let mut keys: Vec<String> = FRONTMATTER_ORDER.iter()
+        .filter(|key| fields.contains_key(**key))
+        .map(|key| key.to_string())
+        .collect();
+    keys.extend(fields.keys().filter(|key| !FRONTMATTER_ORDER.contains(&key.as_str())).cloned());
+
+    let mut out = String::from("---\n");
+    for key in keys {
+        out.push_str(&format!("{}: {}\n", key, yaml_value(&fields[&key])));
+    }
+    out.push_str("---\n");
+    if !body.is_empty() {
+        out.push_str(&format!("\n{}\n", body));
+    }
+    Ok(out)
+}
+
+/// Idea of a Markdown document written by `render_markdown` (or by hand).
+/// CRLF line endings, as git checks out with `core.autocrlf`, are read as LF.
+pub fn parse_markdown(content: &str) -> Result<Idea> {
+    let content = content.replace("\r\n", "\n");
+    let rest = content.strip_prefix("---\n").ok_or_else(|| anyhow!("Missing frontmatter"))?;
+    let (frontmatter, body) = match rest.find("\n---\n") {
+        Some(end) => (&rest[..end], &rest[end + 5..]),
+        None => (rest.strip_suffix("\n---").ok_or_else(|| anyhow!("Unterminated frontmatter"))?, ""),
+    };
+
+    let mut fields = Map::new();
+    for line in frontmatter.lines().filter(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#')) {
+        let (key, value) = line.split_once(':')
+            .ok_or_else(|| anyhow!("Invalid frontmatter line: {}", line))?;
+        fields.insert(key.trim().to_string(), parse_yaml_value(value.trim())?);
+    }
+
+    let body = body.strip_prefix('\n').unwrap_or(body);
+    let body = body.strip_suffix('\n').unwrap_or(body);
+    fields.insert(BODY_FIELD.to_string(), Value::String(body.to_string()));
+
+    serde_json::from_value(Value::Object(fields)).context("Invalid idea fields")
+}
+
+/// Scalar or flow collection: strings stay bare while unambiguous, everything
+/// else is written as JSON, which YAML reads as well
+fn yaml_value(value: &Value) -> String {
+    match value {
+        Value::String(s) if is_plain_scalar(s) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+fn is_plain_scalar(s: &str) -> bool {
+    !s.is_empty()
+        && s.trim() == s
+        && !s.contains(['\n', '\r', '\t'])
+        && !s.contains(": ")
+        && !s.contains(" #")
+        && !s.ends_with(':')
+        && !s.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@`".contains(c))
+        && !matches!(s.to_lowercase().as_str(), "yes" | "no" | "on" | "off" | "~")
+        && serde_json::from_str::<Value>(s).is_err()
+}
+
+fn parse_yaml_value(value: &str) -> Result<Value> {
+    if value.is_empty() || value == "~" {
+        return Ok(Value::Null);
+    }
+    if value.starts_with('"') {
+        return serde_json::from_str(value).with_context(|| format!("Invalid quoted value: {}", value));
+    }
+    if let Some(quoted) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        return Ok(Value::String(quoted.replace("''", "'")));
+    }
+    if let Ok(parsed) = serde_json::from_str::<Value>(value) {
+        return Ok(parsed);
+    }
+    // Hand-written flow sequence such as `[ui, mobile]`
+    if let Some(items) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        return Ok(Value::Array(items.split(',')
+            .map(|item| item.trim().trim_matches(|c| c == '"' || c == '\''))
+            .filter(|item| !item.is_empty())
+            .map(|item| Value::String(item.to_string()))
+            .collect()));
+    }
+    Ok(Value::String(value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::idea::graph::{IdeaLink, LinkKind};
+    use crate::commands::idea::{test_idea, IdeaPriority, IdeaStatus, ScoreInputs};
+    use tempfile::TempDir;
+
+    fn idea(id: &str, title: &str, description: &str) -> Idea {
+        Idea {
+            title: title.to_string(),
+            description: description.to_string(),
+            category: "Product".to_string(),
+            tags: vec!["mobile".to_string(), "sync, offline".to_string()],
+            status: IdeaStatus::InDevelopment,
+            priority: IdeaPriority::High,
+            related_ideas: vec!["other".to_string()],
+            links: vec![IdeaLink { kind: LinkKind::DependsOn, target: "other".to_string() }],
+            scores: ScoreInputs { reach: Some(250.0), impact: Some(0.5), confidence: None, effort: Some(2.0) },
+            notes: "line one\nkey: \"quoted\"".to_string(),
+            ..test_idea(id)
+        }
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        for original in [
+            idea("a1", "Offline mode", "Queue changes.\n\n---\n\n## Details\n"),
+            idea("b2", "true", ""),
+            idea("c3", "- Fix: login # now", "\nstarts with a blank line"),
+        ] {
+            let rendered = render_markdown(&original).unwrap();
+            assert!(rendered.starts_with(&format!("---\nid: {}\n", original.id)));
+            let parsed = parse_markdown(&rendered).unwrap();
+            assert_eq!(serde_json::to_value(&parsed).unwrap(), serde_json::to_value(&original).unwrap());
+            let crlf = parse_markdown(&rendered.replace('\n', "\r\n")).unwrap();
+            assert_eq!(serde_json::to_value(&crlf).unwrap(), serde_json::to_value(&original).unwrap());
+        }
+
+        let handwritten = "---\nid: x9\ntitle: Dark mode\nstatus: New\npriority: Low\ncategory: UI\ntags: [ui, 'theme']\n\
+            created_at: 2024-05-01T10:00:00Z\nupdated_at: 2024-05-01T10:00:00Z\nrelated_ideas: []\nnotes: ''\n---\n\nFollow the OS setting.\n";
+        let parsed = parse_markdown(handwritten).unwrap();
+        assert_eq!(parsed.title, "Dark mode");
+        assert_eq!(parsed.tags, vec!["ui", "theme"]);
+        assert_eq!(parsed.description, "Follow the OS setting.");
+    }
+
+    #[test]
+    fn test_copy_between_backends() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        assert_eq!(StoreBackend::select(dir, None).unwrap(), StoreBackend::Json);
+
+        let json = StoreBackend::Json.open(dir);
+        json.save(&[idea("a1", "First", "one"), idea("b2", "Second", "two")]).unwrap();
+        let markdown = StoreBackend::Markdown.open(dir);
+        assert_eq!(copy_ideas(json.as_ref(), markdown.as_ref()).unwrap(), 2);
+        assert!(dir.join(MARKDOWN_DIR).join("a1.md").is_file());
+
+        fs::remove_file(dir.join(JSON_FILE)).unwrap();
+        assert_eq!(StoreBackend::select(dir, None).unwrap(), StoreBackend::Markdown);
+        assert_eq!(StoreBackend::select(dir, Some("json")).unwrap(), StoreBackend::Json);
+
+        // Saving drops the files of deleted ideas
+        let mut ideas = markdown.load().unwrap();
+        ideas.retain(|i| i.id != "a1");
+        markdown.save(&ideas).unwrap();
+        assert!(!dir.join(MARKDOWN_DIR).join("a1.md").exists());
+
+        assert_eq!(copy_ideas(markdown.as_ref(), json.as_ref()).unwrap(), 1);
+        assert_eq!(json.load().unwrap()[0].title, "Second");
+    }
+}
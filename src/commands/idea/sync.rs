@@ -0,0 +1,326 @@
+//! Merging the project and global idea stores
+//!
+//! `ci idea sync` merges both stores by id. Every project idea is copied to
+//! the global store, but the global store only feeds the project the ideas
+//! it already shares with it (those seen by an earlier sync of this project),
+//! so ideas pushed from other projects stay put unless `--all` is given. When the two copies of an idea differ, the later `updated_at` wins.
+//! A small sync state in the global data dir remembers which version of
+//! each idea the last sync saw. From that, a side that has not changed since
+//! then can take the other's edit or deletion quietly. Edits on both sides,
+//! or an edit racing a deletion, are resolved the same way but reported as
+//! conflicts. Ideas relocated with `ci idea move` stay pinned to their new
+//! store and are not copied back.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::store::StoreScope;
+use super::Idea;
+use crate::helpers::memory_history::hex;
+
+/// What the last sync between one project and the global store saw
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    #[serde(default)]
+    pub project: PathBuf,
+    #[serde(default)]
+    pub synced_at: Option<DateTime<Utc>>,
+    /// `updated_at` of every idea as of the last sync, by id
+    #[serde(default)]
+    pub versions: BTreeMap<String, DateTime<Utc>>,
+    /// Ideas moved with `ci idea move`, kept only in the named store
+    #[serde(default)]
+    pub pinned: BTreeMap<String, StoreScope>,
+}
+
+impl SyncState {
+    /// State file of `project` inside the global ideas directory
+    pub fn path(global_dir: &Path, project: &Path) -> PathBuf {
+        let digest = hex(&Sha256::digest(project.to_string_lossy().as_bytes()));
+        global_dir.join("sync").join(format!("{}.json", &digest[..16]))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read idea sync state: {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| "Failed to parse idea sync state")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write idea sync state: {}", path.display()))
+    }
+}
+
+/// Idea changed on both sides since the last sync
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub id: String,
+    pub title: String,
+    pub project_updated: Option<DateTime<Utc>>,
+    pub global_updated: Option<DateTime<Utc>>,
+    /// Side whose version was kept
+    pub kept: StoreScope,
+    /// Fields that differ, or a note when one side deleted the idea
+    pub detail: String,
+}
+
+/// Result of merging the two stores
+#[derive(Debug, Default)]
+pub struct SyncOutcome {
+    /// Ideas the project store holds after the sync
+    pub project: Vec<Idea>,
+    /// Ideas the global store holds after the sync
+    pub global: Vec<Idea>,
+    /// Pins still naming an existing idea
+    pub pinned: BTreeMap<String, StoreScope>,
+    pub added_to_project: usize,
+    pub added_to_global: usize,
+    pub updated_project: usize,
+    pub updated_global: usize,
+    pub deleted_from_project: usize,
+    pub deleted_from_global: usize,
+    /// Global ideas never shared with this project and not pulled into it
+    pub left_in_global: usize,
+    pub conflicts: Vec<Conflict>,
+}
+
+impl SyncOutcome {
+    /// Sync state recording the versions both stores now share
+    pub fn state(&self, project: &Path) -> SyncState {
+        SyncState {
+            project: project.to_path_buf(),
+            synced_at: Some(Utc::now()),
+            versions: self.project.iter()
+                .filter(|idea| !self.pinned.contains_key(&idea.id))
+                .map(|idea| (idea.id.clone(), idea.updated_at))
+                .collect(),
+            pinned: self.pinned.clone(),
+        }
+    }
+
+    fn keep(&mut self, idea: &Idea) {
+        self.project.push(idea.clone());
+        self.global.push(idea.clone());
+    }
+
+    pub fn is_noop(&self) -> bool {
+        self.added_to_project + self.added_to_global + self.updated_project + self.updated_global
+            + self.deleted_from_project + self.deleted_from_global == 0
+    }
+}
+
+fn differing_fields(a: &Idea, b: &Idea) -> Vec<String> {
+    let (Ok(Value::Object(a)), Ok(Value::Object(b))) = (serde_json::to_value(a), serde_json::to_value(b)) else {
+        return Vec::new();
+    };
+    let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+    keys.into_iter()
+        .filter(|key| key.as_str() != "updated_at" && a.get(*key) != b.get(*key))
+        .cloned()
+        .collect()
+}
+
+/// Three-way merge of both stores against the last sync
+///
+/// Global ideas this project has not synced before are left out of it
+/// unless `pull_all` is set.
+pub fn merge(project: &[Idea], global: &[Idea], base: &SyncState, pull_all: bool) -> SyncOutcome {
+    let mut outcome = SyncOutcome::default();
+    let changed = |idea: &Idea| base.versions.get(&idea.id) != Some(&idea.updated_at);
+
+    let ids: BTreeSet<&str> = project.iter().chain(global).map(|idea| idea.id.as_str()).collect();
+    for id in ids {
+        let p = project.iter().find(|idea| idea.id == id);
+        let g = global.iter().find(|idea| idea.id == id);
+
+        if let Some(&scope) = base.pinned.get(id) {
+            // A moved idea lives only in its store; a copy left on the other side goes
+            let (own, other) = match scope {
+                StoreScope::Project => (p, g),
+                StoreScope::Global => (g, p),
+            };
+            let idea = own.or(other).expect("id from one of the stores").clone();
+            let (added, deleted) = match scope {
+                StoreScope::Project => (&mut outcome.added_to_project, &mut outcome.deleted_from_global),
+                StoreScope::Global => (&mut outcome.added_to_global, &mut outcome.deleted_from_project),
+            };
+            *added += usize::from(own.is_none());
+            *deleted += usize::from(other.is_some());
+            outcome.pinned.insert(id.to_string(), scope);
+            match scope {
+                StoreScope::Project => outcome.project.push(idea),
+                StoreScope::Global => outcome.global.push(idea),
+            }
+            continue;
+        }
+
+        match (p, g) {
+            (Some(p), Some(g)) => {
+                let fields = differing_fields(p, g);
+                if fields.is_empty() {
+                    outcome.keep(if p.updated_at >= g.updated_at { p } else { g });
+                    continue;
+                }
+                let kept = if g.updated_at > p.updated_at { StoreScope::Global } else { StoreScope::Project };
+                if changed(p) && changed(g) {
+                    outcome.conflicts.push(Conflict {
+                        id: id.to_string(),
+                        title: p.title.clone(),
+                        project_updated: Some(p.updated_at),
+                        global_updated: Some(g.updated_at),
+                        kept,
+                        detail: fields.join(", "),
+                    });
+                }
+                match kept {
+                    StoreScope::Project => {
+                        outcome.updated_global += 1;
+                        outcome.keep(p);
+                    },
+                    StoreScope::Global => {
+                        outcome.updated_project += 1;
+                        outcome.keep(g);
+                    },
+                }
+            },
+            (Some(idea), None) | (None, Some(idea)) => {
+                let side = if p.is_some() { StoreScope::Project } else { StoreScope::Global };
+                let synced_before = base.versions.contains_key(id);
+
+                if side == StoreScope::Global && !synced_before && !pull_all {
+                    // Another project's idea; it is not this project's to receive
+                    outcome.global.push(idea.clone());
+                    outcome.left_in_global += 1;
+                    continue;
+                }
+                if synced_before && !changed(idea) {
+                    // The other side deleted it since the last sync
+                    match side {
+                        StoreScope::Project => outcome.deleted_from_project += 1,
+                        StoreScope::Global => outcome.deleted_from_global += 1,
+                    }
+                    continue;
+                }
+                if synced_before {
+                    outcome.conflicts.push(Conflict {
+                        id: id.to_string(),
+                        title: idea.title.clone(),
+                        project_updated: p.map(|i| i.updated_at),
+                        global_updated: g.map(|i| i.updated_at),
+                        kept: side,
+                        detail: format!("edited in {}, deleted in {}", side.label(), side.other().label()),
+                    });
+                }
+                match side {
+                    StoreScope::Project => outcome.added_to_global += 1,
+                    StoreScope::Global => outcome.added_to_project += 1,
+                }
+                outcome.keep(idea);
+            },
+            (None, None) => {},
+        }
+    }
+
+    for ideas in [&mut outcome.project, &mut outcome.global] {
+        ideas.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::idea::test_idea;
+    use chrono::Duration;
+
+    fn idea(id: &str, title: &str, minutes: i64) -> Idea {
+        let epoch = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        Idea {
+            title: title.to_string(),
+            created_at: epoch,
+            updated_at: epoch + Duration::minutes(minutes),
+            ..test_idea(id)
+        }
+    }
+
+    #[test]
+    fn test_first_sync_unions_and_reports_differences() {
+        let project = vec![idea("a", "Only here", 0), idea("shared", "Project title", 5)];
+        let global = vec![idea("b", "Only global", 0), idea("shared", "Global title", 9)];
+
+        let outcome = merge(&project, &global, &SyncState::default(), true);
+        let ids: Vec<&str> = outcome.project.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "shared"]);
+        assert_eq!(outcome.global[2].title, "Global title");
+        assert_eq!((outcome.added_to_global, outcome.added_to_project, outcome.updated_project), (1, 1, 1));
+
+        // Without a previous sync both copies count as changed
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].kept, StoreScope::Global);
+        assert_eq!(outcome.conflicts[0].detail, "title");
+    }
+
+    #[test]
+    fn test_sync_against_previous_state() {
+        let synced = vec![idea("edited", "Old", 0), idea("gone", "Deleted in global", 0), idea("both", "Old", 0)];
+        let mut base = SyncOutcome { project: synced, ..Default::default() }.state(Path::new("/p"));
+        base.pinned.insert("moved".to_string(), StoreScope::Global);
+
+        let project = vec![idea("edited", "Old", 0), idea("gone", "Deleted in global", 0), idea("both", "Project edit", 3)];
+        let global = vec![idea("edited", "New", 2), idea("both", "Global edit", 1), idea("moved", "Moved away", 0)];
+
+        let outcome = merge(&project, &global, &base, false);
+        assert_eq!(outcome.deleted_from_project, 1);
+        assert_eq!(outcome.updated_project, 1);
+        assert_eq!(outcome.updated_global, 1);
+        assert!(outcome.project.iter().all(|i| i.id != "gone" && i.id != "moved"));
+        assert!(outcome.global.iter().any(|i| i.id == "moved"));
+        assert_eq!(outcome.project.iter().find(|i| i.id == "edited").unwrap().title, "New");
+
+        // Only the idea edited on both sides is a conflict; the later edit wins
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].id, "both");
+        assert_eq!(outcome.conflicts[0].kept, StoreScope::Project);
+
+        let next = outcome.state(Path::new("/p"));
+        assert!(merge(&outcome.project, &outcome.global, &next, false).is_noop());
+    }
+
+    #[test]
+    fn test_sync_keeps_other_projects_ideas_out() {
+        // Project A pushes its idea to the global store
+        let first = merge(&[idea("a", "From A", 0)], &[], &SyncState::default(), false);
+        let state_a = first.state(Path::new("/a"));
+        assert_eq!(first.added_to_global, 1);
+
+        // Project B neither receives it nor deletes it from the global store
+        let b = merge(&[idea("b", "From B", 0)], &first.global, &SyncState::default(), false);
+        assert_eq!(b.project.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(), vec!["b"]);
+        assert_eq!(b.global.len(), 2);
+        assert_eq!((b.added_to_project, b.left_in_global), (0, 1));
+        assert!(!b.state(Path::new("/b")).versions.contains_key("a"));
+
+        // Asked to, B pulls it
+        let all = merge(&[idea("b", "From B", 0)], &first.global, &SyncState::default(), true);
+        assert!(all.project.iter().any(|i| i.id == "a"));
+
+        // A still receives global edits to its own idea
+        let global = vec![idea("a", "Edited globally", 4), idea("b", "From B", 0)];
+        let again = merge(&first.project, &global, &state_a, false);
+        assert_eq!(again.project.iter().map(|i| i.title.as_str()).collect::<Vec<_>>(), vec!["Edited globally"]);
+        assert_eq!((again.updated_project, again.left_in_global), (1, 1));
+    }
+}
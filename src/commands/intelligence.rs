@@ -1,12 +1,28 @@
 use anyhow::Result;
 use colored::*;
 use std::path::{Path, PathBuf};
-use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use chrono::Utc;
-use std::process::Command;
 
 use crate::config::Config;
 use crate::helpers::CommandHelpers;
+use crate::helpers::agent_supervisor::{AgentSupervisor, InstanceStatus, SupervisorState, WaitOptions, format_duration_secs};
+use crate::helpers::agent_launcher::{AgentLauncher, LaunchRequest, configured_launcher};
+use crate::tools::memory_composer::MemoryComposer;
+use crate::tools::memory_inheritance::MemoryResolver;
+use crate::helpers::memory_history::MemoryHistory;
+use crate::helpers::agent_matcher::{rank_agents, suggested_team, print_matches};
+use crate::helpers::agent_stats::AgentSession;
+use crate::helpers::session_provenance::GitProvenance;
+use crate::helpers::session_resume::ResumePlan;
+use crate::helpers::transcript;
+use crate::helpers::session_store::{absolute, parallel_status, task_objective, ParallelSettings, SessionKind, SessionRecord, SessionStatus, SessionStore};
+use crate::helpers::coordination::BOARD_FILE;
+use crate::helpers::parallel_report::{ReportFormat, SessionReport};
+use crate::helpers::sharding::{plan_shards, ShardStrategy};
+use crate::helpers::worktrees::{WorktreeSession, WorktreeStatus};
+use crate::helpers::headless::{HeadlessRun, HeadlessStatus, OUTPUT_LOG, RESULT_FILE};
+pub use crate::helpers::agent_manifest::AgentMetadata;
 
 pub async fn intent(_config: &Config) -> Result<()> {
     // Follows exact format of the original CI intent command for perfect parity
@@ -100,9 +116,12 @@ pub async fn agents(config: &Config) -> Result<()> {
                                 continue;
                             }
                             
-                            // Try to read the README.md for agent description
+                            // Prefer the agent.toml manifest, then the README.md, for the description
                             let readme_path = path.join("README.md");
-                            let description = if readme_path.exists() {
+                            let manifest = AgentMetadata::from_manifest(&path).ok().flatten();
+                            let description = if let Some(manifest) = manifest.filter(|m| !m.description.is_empty()) {
+                                manifest.description
+                            } else if readme_path.exists() {
                                 std::fs::read_to_string(&readme_path)
                                     .ok()
                                     .and_then(|content| extract_agent_description(&content))
@@ -292,71 +311,15 @@ fn get_agent_usage_count(agent_dir: &Path) -> usize {
     }
 }
 
-/// Structure to represent agent metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AgentMetadata {
-    /// Agent name
-    pub name: String,
-    
-    /// Agent role/description
-    pub description: String,
-    
-    /// Agent capabilities
-    pub capabilities: Vec<String>,
-    
-    /// Agent creation date
-    pub created_at: String,
-    
-    /// Agent last used date
-    pub last_used: Option<String>,
-    
-    /// Number of times the agent has been used
-    pub usage_count: usize,
-    
-    /// Agent version
-    pub version: String,
-    
-    /// Agent toolkit path
-    pub toolkit_path: String,
-    
-    /// Agent memory path
-    pub memory_path: String,
-    
-    /// Agent continuous learning path
-    pub learning_path: Option<String>,
-    
-    /// Custom attributes
-    pub attributes: std::collections::HashMap<String, String>,
-}
-
-/// Structure for agent session tracking
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct AgentSession {
-    /// Agent name
-    agent_name: String,
-    
-    /// Session start time
-    start_time: String,
-    
-    /// Context used for session
-    context: Option<String>,
-    
-    /// Session end time (if completed)
-    end_time: Option<String>,
-    
-    /// Session output path
-    output_path: Option<String>,
-}
-
 /// Load multiple agents into a combined Claude Code session
-pub async fn load_agents(agent_names: &[String], context: Option<&str>, path: Option<&Path>, auto_yes: bool, allow: bool, config: &Config) -> Result<()> {
+pub async fn load_agents(agent_names: &[String], context: Option<&str>, path: Option<&Path>, auto_yes: bool, allow: bool, options: &LoadOptions, config: &Config) -> Result<()> {
     if agent_names.is_empty() {
         return Err(anyhow::anyhow!("No agents specified"));
     }
     
     // Handle single agent case by delegating to existing function
     if agent_names.len() == 1 {
-        return load_agent(&agent_names[0], context, path, auto_yes, allow, config).await;
+        return load_agent(&agent_names[0], context, path, auto_yes, allow, options, config).await;
     }
     
     CommandHelpers::print_command_header(
@@ -377,73 +340,30 @@ pub async fn load_agents(agent_names: &[String], context: Option<&str>, path: Op
     CommandHelpers::print_info(&format!("Loading {} agents for combined session...", agent_names.len()));
     
     // Collect all agent memory content
-    let mut combined_memory = String::new();
+    let mut composer = MemoryComposer::new(options.budget)
+        .with_separator(&format!("\n\n# Agent Separator: {}\n\n", "=".repeat(50)));
     let mut loaded_agents = Vec::new();
     let mut agent_toolkit_paths = Vec::new();
+    if let Some(ctx) = context {
+        composer.add_pinned("Session Context", ctx);
+    }
     
     for agent_name in agent_names {
         CommandHelpers::print_info(&format!("Processing agent: {}", agent_name.cyan().bold()));
         
-        // Check for agent files (same logic as load_agent)
         let agent_directory = config.ci_path.join("AGENTS").join(agent_name);
-        let direct_memory_path = agent_directory.join(format!("{}.md", agent_name));
-        let memory_path = agent_directory.join(format!("{}_memory.md", agent_name));
-        let agents_md_path = config.ci_path.join("AGENTS.md");
-        
-        let agent_memory = if direct_memory_path.exists() {
-            match std::fs::read_to_string(&direct_memory_path) {
-                Ok(content) => {
-                    CommandHelpers::print_success(&format!("  ✓ Loaded from: {}", direct_memory_path.display()));
-                    content
-                },
-                Err(e) => {
-                    CommandHelpers::print_warning(&format!("  ⚠ Failed to read {}: {}", direct_memory_path.display(), e));
-                    continue;
-                }
-            }
-        } else if memory_path.exists() {
-            match std::fs::read_to_string(&memory_path) {
-                Ok(content) => {
-                    CommandHelpers::print_success(&format!("  ✓ Loaded from: {}", memory_path.display()));
-                    content
-                },
-                Err(e) => {
-                    CommandHelpers::print_warning(&format!("  ⚠ Failed to read {}: {}", memory_path.display(), e));
-                    continue;
-                }
-            }
-        } else if agents_md_path.exists() {
-            // Load from AGENTS.md
-            match std::fs::read_to_string(&agents_md_path) {
-                Ok(agents_content) => {
-                    if agent_exists(&agents_content, agent_name) {
-                        let memory = extract_agent_memory(&agents_content, agent_name);
-                        CommandHelpers::print_success(&format!("  ✓ Loaded from: AGENTS.md"));
-                        memory
-                    } else {
-                        CommandHelpers::print_warning(&format!("  ⚠ Agent '{}' not found in AGENTS.md", agent_name));
-                        continue;
-                    }
-                },
-                Err(e) => {
-                    CommandHelpers::print_warning(&format!("  ⚠ Failed to read AGENTS.md: {}", e));
-                    continue;
-                }
+        let agent_memory = match read_agent_memory(agent_name, config) {
+            Ok((content, source)) => {
+                CommandHelpers::print_success(&format!("  ✓ Loaded from: {}", source));
+                content
+            },
+            Err(e) => {
+                CommandHelpers::print_warning(&format!("  ⚠ {}", e));
+                continue;
             }
-        } else {
-            CommandHelpers::print_warning(&format!("  ⚠ No memory files found for agent: {}", agent_name));
-            continue;
         };
         
-        // Add agent separator and memory to combined content
-        if !combined_memory.is_empty() {
-            combined_memory.push_str("\n\n");
-            combined_memory.push_str(&format!("# Agent Separator: {}\n", "=".repeat(50)));
-            combined_memory.push_str("\n");
-        }
-        
-        combined_memory.push_str(&format!("# Agent: {}\n\n", agent_name));
-        combined_memory.push_str(&agent_memory);
+        composer.add_source(agent_name, &format!("# Agent: {}\n\n{}", agent_name, agent_memory));
         
         loaded_agents.push(agent_name.clone());
         agent_toolkit_paths.push(agent_directory.clone());
@@ -465,6 +385,10 @@ pub async fn load_agents(agent_names: &[String], context: Option<&str>, path: Op
         loaded_agents.join(", ")
     ));
     
+    // Deduplicate shared sections and trim to the token budget
+    let composed = composer.compose();
+    composed.report.print();
+    
     // Create combined memory file
     let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
     let session_name = format!("multi_agent_session_{}", timestamp);
@@ -487,7 +411,7 @@ pub async fn load_agents(agent_names: &[String], context: Option<&str>, path: Op
         Utc::now().to_rfc3339(),
         loaded_agents.len()
     );
-    final_memory.push_str(&combined_memory);
+    final_memory.push_str(&composed.content);
     
     // Write combined memory to file
     std::fs::write(&combined_memory_path, &final_memory)
@@ -511,56 +435,33 @@ pub async fn load_agents(agent_names: &[String], context: Option<&str>, path: Op
     std::fs::write(&session_path, session_json)
         .map_err(|e| anyhow::anyhow!("Failed to write session data: {}", e))?;
     
-    // Launch Claude Code or provide instructions
+    let mut record = SessionRecord::new(SessionKind::MultiAgent, loaded_agents.clone());
+    record.context = context.map(|s| s.to_string());
+    record.memory_path = Some(combined_memory_path.clone());
+    record.bypass_permissions = allow;
+    let record = record_session(record, &session_path, options, config);
+    
+    snapshot_agent_memory(&loaded_agents, "load", config);
+    
+    // Launch the configured assistant or provide instructions
+    let launcher = configured_launcher()?;
     if auto_yes {
-        // Check if claude CLI is available
-        if has_claude_cli() {
-            // Launch Claude Code with the combined memory
-            println!("Launching Claude Code with multi-agent team...");
-            
-            let mut claude_cmd = Command::new("claude");
-            claude_cmd.arg("code");
-            claude_cmd.arg(&combined_memory_path);
-            
-            if allow {
-                claude_cmd.args(&["--permission-mode", "bypassPermissions"]);
-            }
-            
-            let status = claude_cmd.status()
-                .map_err(|e| anyhow::anyhow!("Failed to launch Claude Code: {}", e))?;
-                
-            if !status.success() {
-                CommandHelpers::print_warning("Claude Code exited with a non-zero status");
-            }
-            
-            // Update session with end time
-            let mut session = serde_json::from_str::<AgentSession>(&std::fs::read_to_string(&session_path)?)
-                .map_err(|e| anyhow::anyhow!("Failed to read session data: {}", e))?;
-                
-            session.end_time = Some(Utc::now().to_rfc3339());
-            
-            let session_json = serde_json::to_string_pretty(&session)
-                .map_err(|e| anyhow::anyhow!("Failed to serialize session data: {}", e))?;
-                
-            std::fs::write(&session_path, session_json)
-                .map_err(|e| anyhow::anyhow!("Failed to update session data: {}", e))?;
-        } else {
-            CommandHelpers::print_warning("Claude CLI not found. Please use one of the following methods:");
-            CommandHelpers::print_info(&format!("  cat {} | claude code", combined_memory_path.display()));
-            CommandHelpers::print_info(&format!("  # or"));
-            CommandHelpers::print_info(&format!("  claude code < {}", combined_memory_path.display()));
-        }
+        launch_agent_session(launcher.as_ref(), &LaunchRequest {
+            agent_name: loaded_agents.join(", "),
+            memory_file: Some(combined_memory_path.clone()),
+            bypass_permissions: allow,
+            ..Default::default()
+        }, &session_path, record.as_ref(), config)?;
+        snapshot_agent_memory(&loaded_agents, "session end", config);
     } else {
-        CommandHelpers::print_info("To use this multi-agent team in Claude Code:");
-        CommandHelpers::print_info(&format!("  cat {} | claude code", combined_memory_path.display()));
-        CommandHelpers::print_info(&format!("  # or"));
-        CommandHelpers::print_info(&format!("  claude code < {}", combined_memory_path.display()));
+        print_manual_launch_hint(launcher.as_ref(), &combined_memory_path, "this multi-agent team");
+        hand_off_session_record(record.as_ref().map(|r| r.id.as_str()), config);
     }
     
     Ok(())
 }
 
-pub async fn load_agent(agent_name: &str, context: Option<&str>, path: Option<&Path>, auto_yes: bool, allow: bool, config: &Config) -> Result<()> {
+pub async fn load_agent(agent_name: &str, context: Option<&str>, path: Option<&Path>, auto_yes: bool, allow: bool, options: &LoadOptions, config: &Config) -> Result<()> {
     CommandHelpers::print_command_header(
         &format!("Load agent: {}", agent_name), 
         "🧠", 
@@ -586,12 +487,12 @@ pub async fn load_agent(agent_name: &str, context: Option<&str>, path: Option<&P
     
     // Load agent from direct files if they exist
     if direct_memory_path.exists() {
-        return load_from_direct_files(agent_name, context, direct_memory_path, auto_yes, allow, config).await;
+        return load_from_direct_files(agent_name, context, direct_memory_path, auto_yes, allow, options, config).await;
     } else if memory_path.exists() {
-        return load_from_direct_files(agent_name, context, memory_path, auto_yes, allow, config).await;
+        return load_from_direct_files(agent_name, context, memory_path, auto_yes, allow, options, config).await;
     } else if agents_md_path.exists() {
         // Fall back to legacy AGENTS.md loading
-        return load_from_agents_md(agent_name, context, path, auto_yes, allow, config).await;
+        return load_from_agents_md(agent_name, context, path, auto_yes, allow, options, config).await;
     } else {
         // Neither method is available
         CommandHelpers::print_error("No agent sources found. Neither direct agent files nor AGENTS.md exist.");
@@ -600,7 +501,7 @@ pub async fn load_agent(agent_name: &str, context: Option<&str>, path: Option<&P
 }
 
 /// Load an agent from direct files in the AGENTS directory
-async fn load_from_direct_files(agent_name: &str, context: Option<&str>, memory_file: PathBuf, auto_yes: bool, allow: bool, config: &Config) -> Result<()> {
+async fn load_from_direct_files(agent_name: &str, context: Option<&str>, memory_file: PathBuf, auto_yes: bool, allow: bool, options: &LoadOptions, config: &Config) -> Result<()> {
     CommandHelpers::print_info("Loading agent from direct files");
     
     // Determine the agent toolkit path
@@ -631,6 +532,11 @@ async fn load_from_direct_files(agent_name: &str, context: Option<&str>, memory_
         create_agent_metadata(agent_name, &agent_toolkit_path, &memory_file)
     };
     
+    // Manifest data takes precedence over the stored metadata
+    if let Ok(Some(manifest)) = AgentMetadata::from_manifest(&agent_toolkit_path) {
+        metadata.apply_manifest(&manifest);
+    }
+    
     // Update metadata with current session
     metadata.last_used = Some(Utc::now().to_rfc3339());
     metadata.usage_count += 1;
@@ -653,6 +559,7 @@ async fn load_from_direct_files(agent_name: &str, context: Option<&str>, memory_
     // Read memory content
     let mut memory_content = std::fs::read_to_string(&memory_file)
         .map_err(|e| anyhow::anyhow!("Failed to read agent memory file: {}", e))?;
+    memory_content = resolve_memory_inheritance(agent_name, &memory_content, config)?;
     
     // Check for continuous learning file and append if it exists
     let learning_file = agent_toolkit_path.join("ContinuousLearning.md");
@@ -688,8 +595,6 @@ async fn load_from_direct_files(agent_name: &str, context: Option<&str>, memory_
     
     // Extended contextual data
     let agent_context = generate_agent_context(agent_name, context, &metadata);
-    memory_content.push_str("\n\n");
-    memory_content.push_str(&agent_context);
     
     // Save working memory file with BRAIN knowledge for Claude Code
     let working_memory_path = agent_toolkit_path.join(format!("working_{}.md", Utc::now().timestamp()));
@@ -725,7 +630,13 @@ async fn load_from_direct_files(agent_name: &str, context: Option<&str>, memory_
         std::process::exit(1);
     };
 
-    let full_working_memory = agent_context;
+    // Compose the agent context with its memory, trimmed to the token budget
+    let mut composer = MemoryComposer::new(options.budget);
+    composer.add_pinned(agent_name, &agent_context);
+    composer.add_source(agent_name, &memory_content);
+    let composed = composer.compose();
+    composed.report.print();
+    let full_working_memory = composed.content;
     
     std::fs::write(&working_memory_path, &full_working_memory)
         .map_err(|e| anyhow::anyhow!("Failed to write working memory file: {}", e))?;
@@ -734,57 +645,35 @@ async fn load_from_direct_files(agent_name: &str, context: Option<&str>, memory_
     
     CommandHelpers::print_success(&format!("Agent {} loaded successfully", agent_name));
     
+    let mut record = SessionRecord::new(SessionKind::Agent, vec![agent_name.to_string()]);
+    record.context = context.map(|c| c.to_string());
+    record.memory_path = Some(working_memory_path.clone());
+    record.bypass_permissions = allow;
+    let record = record_session(record, &session_path, options, config);
+    
+    snapshot_agent_memory(&[agent_name.to_string()], "load", config);
+    
     // Offer launch options
-    if auto_yes || allow || CommandHelpers::prompt_confirmation("Launch Claude Code with this agent now?") {
-        // Check if claude CLI is available
-        if has_claude_cli() {
-            // Launch Claude Code with the agent
-            println!("Launching Claude Code with {}...", agent_name.cyan().bold());
-            
-            let mut claude_cmd = Command::new("claude");
-            claude_cmd.arg("code");
-            claude_cmd.arg(&working_memory_path);
-            
-            if allow {
-                claude_cmd.args(&["--permission-mode", "bypassPermissions"]);
-            }
-            
-            let status = claude_cmd.status()
-                .map_err(|e| anyhow::anyhow!("Failed to launch Claude Code: {}", e))?;
-                
-            if !status.success() {
-                CommandHelpers::print_warning("Claude Code exited with a non-zero status");
-            }
-            
-            // Update session with end time
-            let mut session = serde_json::from_str::<AgentSession>(&std::fs::read_to_string(&session_path)?)
-                .map_err(|e| anyhow::anyhow!("Failed to read session data: {}", e))?;
-                
-            session.end_time = Some(Utc::now().to_rfc3339());
-            
-            let session_json = serde_json::to_string_pretty(&session)
-                .map_err(|e| anyhow::anyhow!("Failed to serialize session data: {}", e))?;
-                
-            std::fs::write(&session_path, session_json)
-                .map_err(|e| anyhow::anyhow!("Failed to update session data: {}", e))?;
-        } else {
-            CommandHelpers::print_warning("Claude CLI not found. Please use one of the following methods:");
-            CommandHelpers::print_info(&format!("  cat {} | claude code", working_memory_path.display()));
-            CommandHelpers::print_info(&format!("  # or"));
-            CommandHelpers::print_info(&format!("  claude code < {}", working_memory_path.display()));
-        }
+    let launcher = configured_launcher()?;
+    let launch_prompt = format!("Launch {} with this agent now?", launcher.display_name());
+    if auto_yes || allow || CommandHelpers::prompt_confirmation(&launch_prompt) {
+        launch_agent_session(launcher.as_ref(), &LaunchRequest {
+            agent_name: agent_name.to_string(),
+            memory_file: Some(working_memory_path.clone()),
+            bypass_permissions: allow,
+            ..Default::default()
+        }, &session_path, record.as_ref(), config)?;
+        snapshot_agent_memory(&[agent_name.to_string()], "session end", config);
     } else {
-        CommandHelpers::print_info("To use this agent in Claude Code:");
-        CommandHelpers::print_info(&format!("  cat {} | claude code", working_memory_path.display()));
-        CommandHelpers::print_info(&format!("  # or"));
-        CommandHelpers::print_info(&format!("  claude code < {}", working_memory_path.display()));
+        print_manual_launch_hint(launcher.as_ref(), &working_memory_path, "this agent");
+        hand_off_session_record(record.as_ref().map(|r| r.id.as_str()), config);
     }
     
     Ok(())
 }
 
 /// Load agent from the legacy AGENTS.md file
-async fn load_from_agents_md(agent_name: &str, context: Option<&str>, path: Option<&Path>, auto_yes: bool, allow: bool, config: &Config) -> Result<()> {
+async fn load_from_agents_md(agent_name: &str, context: Option<&str>, path: Option<&Path>, auto_yes: bool, allow: bool, options: &LoadOptions, config: &Config) -> Result<()> {
     CommandHelpers::print_info("Loading agent from AGENTS.md");
     
     // First check if the agent exists in AGENTS.md
@@ -873,6 +762,11 @@ async fn load_from_agents_md(agent_name: &str, context: Option<&str>, path: Opti
         create_agent_metadata(agent_name, &agent_toolkit_path, &agent_memory_path)
     };
     
+    // Manifest data takes precedence over the stored metadata
+    if let Ok(Some(manifest)) = AgentMetadata::from_manifest(&agent_toolkit_path) {
+        metadata.apply_manifest(&manifest);
+    }
+    
     // Update metadata with current session
     metadata.last_used = Some(Utc::now().to_rfc3339());
     metadata.usage_count += 1;
@@ -926,8 +820,6 @@ async fn load_from_agents_md(agent_name: &str, context: Option<&str>, path: Opti
     
     // Extended contextual data
     let agent_context = generate_agent_context(agent_name, context, &metadata);
-    full_memory.push_str("\n\n");
-    full_memory.push_str(&agent_context);
     
     // Save working memory file with BRAIN knowledge for Claude Code  
     let working_memory_path = agent_toolkit_path.join(format!("working_{}.md", Utc::now().timestamp()));
@@ -963,7 +855,13 @@ async fn load_from_agents_md(agent_name: &str, context: Option<&str>, path: Opti
         std::process::exit(1);
     };
 
-    let full_working_memory = agent_context;
+    // Compose the agent context with its memory, trimmed to the token budget
+    let mut composer = MemoryComposer::new(options.budget);
+    composer.add_pinned(agent_name, &agent_context);
+    composer.add_source(agent_name, &full_memory);
+    let composed = composer.compose();
+    composed.report.print();
+    let full_working_memory = composed.content;
     
     std::fs::write(&working_memory_path, &full_working_memory)
         .map_err(|e| anyhow::anyhow!("Failed to write working memory file: {}", e))?;
@@ -981,50 +879,28 @@ async fn load_from_agents_md(agent_name: &str, context: Option<&str>, path: Opti
     
     CommandHelpers::print_success(&format!("Agent {} loaded successfully", agent_name));
     
+    let mut record = SessionRecord::new(SessionKind::Agent, vec![agent_name.to_string()]);
+    record.context = context.map(|c| c.to_string());
+    record.memory_path = Some(working_memory_path.clone());
+    record.bypass_permissions = allow;
+    let record = record_session(record, &session_path, options, config);
+    
+    snapshot_agent_memory(&[agent_name.to_string()], "load", config);
+    
     // Offer launch options
-    if auto_yes || allow || CommandHelpers::prompt_confirmation("Launch Claude Code with this agent now?") {
-        // Check if claude CLI is available
-        if has_claude_cli() {
-            // Launch Claude Code with the agent
-            println!("Launching Claude Code with {}...", agent_name.cyan().bold());
-            
-            let mut claude_cmd = Command::new("claude");
-            claude_cmd.arg("code");
-            claude_cmd.arg(&working_memory_path);
-            
-            if allow {
-                claude_cmd.args(&["--permission-mode", "bypassPermissions"]);
-            }
-            
-            let status = claude_cmd.status()
-                .map_err(|e| anyhow::anyhow!("Failed to launch Claude Code: {}", e))?;
-                
-            if !status.success() {
-                CommandHelpers::print_warning("Claude Code exited with a non-zero status");
-            }
-            
-            // Update session with end time
-            let mut session = serde_json::from_str::<AgentSession>(&std::fs::read_to_string(&session_path)?)
-                .map_err(|e| anyhow::anyhow!("Failed to read session data: {}", e))?;
-                
-            session.end_time = Some(Utc::now().to_rfc3339());
-            
-            let session_json = serde_json::to_string_pretty(&session)
-                .map_err(|e| anyhow::anyhow!("Failed to serialize session data: {}", e))?;
-                
-            std::fs::write(&session_path, session_json)
-                .map_err(|e| anyhow::anyhow!("Failed to update session data: {}", e))?;
-        } else {
-            CommandHelpers::print_warning("Claude CLI not found. Please use one of the following methods:");
-            CommandHelpers::print_info(&format!("  cat {} | claude code", working_memory_path.display()));
-            CommandHelpers::print_info(&format!("  # or"));
-            CommandHelpers::print_info(&format!("  claude code < {}", working_memory_path.display()));
-        }
+    let launcher = configured_launcher()?;
+    let launch_prompt = format!("Launch {} with this agent now?", launcher.display_name());
+    if auto_yes || allow || CommandHelpers::prompt_confirmation(&launch_prompt) {
+        launch_agent_session(launcher.as_ref(), &LaunchRequest {
+            agent_name: agent_name.to_string(),
+            memory_file: Some(working_memory_path.clone()),
+            bypass_permissions: allow,
+            ..Default::default()
+        }, &session_path, record.as_ref(), config)?;
+        snapshot_agent_memory(&[agent_name.to_string()], "session end", config);
     } else {
-        CommandHelpers::print_info("To use this agent in Claude Code:");
-        CommandHelpers::print_info(&format!("  cat {} | claude code", working_memory_path.display()));
-        CommandHelpers::print_info(&format!("  # or"));
-        CommandHelpers::print_info(&format!("  claude code < {}", working_memory_path.display()));
+        print_manual_launch_hint(launcher.as_ref(), &working_memory_path, "this agent");
+        hand_off_session_record(record.as_ref().map(|r| r.id.as_str()), config);
     }
     
     Ok(())
@@ -1039,7 +915,137 @@ pub async fn execute_task(description: &str, agent_name: &str, autonomous: bool,
         "cyan"
     );
     
-    // Create enhanced task context for the agent
+    let operating_mode = if autonomous {
+        "Autonomous - You have full permission to execute all necessary actions without asking for approval. Proceed with confidence."
+    } else {
+        "Interactive - Ask for permission before executing potentially impactful actions."
+    };
+    let task_context = build_task_context(description, context, output, operating_mode);
+    
+    println!("📋 Task: {}", description.cyan().bold());
+    println!("🤖 Agent: {}", agent_name.yellow().bold());
+    println!("⚡ Mode: {}", if autonomous { "Autonomous".green() } else { "Interactive".blue() });
+    
+    // Load the agent with task context
+    load_agent(agent_name, Some(&task_context), path, true, autonomous, &LoadOptions::default(), config).await
+}
+
+/// Options for running a task without an interactive session
+pub struct HeadlessTaskOptions<'a> {
+    pub timeout: Option<Duration>,
+
+    /// Memory file overriding the agent's own
+    pub memory_path: Option<&'a Path>,
+
+    /// Print the result as JSON instead of a summary
+    pub json: bool,
+}
+
+/// Run a task non-interactively: output goes to a log in the session directory and a
+/// JSON result is written next to it. Fails when the run fails or times out.
+pub async fn execute_task_headless(description: &str, agent_name: &str, autonomous: bool, context: Option<&str>, output: Option<&str>, options: &HeadlessTaskOptions<'_>, config: &Config) -> Result<()> {
+    let launcher = configured_launcher()?;
+    if !launcher.is_available() {
+        return Err(anyhow::anyhow!("{} not available for headless execution", launcher.display_name()));
+    }
+    
+    let (memory, _) = match options.memory_path {
+        Some(path) => (std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?, path.display().to_string()),
+        None => read_agent_memory(agent_name, config)?,
+    };
+    
+    let operating_mode = "Headless - Nobody is watching this session and questions cannot be answered. \
+        Work autonomously, make reasonable assumptions and state them, and finish with a summary of what you did.";
+    let task_context = build_task_context(description, context, output, operating_mode);
+    
+    let started = Utc::now();
+    let sessions_dir = config.ci_path.join("AGENTS").join(agent_name).join("sessions");
+    let session_dir = sessions_dir.join(started.timestamp_millis().to_string());
+    std::fs::create_dir_all(&session_dir)
+        .map_err(|e| anyhow::anyhow!("Failed to create session directory: {}", e))?;
+    
+    let memory_file = session_dir.join("memory.md");
+    std::fs::write(&memory_file, format!("{}\n\n---\n\n{}", memory, task_context))?;
+    
+    let working_dir = std::env::current_dir()?;
+    let request = LaunchRequest {
+        agent_name: agent_name.to_string(),
+        memory_file: Some(memory_file),
+        prompt: Some(format!("You are {}. Complete the task assignment at the end of the provided context.", agent_name)),
+        bypass_permissions: autonomous,
+        cwd: Some(working_dir.clone()),
+        headless: true,
+        ..Default::default()
+    };
+    let spec = launcher.prepare(&request)?;
+    
+    let mut session = AgentSession {
+        agent_name: agent_name.to_string(),
+        start_time: started.to_rfc3339(),
+        context: context.map(|c| c.to_string()),
+        end_time: None,
+        output_path: Some(session_dir.join(OUTPUT_LOG).display().to_string()),
+    };
+    let session_path = sessions_dir.join(format!("{}.json", started.timestamp_millis()));
+    std::fs::write(&session_path, serde_json::to_string_pretty(&session)?)?;
+    
+    let mut record = SessionRecord::new(SessionKind::Headless, vec![agent_name.to_string()]);
+    record.started_at = started;
+    record.project = Some(working_dir.clone());
+    record.task = Some(description.to_string());
+    record.context = context.map(|c| c.to_string());
+    record.memory_path = Some(session_dir.join("memory.md"));
+    record.output_path = Some(session_dir.join(OUTPUT_LOG));
+    record.bypass_permissions = autonomous;
+    let record = record_session(record, &session_path, &LoadOptions::default(), config);
+    
+    if !options.json {
+        println!("🎯 Running {} headless: {}", agent_name.yellow().bold(), description.cyan());
+        println!("   Log: {}", session_dir.join(OUTPUT_LOG).display());
+    }
+    
+    snapshot_agent_memory(std::slice::from_ref(&agent_name.to_string()), "load", config);
+    let result = HeadlessRun {
+        agent: agent_name,
+        task: description,
+        session_dir: &session_dir,
+        timeout: options.timeout,
+        repo_dir: &working_dir,
+    }.execute(&spec)?;
+    snapshot_agent_memory(std::slice::from_ref(&agent_name.to_string()), "session end", config);
+    
+    session.end_time = Some(result.finished_at.to_rfc3339());
+    std::fs::write(&session_path, serde_json::to_string_pretty(&session)?)?;
+    finish_session_record(record.as_ref().map(|r| r.id.as_str()), result.status == HeadlessStatus::Success, config);
+    
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        let status = match result.status {
+            HeadlessStatus::Success => "success".green(),
+            HeadlessStatus::Failed => "failed".red(),
+            HeadlessStatus::TimedOut => "timed out".red(),
+        };
+        println!("   Status: {} ({})", status, format_duration_secs(result.duration_secs as u64));
+        println!("   Files changed: {}", result.files_changed.len());
+        for file in &result.files_changed {
+            println!("     {}", file);
+        }
+        println!("   Result: {}", session_dir.join(RESULT_FILE).display());
+    }
+    
+    match result.status {
+        HeadlessStatus::Success => Ok(()),
+        HeadlessStatus::Failed => Err(anyhow::anyhow!("Task failed with exit code {}",
+            result.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "unknown".to_string()))),
+        HeadlessStatus::TimedOut => Err(anyhow::anyhow!("Task timed out after {}",
+            format_duration_secs(result.timeout_secs.unwrap_or_default()))),
+    }
+}
+
+/// Task assignment appended to the agent's memory
+fn build_task_context(description: &str, context: Option<&str>, output: Option<&str>, operating_mode: &str) -> String {
     let mut task_context = format!("# Task Assignment\n\n**OBJECTIVE**: {}\n\n", description);
     
     if let Some(ctx) = context {
@@ -1050,12 +1056,7 @@ pub async fn execute_task(description: &str, agent_name: &str, autonomous: bool,
         task_context.push_str(&format!("**REQUIRED OUTPUT**: Please save results to '{}'\n\n", output_file));
     }
     
-    task_context.push_str("**OPERATING MODE**: ");
-    if autonomous {
-        task_context.push_str("Autonomous - You have full permission to execute all necessary actions without asking for approval. Proceed with confidence.\n\n");
-    } else {
-        task_context.push_str("Interactive - Ask for permission before executing potentially impactful actions.\n\n");
-    }
+    task_context.push_str(&format!("**OPERATING MODE**: {}\n\n", operating_mode));
     
     task_context.push_str("**INSTRUCTIONS**: \n");
     task_context.push_str("- Focus on completing the specified objective\n");
@@ -1063,12 +1064,7 @@ pub async fn execute_task(description: &str, agent_name: &str, autonomous: bool,
     task_context.push_str("- Provide progress updates as you work\n");
     task_context.push_str("- Be thorough and systematic in your approach\n\n");
     
-    println!("📋 Task: {}", description.cyan().bold());
-    println!("🤖 Agent: {}", agent_name.yellow().bold());
-    println!("⚡ Mode: {}", if autonomous { "Autonomous".green() } else { "Interactive".blue() });
-    
-    // Load the agent with task context
-    load_agent(agent_name, Some(&task_context), path, true, autonomous, config).await
+    task_context
 }
 
 pub async fn adapt_session(path: &Path, config: &Config) -> Result<()> {
@@ -1094,10 +1090,11 @@ pub async fn adapt_session(path: &Path, config: &Config) -> Result<()> {
     
     CommandHelpers::print_info(&format!("Loading adaptive memory from: {}", adapt_file.display()));
     
-    // Check if claude CLI is available
-    if !has_claude_cli() {
-        CommandHelpers::print_error("Claude CLI not found. Please install it first.");
-        return Err(anyhow::anyhow!("Claude CLI not available"));
+    // Check if the configured assistant is available
+    let launcher = configured_launcher()?;
+    if !launcher.is_available() {
+        CommandHelpers::print_error(&format!("{} not found. Please install it first.", launcher.display_name()));
+        return Err(anyhow::anyhow!("{} not available", launcher.display_name()));
     }
     
     // Create a temporary file with the adapt content
@@ -1107,27 +1104,71 @@ pub async fn adapt_session(path: &Path, config: &Config) -> Result<()> {
     std::fs::write(&temp_file, &adapt_content)
         .map_err(|e| anyhow::anyhow!("Failed to create temporary file: {}", e))?;
     
-    CommandHelpers::print_success("Launching Claude Code with adaptive configuration...");
+    CommandHelpers::print_success(&format!("Launching {} with adaptive configuration...", launcher.display_name()));
     
-    // Launch Claude Code with the adapt content
-    let status = Command::new("claude")
-        .arg("code")
-        .arg(&temp_file)
-        .status()
-        .map_err(|e| anyhow::anyhow!("Failed to launch Claude Code: {}", e))?;
+    // Launch the assistant with the adapt content
+    let outcome = launcher.launch(&LaunchRequest {
+        agent_name: "adaptive".to_string(),
+        memory_file: Some(temp_file.clone()),
+        ..Default::default()
+    });
     
     // Clean up temp file
     let _ = std::fs::remove_file(&temp_file);
     
-    if !status.success() {
-        CommandHelpers::print_warning("Claude Code exited with a non-zero status");
+    if !outcome?.success {
+        CommandHelpers::print_warning(&format!("{} exited with a non-zero status", launcher.display_name()));
     }
     
     Ok(())
 }
 
+/// Read an agent's memory from its directory or AGENTS.md.
+/// Returns the memory and a description of where it was found.
+fn read_agent_memory(agent_name: &str, config: &Config) -> Result<(String, String)> {
+    let agent_directory = config.ci_path.join("AGENTS").join(agent_name);
+    let direct_memory_path = agent_directory.join(format!("{}.md", agent_name));
+    let memory_path = agent_directory.join(format!("{}_memory.md", agent_name));
+    let agents_md_path = config.ci_path.join("AGENTS.md");
+    
+    for candidate in [&direct_memory_path, &memory_path] {
+        if candidate.exists() {
+            let content = std::fs::read_to_string(candidate)
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", candidate.display(), e))?;
+            let content = resolve_memory_inheritance(agent_name, &content, config)?;
+            return Ok((content, candidate.display().to_string()));
+        }
+    }
+    
+    if agents_md_path.exists() {
+        let agents_content = std::fs::read_to_string(&agents_md_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read AGENTS.md: {}", e))?;
+        if agent_exists(&agents_content, agent_name) {
+            return Ok((extract_agent_memory(&agents_content, agent_name), "AGENTS.md".to_string()));
+        }
+        return Err(anyhow::anyhow!("Agent '{}' not found in AGENTS.md", agent_name));
+    }
+    
+    Err(anyhow::anyhow!("No memory files found for agent: {}", agent_name))
+}
+
+/// Apply `extends`/`mixins` frontmatter of an agent memory file
+fn resolve_memory_inheritance(agent_name: &str, content: &str, config: &Config) -> Result<String> {
+    MemoryResolver::new(&config.ci_path.join("AGENTS"))
+        .resolve_content(agent_name, content)
+}
+
 /// Create agent metadata from available information
 fn create_agent_metadata(agent_name: &str, toolkit_path: &Path, memory_path: &Path) -> AgentMetadata {
+    // Prefer the agent.toml manifest when the agent ships one
+    if let Ok(Some(mut manifest)) = AgentMetadata::from_manifest(toolkit_path) {
+        manifest.created_at = Utc::now().to_rfc3339();
+        if manifest.memory_path.is_empty() {
+            manifest.memory_path = memory_path.display().to_string();
+        }
+        return manifest;
+    }
+    
     // Extract description from memory file if available
     let description = if memory_path.exists() {
         if let Ok(content) = std::fs::read_to_string(memory_path) {
@@ -1176,13 +1217,194 @@ fn generate_agent_context(agent_name: &str, context_type: Option<&str>, metadata
     context
 }
 
-/// Check if claude CLI is available on the system
-fn has_claude_cli() -> bool {
-    Command::new("which")
-        .arg("claude")
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+/// Launch an agent session with the configured assistant and record its end time.
+/// Prints manual instructions instead when the assistant is not installed.
+fn launch_agent_session(launcher: &dyn AgentLauncher, request: &LaunchRequest, session_path: &Path, record: Option<&SessionRecord>, config: &Config) -> Result<()> {
+    if !launcher.is_available() {
+        CommandHelpers::print_warning(&format!("{} not found. Launch it manually with:", launcher.display_name()));
+        if let Some(memory_file) = &request.memory_file {
+            CommandHelpers::print_info(&format!("  {}", launcher.manual_hint(memory_file)));
+        }
+        hand_off_session_record(record.map(|r| r.id.as_str()), config);
+        return Ok(());
+    }
+    
+    println!("Launching {} with {}...", launcher.display_name(), request.agent_name.cyan().bold());
+    
+    let transcript = record.and_then(|r| r.transcript_path.clone());
+    if let Some(path) = &transcript {
+        println!("📝 Recording transcript to {}", path.display());
+    }
+    let outcome = launcher.launch(&LaunchRequest {
+        transcript,
+        session_id: record.map(|r| r.id.clone()),
+        ..request.clone()
+    })?;
+    if !outcome.success {
+        CommandHelpers::print_warning(&format!("{} exited with a non-zero status", launcher.display_name()));
+    }
+    
+    finish_session_record(record.map(|r| r.id.as_str()), outcome.success, config);
+    
+    // Update session with end time
+    let mut session = serde_json::from_str::<AgentSession>(&std::fs::read_to_string(session_path)?)
+        .map_err(|e| anyhow::anyhow!("Failed to read session data: {}", e))?;
+        
+    session.end_time = Some(Utc::now().to_rfc3339());
+    
+    let session_json = serde_json::to_string_pretty(&session)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize session data: {}", e))?;
+        
+    std::fs::write(session_path, session_json)
+        .map_err(|e| anyhow::anyhow!("Failed to update session data: {}", e))?;
+    
+    Ok(())
+}
+
+/// Relaunch a stored session through the load path with its agents, task, permission
+/// mode and working directory, prefixed with a summary of what happened since it started
+pub async fn resume_session(session_id: &str, record_transcript: bool, config: &Config) -> Result<()> {
+    let record = SessionStore::new(&config.ci_path).load(session_id)?;
+    let plan = ResumePlan::from_record(&record)?;
+    
+    CommandHelpers::print_command_header(
+        &format!("Resume session {}", record.id), 
+        "⏯️", 
+        "Intelligence & Discovery", 
+        "blue"
+    );
+    println!("📋 {}", record.title().cyan().bold());
+    println!("🤖 Agent(s): {}", plan.agents.join(", ").yellow().bold());
+    println!("🕒 Started: {}", record.started_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    
+    match &plan.cwd {
+        Some(cwd) => {
+            std::env::set_current_dir(cwd)
+                .map_err(|e| anyhow::anyhow!("Failed to enter {}: {}", cwd.display(), e))?;
+            println!("📁 Working directory: {}", cwd.display());
+        },
+        None => if let Some(project) = &record.project {
+            CommandHelpers::print_warning(&format!("Project directory {} no longer exists; staying in the current one", project.display()));
+        },
+    }
+    println!();
+    
+    let options = LoadOptions {
+        record: record_transcript,
+        resumed_from: Some(record.id.clone()),
+        ..Default::default()
+    };
+    let parallel_options = plan.parallel_settings.as_ref()
+        .map(ParallelOptions::from_settings)
+        .unwrap_or_default();
+    load_agents_with_task(
+        &plan.agents,
+        Some(&plan.context),
+        None,
+        true,
+        plan.bypass_permissions,
+        &options,
+        plan.task.as_deref(),
+        plan.parallel,
+        &parallel_options,
+        config
+    ).await
+}
+
+/// Mirror a session into the session store, pointing back at its legacy session file,
+/// and reserve its transcript when `--record` was given. The legacy file is what older
+/// tooling reads, so a failing store only warns.
+fn record_session(mut record: SessionRecord, legacy_path: &Path, options: &LoadOptions, config: &Config) -> Option<SessionRecord> {
+    if record.project.is_none() {
+        record.project = std::env::current_dir().ok();
+    }
+    if record.task.is_none() {
+        record.task = record.context.as_deref().and_then(task_objective);
+    }
+    if record.git.is_none() {
+        record.git = record.project.as_deref().and_then(GitProvenance::start);
+    }
+    record.source = Some(absolute(legacy_path).display().to_string());
+    record.resumed_from = options.resumed_from.clone();
+    record.launcher_pid = Some(std::process::id());
+    
+    let store = SessionStore::new(&config.ci_path);
+    if options.record && record.kind != SessionKind::Headless && record.kind != SessionKind::Parallel {
+        if transcript::is_supported() {
+            record.transcript_path = Some(transcript::transcript_path(&store.session_dir(&record.id)));
+        } else {
+            CommandHelpers::print_warning("Transcript capture needs script(1), which is not installed; not recording");
+        }
+    }
+    
+    match store.save(&record) {
+        Ok(()) => Some(record),
+        Err(e) => {
+            CommandHelpers::print_warning(&format!("Session not recorded in the session store: {}", e));
+            None
+        }
+    }
+}
+
+/// The assistant is started by hand outside ci, which never sees the session end;
+/// record that its end is unknown rather than leaving it active
+fn hand_off_session_record(record_id: Option<&str>, config: &Config) {
+    if let Some(id) = record_id {
+        if let Err(e) = SessionStore::new(&config.ci_path).update(id, |record| record.status = SessionStatus::Unknown) {
+            CommandHelpers::print_warning(&format!("Failed to update session {}: {}", id, e));
+        }
+    }
+}
+
+fn finish_session_record(record_id: Option<&str>, success: bool, config: &Config) {
+    if let Some(id) = record_id {
+        if let Err(e) = SessionStore::new(&config.ci_path).update(id, |record| record.finish(success)) {
+            CommandHelpers::print_warning(&format!("Failed to update session {}: {}", id, e));
+        }
+    }
+}
+
+/// Carry the overall state of a parallel session over to its store record. `live` is
+/// set when the session has just ended, so the repository still shows its end state.
+fn sync_parallel_record(record_id: Option<&str>, state: &SupervisorState, live: bool, config: &Config) {
+    let Some(id) = record_id else { return };
+    let result = SessionStore::new(&config.ci_path).update(id, |record| {
+        record.status = parallel_status(state);
+        record.ended_at = state.instances.iter().filter_map(|i| i.ended_at).max()
+            .filter(|_| !state.instances.iter().any(|i| i.status == InstanceStatus::Running));
+        match (record.ended_at.is_some(), live) {
+            (true, true) => record.finish_git(),
+            (true, false) => record.finish_git_at_end_time(),
+            (false, _) => {},
+        }
+    });
+    if let Err(e) = result {
+        CommandHelpers::print_warning(&format!("Failed to update session {}: {}", id, e));
+    }
+}
+
+/// Append text to a file, creating it if needed
+fn append_to_file(path: &Path, text: &str) -> Result<()> {
+    use std::io::Write;
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(text.as_bytes())?;
+    Ok(())
+}
+
+/// Record a memory snapshot of each agent so edits made during a session can be reverted
+fn snapshot_agent_memory(agent_names: &[String], reason: &str, config: &Config) {
+    let history = MemoryHistory::new(&config.ci_path.join("AGENTS"));
+    for agent_name in agent_names {
+        if let Err(e) = history.snapshot(agent_name, reason) {
+            CommandHelpers::print_warning(&format!("Could not snapshot memory of {}: {}", agent_name, e));
+        }
+    }
+}
+
+/// Print how to start a session by hand with the configured assistant
+fn print_manual_launch_hint(launcher: &dyn AgentLauncher, memory_path: &Path, subject: &str) {
+    CommandHelpers::print_info(&format!("To use {} in {}:", subject, launcher.display_name()));
+    CommandHelpers::print_info(&format!("  {}", launcher.manual_hint(memory_path)));
 }
 
 /// Check if the agent exists in the AGENTS.md content
@@ -1479,6 +1701,82 @@ fn scan_for_integrated_projects(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Restarts per instance allowed by `--restart-on-failure` unless `--max-restarts` says otherwise
+pub const DEFAULT_MAX_RESTARTS: u32 = 1;
+
+/// Supervision options for parallel agent sessions
+#[derive(Debug, Clone)]
+pub struct ParallelOptions {
+    /// Wait for every instance to exit before returning
+    pub wait: bool,
+    
+    /// Overall timeout in seconds when waiting
+    pub timeout_secs: Option<u64>,
+    
+    /// Relaunch instances that exit with a non-zero status
+    pub restart_on_failure: bool,
+    
+    /// Maximum restarts per instance
+    pub max_restarts: u32,
+    
+    /// Give every instance its own git worktree and branch
+    pub worktrees: bool,
+    
+    /// Split the repository into one shard of paths per instance
+    pub shard_by: Option<ShardStrategy>,
+}
+
+impl Default for ParallelOptions {
+    fn default() -> Self {
+        Self {
+            wait: false,
+            timeout_secs: None,
+            restart_on_failure: false,
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            worktrees: false,
+            shard_by: None,
+        }
+    }
+}
+
+impl ParallelOptions {
+    /// Settings kept on the session record so a resume runs the same way
+    pub fn settings(&self) -> ParallelSettings {
+        ParallelSettings {
+            wait: self.wait,
+            timeout_secs: self.timeout_secs,
+            restart_on_failure: self.restart_on_failure,
+            max_restarts: self.max_restarts,
+            worktrees: self.worktrees,
+            shard_by: self.shard_by.as_ref().map(|s| s.to_string()),
+        }
+    }
+    
+    pub fn from_settings(settings: &ParallelSettings) -> Self {
+        Self {
+            wait: settings.wait,
+            timeout_secs: settings.timeout_secs,
+            restart_on_failure: settings.restart_on_failure,
+            max_restarts: settings.max_restarts,
+            worktrees: settings.worktrees,
+            shard_by: settings.shard_by.as_deref().and_then(|s| s.parse().ok()),
+        }
+    }
+}
+
+/// Settings of one load that reach the session it starts
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    /// Token budget for the composed memory
+    pub budget: Option<usize>,
+    
+    /// Record a transcript of the session (`--record`)
+    pub record: bool,
+    
+    /// Store id of the session this load resumes
+    pub resumed_from: Option<String>,
+}
+
 /// Enhanced load_agents function that supports optional task execution
 pub async fn load_agents_with_task(
     agent_names: &[String], 
@@ -1486,8 +1784,10 @@ pub async fn load_agents_with_task(
     path: Option<&Path>, 
     auto_yes: bool, 
     allow: bool, 
+    options: &LoadOptions,
     task: Option<&str>,
     parallel: bool,
+    parallel_options: &ParallelOptions,
     config: &Config
 ) -> Result<()> {
     if agent_names.is_empty() {
@@ -1498,8 +1798,8 @@ pub async fn load_agents_with_task(
     let expanded_agents = expand_agent_multipliers(agent_names)?;
     
     // Handle parallel execution for multiple agents or when explicitly requested
-    if parallel && expanded_agents.len() > 1 && task.is_some() {
-        return execute_parallel_agents_expanded(&expanded_agents, context, path, auto_yes, allow, task.unwrap(), config).await;
+    if let Some(task) = task.filter(|_| parallel && expanded_agents.len() > 1) {
+        return execute_parallel_agents_expanded(&expanded_agents, context, path, auto_yes, allow, options, task, parallel_options, config).await;
     }
     
     // For non-parallel case with multipliers, error if more than one instance
@@ -1575,10 +1875,60 @@ pub async fn load_agents_with_task(
         path, 
         auto_yes, 
         allow, 
+        options,
         config
     ).await
 }
 
+/// Rank agents against a task description and let the user confirm the team.
+/// Returns `None` when nothing matched or the user cancelled.
+pub fn select_agents_for_task(description: &str, top: usize, config: &Config) -> Result<Option<Vec<String>>> {
+    CommandHelpers::print_command_header(
+        "Find agents for task", 
+        "🔎", 
+        "Intelligence & Discovery", 
+        "cyan"
+    );
+    
+    println!("📋 Task: {}", description.cyan().bold());
+    println!();
+    
+    let matches = rank_agents(&config.ci_path.join("AGENTS"), description)?;
+    if matches.is_empty() {
+        CommandHelpers::print_warning("No agent matches this task. Add capabilities to agent.toml manifests or name agents explicitly.");
+        return Ok(None);
+    }
+    
+    print_matches(&matches, top);
+    println!();
+    
+    let team = suggested_team(&matches, top);
+    let answer = CommandHelpers::prompt_input(
+        "Agents to load (names or numbers, comma-separated; 'n' to cancel)",
+        Some(&team.join(","))
+    )?;
+    
+    if answer.eq_ignore_ascii_case("n") || answer.eq_ignore_ascii_case("no") {
+        CommandHelpers::print_info("Cancelled");
+        return Ok(None);
+    }
+    
+    let mut selected = Vec::new();
+    for choice in answer.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+        let name = match choice.parse::<usize>() {
+            Ok(number) => matches.get(number.wrapping_sub(1))
+                .map(|m| m.name.clone())
+                .ok_or_else(|| anyhow::anyhow!("No match number {}", number))?,
+            Err(_) => choice.to_string(),
+        };
+        if !selected.contains(&name) {
+            selected.push(name);
+        }
+    }
+    
+    Ok(Some(selected))
+}
+
 /// Execute multiple agents in parallel sessions for collaborative task work (with pre-expanded agents)
 async fn execute_parallel_agents_expanded(
     expanded_agents: &[AgentInstance],
@@ -1586,10 +1936,11 @@ async fn execute_parallel_agents_expanded(
     path: Option<&Path>,
     auto_yes: bool,
     allow: bool,
+    options: &LoadOptions,
     task: &str,
+    parallel_options: &ParallelOptions,
     config: &Config
 ) -> Result<()> {
-    use std::process::Command;
     use std::time::{SystemTime, UNIX_EPOCH};
     
     let total_agents = expanded_agents.len();
@@ -1627,7 +1978,8 @@ async fn execute_parallel_agents_expanded(
         if allow { "Autonomous".green() } else { "Interactive".blue() },
         "Parallel Sessions".magenta().bold()
     );
-    println!("👥 Sessions: {} independent Claude Code instances", total_agents);
+    let launcher = configured_launcher()?;
+    println!("👥 Sessions: {} independent {} instances", total_agents, launcher.display_name());
     println!();
     
     // Create shared coordination directory
@@ -1664,10 +2016,46 @@ async fn execute_parallel_agents_expanded(
     
     println!("📁 Coordination directory: {}", coordination_dir.display());
     println!("📄 Task coordination file: {}", coordination_file.display());
+    
+    let mut worktrees = if parallel_options.worktrees {
+        let session = WorktreeSession::create(&session_id, &std::env::current_dir()?)?;
+        println!("🌳 Worktrees: one branch per instance from {} ({})",
+            session.base_branch.as_deref().unwrap_or("detached HEAD"),
+            &session.base_commit[..session.base_commit.len().min(8)]
+        );
+        Some(session)
+    } else {
+        None
+    };
+    
+    let shard_plan = match &parallel_options.shard_by {
+        Some(strategy) => {
+            let plan = plan_shards(&std::env::current_dir()?, strategy, total_agents, &[config.ci_path.join("AGENTS")])?;
+            plan.save(&coordination_dir)?;
+            let assigned: usize = plan.shards.iter().map(|s| s.file_count()).sum();
+            println!("🧩 Shards: {} files split by {} across {} instances", assigned, plan.strategy, total_agents);
+            Some(plan)
+        },
+        None => None,
+    };
     println!();
     
-    // Launch each agent instance in separate Claude Code session
-    let mut handles = Vec::new();
+    let mut agent_names: Vec<String> = unique_agents.iter().map(|name| name.to_string()).collect();
+    agent_names.sort();
+    snapshot_agent_memory(&agent_names, "load", config);
+    
+    // Launch each agent instance in a separate assistant session
+    let mut supervisor = AgentSupervisor::new(&session_id, task, &coordination_dir);
+    supervisor.record_git_base(&std::env::current_dir()?);
+    
+    let mut record = SessionRecord::new(SessionKind::Parallel, agent_names.clone());
+    record.name = Some(session_id.clone());
+    record.task = Some(task.to_string());
+    record.context = context.map(|c| c.to_string());
+    record.memory_path = Some(coordination_dir.clone());
+    record.bypass_permissions = allow;
+    record.parallel = Some(parallel_options.settings());
+    let record = record_session(record, &coordination_dir, options, config);
     
     for (index, agent_instance) in expanded_agents.iter().enumerate() {
         let agent_task_context = create_parallel_agent_context(
@@ -1684,31 +2072,67 @@ async fn execute_parallel_agents_expanded(
         
         println!("🚀 Launching {} in session {}...", agent_instance.display_name().cyan().bold(), index + 1);
         
-        // Create individual agent memory file for this session
-        let agent_session_file = coordination_dir.join(format!("{}_session_memory.md", agent_instance.file_safe_name()));
-        std::fs::write(&agent_session_file, &agent_task_context)?;
+        let mut agent_task_context = agent_task_context;
+        let worktree = match worktrees.as_mut() {
+            Some(session) => {
+                let worktree = session.add(&agent_instance.display_name(), &agent_instance.file_safe_name())?;
+                session.save(&coordination_dir)?;
+                agent_task_context.push_str(&format!(
+                    "**WORKTREE**: You are working in your own git worktree at `{}` on branch `{}`. \
+                    Other instances cannot see your changes until they are merged. Commit your work on this branch.\n\n",
+                    worktree.path.display(), worktree.branch
+                ));
+                append_to_file(&coordination_file, &format!("- **{}**: branch `{}`\n", agent_instance.display_name(), worktree.branch))?;
+                println!("   🌳 Worktree: {} ({})", worktree.path.display(), worktree.branch.cyan());
+                Some(worktree)
+            },
+            None => None,
+        };
         
-        // Launch Claude Code with the agent's specific context
-        let mut claude_cmd = Command::new("claude");
+        if let Some(plan) = &shard_plan {
+            let shard = &plan.shards[index];
+            agent_task_context.push_str(&shard.assignment(index, total_agents, &plan.strategy));
+            append_to_file(&coordination_file, &format!("- **{}**: shard {} — {}\n", agent_instance.display_name(), index + 1, shard.summary()))?;
+            println!("   🧩 Shard {}: {}", index + 1, shard.summary());
+        }
         
-        if allow {
-            claude_cmd.arg("--permission-mode").arg("bypassPermissions");
+        // Compose the parallel context with the agent's memory, trimmed to the token budget
+        let mut composer = MemoryComposer::new(options.budget);
+        composer.add_pinned(&agent_instance.display_name(), &agent_task_context);
+        if let Ok((agent_memory, _)) = read_agent_memory(&agent_instance.name, config) {
+            composer.add_source(&agent_instance.name, &agent_memory);
         }
+        let composed = composer.compose();
+        composed.report.print();
+        let agent_task_context = composed.content;
         
-        // Pass the agent memory content as the initial prompt
-        claude_cmd.arg(&agent_task_context);
+        // Create individual agent memory file for this session
+        let agent_session_file = coordination_dir.join(format!("{}_session_memory.md", agent_instance.file_safe_name()));
+        std::fs::write(&agent_session_file, &agent_task_context)?;
         
-        // Set window title for the agent session
-        claude_cmd.env("CLAUDE_WINDOW_TITLE", format!("[{}] {}", agent_instance.display_name(), task));
+        // Launch the configured assistant with the agent's specific context as the initial prompt
+        let launch_spec = launcher.prepare(&LaunchRequest {
+            agent_name: agent_instance.name.clone(),
+            title: Some(format!("[{}] {}", agent_instance.display_name(), task)),
+            prompt: Some(agent_task_context.clone()),
+            bypass_permissions: allow,
+            cwd: worktree.map(|w| w.path),
+            session_id: record.as_ref().map(|r| r.id.clone()),
+            ..Default::default()
+        })?;
         
         println!("   📝 Memory file: {}", agent_session_file.display());
         println!("   🪟 Window title: [{}] {}", agent_instance.display_name(), task);
         
-        // Launch the process
-        match claude_cmd.spawn() {
-            Ok(child) => {
-                handles.push((agent_instance.display_name(), child));
-                println!("   ✅ {} session started successfully", agent_instance.display_name().green());
+        // Launch the process under supervision
+        match supervisor.spawn(
+            &agent_instance.display_name(),
+            &agent_instance.name,
+            &agent_instance.file_safe_name(),
+            launch_spec,
+        ) {
+            Ok(pid) => {
+                println!("   ✅ {} session started successfully (pid {})", agent_instance.display_name().green(), pid);
             },
             Err(e) => {
                 CommandHelpers::print_warning(&format!("Failed to launch {} session: {}", agent_instance.display_name(), e));
@@ -1720,23 +2144,335 @@ async fn execute_parallel_agents_expanded(
         println!();
     }
     
-    if handles.is_empty() {
+    if supervisor.running_count() == 0 {
         return Err(anyhow::anyhow!("Failed to launch any agent sessions"));
     }
     
-    println!("🎯 {} agent sessions launched successfully!", handles.len());
+    if let Some(plan) = shard_plan.as_ref().filter(|p| !p.leftovers.is_empty()) {
+        let mut section = String::from("\n## Unassigned Paths\n\n");
+        for leftover in &plan.leftovers {
+            section.push_str(&format!("- `{}` ({})\n", leftover.path, leftover.reason));
+        }
+        append_to_file(&coordination_file, &section)?;
+        
+        CommandHelpers::print_warning(&format!("{} paths were not assigned to any shard:", plan.leftovers.len()));
+        for leftover in plan.leftovers.iter().take(10) {
+            println!("   • {} ({})", leftover.path, leftover.reason.dimmed());
+        }
+        if plan.leftovers.len() > 10 {
+            println!("   • ... and {} more (see {})", plan.leftovers.len() - 10, coordination_file.display());
+        }
+        println!();
+    }
+    
+    println!("🎯 {} agent sessions launched successfully!", supervisor.running_count());
     println!("📊 Monitor progress through individual Claude Code windows");
     println!("📁 Shared coordination: {}", coordination_dir.display());
+    println!("📈 Instance status: {}", format!("ci load status {}", session_id).cyan());
+    if worktrees.is_some() {
+        println!("🌳 Review and merge branches: {}", format!("ci load merge {}", session_id).cyan());
+    }
+    println!();
+    
+    if !parallel_options.wait {
+        println!("💡 {}:", "Coordination Tips".yellow().bold());
+        println!("   • Each agent has access to the shared coordination directory");
+        println!("   • Agents can create files in {} to share findings", coordination_dir.display());
+        println!("   • Use the task coordination file to track overall progress");
+        println!("   • Follow claims and blockers with {}", format!("ci coord board {}", session_id).cyan());
+        println!("   • Sessions run independently - monitor each window for progress");
+        return Ok(());
+    }
+    
+    // Supervise the sessions until every instance has finished
+    let wait_options = WaitOptions {
+        timeout: parallel_options.timeout_secs.map(std::time::Duration::from_secs),
+        restart_on_failure: parallel_options.restart_on_failure,
+        max_restarts: parallel_options.max_restarts,
+    };
+    
+    println!("⏳ Waiting for {} sessions to finish{}...",
+        supervisor.running_count(),
+        parallel_options.timeout_secs.map(|t| format!(" (timeout {})", format_duration_secs(t))).unwrap_or_default()
+    );
+    
+    let state = supervisor.wait(&wait_options, |instance| {
+        snapshot_agent_memory(std::slice::from_ref(&instance.agent_name), "session end", config);
+        let duration = instance.duration_secs.map(format_duration_secs).unwrap_or_default();
+        match instance.status {
+            InstanceStatus::Exited => println!("   ✅ {} exited after {}", instance.display_name.green(), duration),
+            _ => println!("   ❌ {} failed with exit code {} after {}",
+                instance.display_name.red(),
+                instance.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "?".to_string()),
+                duration
+            ),
+        }
+    }).await?;
+    
     println!();
-    println!("💡 {}:", "Coordination Tips".yellow().bold());
-    println!("   • Each agent has access to the shared coordination directory");
-    println!("   • Agents can create files in {} to share findings", coordination_dir.display());
-    println!("   • Use the task coordination file to track overall progress");
-    println!("   • Sessions run independently - monitor each window for progress");
+    print_supervisor_state(state);
+    sync_parallel_record(record.as_ref().map(|r| r.id.as_str()), state, true, config);
+    
+    let unsuccessful = state.instances.len() - state.count(InstanceStatus::Exited);
+    if unsuccessful > 0 {
+        return Err(anyhow::anyhow!("{} of {} agent instances did not finish successfully", unsuccessful, state.instances.len()));
+    }
     
     Ok(())
 }
 
+/// Show live per-instance state for a parallel session, or list recent sessions
+pub async fn load_status(session_id: Option<&str>, config: &Config) -> Result<()> {
+    CommandHelpers::print_command_header(
+        "Parallel session status", 
+        "📈", 
+        "Intelligence & Discovery", 
+        "magenta"
+    );
+    
+    let agents_dir = config.ci_path.join("AGENTS");
+    
+    let session_id = match session_id {
+        Some(id) => id,
+        None => {
+            let mut sessions = Vec::new();
+            if let Ok(entries) = std::fs::read_dir(&agents_dir) {
+                for entry in entries.flatten() {
+                    if let Ok(state) = SupervisorState::load(&entry.path()) {
+                        sessions.push(state);
+                    }
+                }
+            }
+            
+            if sessions.is_empty() {
+                CommandHelpers::print_info("No supervised parallel sessions found.");
+                return Ok(());
+            }
+            
+            sessions.sort_by_key(|s| std::cmp::Reverse(s.started_at));
+            
+            println!("{}", "Recent parallel sessions:".bold());
+            for state in sessions.iter_mut().take(10) {
+                state.refresh_liveness();
+                println!("  {} {} - {} running, {} exited, {} failed",
+                    "•".yellow(),
+                    state.session_id.cyan().bold(),
+                    state.count(InstanceStatus::Running),
+                    state.count(InstanceStatus::Exited),
+                    state.count(InstanceStatus::Failed) + state.count(InstanceStatus::TimedOut)
+                );
+            }
+            println!();
+            println!("Show instance details with: {}", "ci load status <session_id>".cyan());
+            return Ok(());
+        }
+    };
+    
+    let coordination_dir = agents_dir.join(session_id);
+    if !coordination_dir.exists() {
+        return Err(anyhow::anyhow!("Parallel session not found: {}", session_id));
+    }
+    
+    let mut state = SupervisorState::load(&coordination_dir)?;
+    if state.refresh_liveness() {
+        state.save(&coordination_dir)?;
+        let source = absolute(&coordination_dir).display().to_string();
+        if let Ok(Some(record)) = SessionStore::new(&config.ci_path).find_by_source(&source) {
+            sync_parallel_record(Some(&record.id), &state, false, config);
+        }
+    }
+    
+    println!("📋 Task: {}", state.task.cyan().bold());
+    println!("🕒 Started: {}", state.started_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    println!();
+    print_supervisor_state(&state);
+    
+    Ok(())
+}
+
+/// Collect the outputs of a parallel session into one Markdown or HTML report
+pub async fn load_report(session_id: &str, format: ReportFormat, output: Option<&Path>, config: &Config) -> Result<()> {
+    let coordination_dir = config.ci_path.join("AGENTS").join(session_id);
+    if !coordination_dir.exists() {
+        return Err(anyhow::anyhow!("Parallel session not found: {}", session_id));
+    }
+    
+    let report = SessionReport::build(&coordination_dir).await?;
+    let content = report.render(format);
+    
+    if output.is_some_and(|path| path == Path::new("-")) {
+        print!("{}", content);
+        return Ok(());
+    }
+    
+    let path = output.map(Path::to_path_buf)
+        .unwrap_or_else(|| coordination_dir.join(format!("report.{}", format.extension())));
+    std::fs::write(&path, content)
+        .map_err(|e| anyhow::anyhow!("Failed to write report to {}: {}", path.display(), e))?;
+    
+    CommandHelpers::print_success(&format!("Report for {} written to {}", session_id, path.display()));
+    println!("   {} instances, {} unresolved blockers, {} changed files",
+        report.instances.len(),
+        report.open_blockers.len(),
+        report.combined.as_ref().map(|c| c.files.len()).unwrap_or(0)
+    );
+    Ok(())
+}
+
+/// What `ci load merge` does with the selected instance branches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMode {
+    /// Ask per instance
+    Interactive,
+    List,
+    Diff,
+    Merge,
+    Discard,
+}
+
+/// Review, merge or discard the per-instance branches of a `--worktrees` session
+pub async fn load_merge(session_id: &str, instance: Option<&str>, mode: MergeMode, config: &Config) -> Result<()> {
+    CommandHelpers::print_command_header(
+        &format!("Merge worktrees of {}", session_id), 
+        "🌳", 
+        "Intelligence & Discovery", 
+        "magenta"
+    );
+    
+    let coordination_dir = config.ci_path.join("AGENTS").join(session_id);
+    if !coordination_dir.exists() {
+        return Err(anyhow::anyhow!("Parallel session not found: {}", session_id));
+    }
+    let mut session = WorktreeSession::load(&coordination_dir)?;
+    
+    let selected: Vec<usize> = session.worktrees.iter()
+        .enumerate()
+        .filter(|(_, w)| w.status == WorktreeStatus::Active)
+        .filter(|(_, w)| instance.is_none_or(|i| w.name.eq_ignore_ascii_case(i) || w.instance.eq_ignore_ascii_case(i) || w.branch == i))
+        .map(|(index, _)| index)
+        .collect();
+    
+    println!("📦 Repository: {}", session.repo_root.display());
+    println!("🔖 Base: {} ({})", session.base_branch.as_deref().unwrap_or("detached HEAD"), &session.base_commit[..session.base_commit.len().min(8)]);
+    println!();
+    
+    if selected.is_empty() {
+        let resolved = session.worktrees.len();
+        CommandHelpers::print_info(&match instance {
+            Some(name) => format!("No active worktree matches '{}'", name),
+            None => format!("All {} instance branches have been merged or discarded", resolved),
+        });
+        if session.cleanup()? {
+            session.save(&coordination_dir)?;
+        }
+        return Ok(());
+    }
+    
+    println!("{:<32} {:<44} {:>7} {:>6} {:>12}", "Instance".bold(), "Branch".bold(), "Commits".bold(), "Files".bold(), "Lines".bold());
+    for &index in &selected {
+        let worktree = &session.worktrees[index];
+        let summary = session.summary(worktree)?;
+        println!("{:<32} {:<44} {:>7} {:>6} {:>12}{}",
+            worktree.instance,
+            worktree.branch,
+            summary.commits,
+            summary.files_changed,
+            format!("+{} -{}", summary.insertions, summary.deletions),
+            if summary.dirty { " (uncommitted changes)".yellow().to_string() } else { String::new() }
+        );
+    }
+    println!();
+    
+    for index in selected {
+        let worktree = session.worktrees[index].clone();
+        let action = match mode {
+            MergeMode::List => continue,
+            MergeMode::Diff => {
+                println!("{}", format!("=== {} ({}) ===", worktree.instance, worktree.branch).cyan().bold());
+                println!("{}", session.diff(&worktree)?);
+                continue;
+            },
+            MergeMode::Merge => "m".to_string(),
+            MergeMode::Discard => "d".to_string(),
+            MergeMode::Interactive => loop {
+                let answer = CommandHelpers::prompt_input(
+                    &format!("{}: [m]erge, [d]iscard, [v]iew diff, [s]kip", worktree.instance),
+                    Some("s")
+                )?.to_lowercase();
+                if answer.starts_with('v') {
+                    println!("{}", session.diff(&worktree)?);
+                    continue;
+                }
+                break answer;
+            },
+        };
+        
+        if action.starts_with('m') {
+            if session.merge(index)? {
+                println!("   {} Committed uncommitted changes of {} before merging", "•".dimmed(), worktree.instance);
+            }
+            println!("{} Merged {} into {}", "✓".green(), worktree.branch.cyan(), session.base_branch.as_deref().unwrap_or("HEAD"));
+        } else if action.starts_with('d') {
+            session.discard(index)?;
+            println!("{} Discarded {}", "✓".green(), worktree.branch.cyan());
+        } else {
+            println!("{} Skipped {}", "•".dimmed(), worktree.instance);
+        }
+        session.save(&coordination_dir)?;
+    }
+    
+    if session.cleanup()? {
+        println!();
+        CommandHelpers::print_success("All instance worktrees resolved and cleaned up");
+    }
+    session.save(&coordination_dir)?;
+    
+    Ok(())
+}
+
+/// Print a per-instance summary table for a supervised session
+fn print_supervisor_state(state: &SupervisorState) {
+    println!("{:<32} {:<10} {:>8} {:>6} {:>10} {:>9}", "Instance".bold(), "Status".bold(), "PID".bold(), "Exit".bold(), "Duration".bold(), "Restarts".bold());
+    
+    for instance in &state.instances {
+        let status = match instance.status {
+            InstanceStatus::Running => instance.status.label().cyan(),
+            InstanceStatus::Exited => instance.status.label().green(),
+            InstanceStatus::Unknown => instance.status.label().yellow(),
+            _ => instance.status.label().red(),
+        };
+        let duration = instance.duration_secs
+            .or_else(|| if instance.status == InstanceStatus::Running {
+                Some((Utc::now() - instance.started_at).num_seconds().max(0) as u64)
+            } else {
+                None
+            })
+            .map(format_duration_secs)
+            .unwrap_or_else(|| "-".to_string());
+        
+        println!("{:<32} {:<10} {:>8} {:>6} {:>10} {:>9}",
+            instance.display_name,
+            status,
+            instance.pid.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string()),
+            instance.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "-".to_string()),
+            duration,
+            instance.restarts
+        );
+        
+        if let Some(error) = &instance.error {
+            println!("    {}", error.dimmed());
+        }
+    }
+    
+    println!();
+    println!("{} running • {} exited • {} failed • {} timed out",
+        state.count(InstanceStatus::Running).to_string().cyan(),
+        state.count(InstanceStatus::Exited).to_string().green(),
+        state.count(InstanceStatus::Failed).to_string().red(),
+        state.count(InstanceStatus::TimedOut).to_string().red()
+    );
+}
+
 /// Create specialized context for each agent in parallel execution
 fn create_parallel_agent_context(
     agent_name: &str,
@@ -1830,11 +2566,29 @@ fn create_parallel_agent_context(
         - Work systematically and thoroughly\n\
         - Coordinate with other agents through shared files\n\
         - Maintain your distinct identity and expertise throughout\n\n\
-        **COORDINATION DIRECTORY**: {}\n\
-        **START WORKING**: Your parallel session is now active!\n\n",
+        **COORDINATION DIRECTORY**: {}\n\n",
         coordination_file.parent().unwrap().display()
     ));
-    
+
+    let instance_key = all_agents[agent_index].file_safe_name();
+    agent_context.push_str(&format!(
+        "**COORDINATION PROTOCOL**:\n\
+        Exchange typed messages with the other instances instead of free-form notes. Your instance key is `{key}`.\n\
+        - Claim work before starting it: `ci coord send {session} claim \"<what>\" --from {key} --path <path>`\n\
+        - Report progress and findings: `ci coord send {session} progress|finding \"<text>\" --from {key}`\n\
+        - Report what blocks you: `ci coord send {session} blocker \"<problem>\" --from {key}`\n\
+        - Resolve someone's blocker: `ci coord send {session} progress \"<fix>\" --from {key} --resolves <blocker-id>`\n\
+        - Pass work on: `ci coord send {session} handoff \"<context>\" --from {key} --to <instance> --path <path>`\n\
+        - Finish with: `ci coord send {session} done \"<summary>\" --from {key}`\n\
+        - Check your inbox regularly: `ci coord read {session} --as {key}`\n\
+        - See who owns what: `ci coord board {session}` (also in `{board}`)\n\
+        Do not work on paths another instance has claimed; send them a message instead.\n\n\
+        **START WORKING**: Your parallel session is now active!\n\n",
+        key = instance_key,
+        session = session_id,
+        board = coordination_file.with_file_name(BOARD_FILE).display()
+    ));
+
     agent_context
 }
 
@@ -1932,5 +2686,5 @@ async fn execute_parallel_agents(
 ) -> Result<()> {
     // Expand agent multipliers and delegate to the expanded version
     let expanded_agents = expand_agent_multipliers(agent_names)?;
-    execute_parallel_agents_expanded(&expanded_agents, context, path, auto_yes, allow, task, config).await
+    execute_parallel_agents_expanded(&expanded_agents, context, path, auto_yes, allow, &LoadOptions::default(), task, &ParallelOptions::default(), config).await
 }
\ No newline at end of file
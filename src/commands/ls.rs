@@ -11,6 +11,7 @@ use std::path::{Path, PathBuf};
 
 use crate::config::Config;
 use crate::helpers::CommandHelpers;
+use crate::helpers::ansi::strip_ansi_codes;
 
 /// File type categories for grouping
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -712,26 +713,6 @@ fn format_line_fixed_width(line: &str, width: usize) -> String {
     }
 }
 
-/// Strip ANSI escape sequences for accurate length calculation
-fn strip_ansi_codes(input: &str) -> String {
-    let mut result = String::new();
-    let mut in_escape = false;
-    
-    for ch in input.chars() {
-        if ch == '\x1b' {
-            in_escape = true;
-        } else if in_escape {
-            if ch == 'm' {
-                in_escape = false;
-            }
-        } else {
-            result.push(ch);
-        }
-    }
-    
-    result
-}
-
 /// Execute the ls command
 pub async fn execute(directory: Option<&str>, _config: &Config) -> Result<()> {
     CommandHelpers::print_command_header(
@@ -3,10 +3,20 @@ use clap::{Arg, ArgMatches, Command};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::commands::visualize::WebExporter;
+use crate::config::{find_nearest_config, RetentionConfig};
 use crate::errors::CIError;
+use crate::topology::GitOperations;
+use crate::helpers::agent_stats::parse_since;
+use crate::helpers::agent_supervisor::format_duration_secs;
 use crate::helpers::path::get_ci_root;
+use crate::helpers::session_retention::{compress, days_before, plan, PlannedAction, RetentionAction, RetentionPolicy, SessionUsage, Trash, TrashEntry};
+use crate::helpers::session_export::{ExportFormat, SessionExport};
+use crate::helpers::session_provenance::{changed_files, commit_summaries, GitProvenance};
+use crate::helpers::session_store::{absolute, SessionKind, SessionQuery, SessionRecord, SessionStatus, SessionStore};
+use crate::VisualizationTheme;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct SessionMetadata {
@@ -38,7 +48,7 @@ pub fn create_command() -> Command {
                         .short('s')
                         .long("status")
                         .value_name("STATUS")
-                        .help("Filter sessions by status (active, completed, archived)")
+                        .help("Filter sessions by status (active, completed, failed, archived, unknown)")
                 )
                 .arg(
                     Arg::new("recent")
@@ -48,6 +58,31 @@ pub fn create_command() -> Command {
                         .default_value("10")
                         .help("Show only recent sessions")
                 )
+                .arg(
+                    Arg::new("project")
+                        .short('p')
+                        .long("project")
+                        .value_name("PATH")
+                        .help("Filter sessions by project directory ('.' for the current one)")
+                )
+                .arg(
+                    Arg::new("since")
+                        .long("since")
+                        .value_name("WHEN")
+                        .help("Only sessions started since a date (YYYY-MM-DD) or age (12h, 7d, 4w)")
+                )
+                .arg(
+                    Arg::new("until")
+                        .long("until")
+                        .value_name("WHEN")
+                        .help("Only sessions started before a date (YYYY-MM-DD) or age (12h, 7d, 4w)")
+                )
+                .arg(
+                    Arg::new("tag")
+                        .long("tag")
+                        .value_name("TAG")
+                        .help("Filter sessions by tag")
+                )
         )
         .subcommand(
             Command::new("create")
@@ -111,9 +146,23 @@ pub fn create_command() -> Command {
                         .index(2)
                 )
         )
+        .subcommand(
+            Command::new("changes")
+                .about("Show the commits and diff stat produced during a session")
+                .arg(
+                    Arg::new("session_id")
+                        .help("Session id (or a unique prefix)")
+                        .required(true)
+                        .index(1)
+                )
+        )
+        .subcommand(
+            Command::new("migrate")
+                .about("Import existing session records into the session store")
+        )
         .subcommand(
             Command::new("cleanup")
-                .about("Clean up old sessions")
+                .about("Archive old sessions and apply the retention policy of .ci-config.json")
                 .arg(
                     Arg::new("days")
                         .short('d')
@@ -128,6 +177,12 @@ pub fn create_command() -> Command {
                         .action(clap::ArgAction::SetTrue)
                         .help("Show what would be cleaned up without making changes")
                 )
+                .arg(
+                    Arg::new("restore")
+                        .long("restore")
+                        .value_name("SESSION_ID")
+                        .help("Move a session removed by an earlier cleanup back out of the trash")
+                )
         )
 }
 
@@ -137,6 +192,8 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
         Some(("create", sub_matches)) => create_session(sub_matches),
         Some(("info", sub_matches)) => show_session_info(sub_matches),
         Some(("archive", sub_matches)) => archive_session(sub_matches),
+        Some(("changes", sub_matches)) => show_session_changes(sub_matches),
+        Some(("migrate", _)) => migrate_sessions(),
         Some(("cleanup", sub_matches)) => cleanup_sessions(sub_matches),
         _ => {
             eprintln!("{}", "No valid subcommand provided".red());
@@ -146,89 +203,75 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
 }
 
 fn list_sessions(matches: &ArgMatches) -> Result<()> {
-    let agent_filter = matches.get_one::<String>("agent");
-    let status_filter = matches.get_one::<String>("status");
     let recent_count: usize = matches.get_one::<String>("recent")
         .unwrap()
         .parse()
         .with_context(|| "Invalid recent count")?;
+    let parse_when = |name: &str| -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+        matches.get_one::<String>(name)
+            .map(|value| parse_since(value).map_err(|e| anyhow::anyhow!(e.replace("--since", &format!("--{}", name)))))
+            .transpose()
+    };
     
-    let ci_root = get_ci_root()?;
-    let agents_dir = ci_root.join("AGENTS");
+    let query = SessionQuery {
+        agent: matches.get_one::<String>("agent").cloned(),
+        project: matches.get_one::<String>("project").cloned(),
+        status: matches.get_one::<String>("status")
+            .map(|status| status.parse::<SessionStatus>().map_err(|e| anyhow::anyhow!(e)))
+            .transpose()?,
+        since: parse_when("since")?,
+        until: parse_when("until")?,
+        tag: matches.get_one::<String>("tag").cloned(),
+        limit: Some(recent_count),
+        ..Default::default()
+    };
     
-    if !agents_dir.exists() {
-        return Err(CIError::NotFound("AGENTS directory not found".to_string()).into());
-    }
+    let store = open_store()?;
+    let sessions = store.query(&query)?;
     
     println!("{}", "Collaborative Intelligence Sessions".cyan().bold());
     println!("{}", "=".repeat(40).cyan());
     println!();
     
-    let mut all_sessions = Vec::new();
-    
-    // Collect sessions from all agents
-    if let Ok(entries) = fs::read_dir(&agents_dir) {
-        for entry in entries.flatten() {
-            if entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false) {
-                let agent_name = entry.file_name().to_string_lossy().to_string();
-                
-                // Apply agent filter
-                if let Some(filter) = agent_filter {
-                    if !agent_name.contains(filter) {
-                        continue;
-                    }
-                }
-                
-                let sessions_dir = entry.path().join("Sessions");
-                if sessions_dir.exists() {
-                    let agent_sessions = collect_agent_sessions(&agent_name, &sessions_dir)?;
-                    all_sessions.extend(agent_sessions);
-                }
-            }
-        }
-    }
-    
-    // Apply status filter
-    if let Some(status) = status_filter {
-        all_sessions.retain(|session| session.status == *status);
-    }
-    
-    // Sort by creation date (newest first)
-    all_sessions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-    
-    // Limit to recent count
-    if all_sessions.len() > recent_count {
-        all_sessions.truncate(recent_count);
-    }
-    
-    if all_sessions.is_empty() {
+    if sessions.is_empty() {
         println!("{}", "No sessions found matching the criteria.".yellow());
         println!("Create a new session with: ci session create <agent> <session_name>");
         return Ok(());
     }
     
-    // Display sessions
-    for session in &all_sessions {
-        let status_color = match session.status.as_str() {
-            "active" => session.status.green(),
-            "completed" => session.status.blue(),
-            "archived" => session.status.yellow(),
-            _ => session.status.white(),
+    for session in &sessions {
+        let status_color = match session.status {
+            SessionStatus::Active => session.status.label().green(),
+            SessionStatus::Completed => session.status.label().blue(),
+            SessionStatus::Failed => session.status.label().red(),
+            SessionStatus::Archived => session.status.label().yellow(),
+            SessionStatus::Unknown => session.status.label().dimmed(),
         };
         
-        println!("{} {} - {}", 
+        println!("{} {} {} - {}", 
             "●".white(),
-            session.session_name.bold(),
-            session.agent_name.dimmed()
+            session.id.dimmed(),
+            session.title().bold(),
+            session.agents.join(", ").dimmed()
         );
         
-        println!("  Status: {} | Created: {}", 
+        println!("  Kind: {} | Status: {} | Started: {}{}", 
+            session.kind.label(),
             status_color,
-            session.created_at.dimmed()
+            session.started_at.format("%Y-%m-%d %H:%M").to_string().dimmed(),
+            session.duration_secs().map(|d| format!(" | Duration: {}", format_duration_secs(d))).unwrap_or_default()
         );
         
-        if !session.description.is_empty() {
-            println!("  Description: {}", session.description);
+        if let Some(project) = &session.project {
+            println!("  Project: {}", project.display());
+        }
+        
+        if let Some(transcript) = &session.transcript_path {
+            println!("  Transcript: {}", transcript.display());
+        }
+        
+        if let Some(archive) = &session.archive_path {
+            println!("  Archive: {}", archive.display());
         }
         
         if !session.tags.is_empty() {
@@ -238,8 +281,158 @@ fn list_sessions(matches: &ArgMatches) -> Result<()> {
         println!();
     }
     
-    println!("Total sessions: {}", all_sessions.len());
+    println!("Total sessions: {}", sessions.len());
+    
+    Ok(())
+}
+
+fn show_session_changes(matches: &ArgMatches) -> Result<()> {
+    let session_id = matches.get_one::<String>("session_id").unwrap();
+    let record = open_store()?.load(session_id)?;
+    let git = record.git.as_ref().ok_or_else(|| CIError::NotFound(format!(
+        "Session {} has no git provenance (it did not start inside a git repository)", record.id
+    )))?;
+    
+    // While the session runs its range ends at the current HEAD
+    let range = match (&git.commit_range, record.is_running()) {
+        (Some(range), _) => Some(range.clone()),
+        (None, true) => Some(git.range_to("HEAD")),
+        (None, false) => None,
+    };
+    
+    println!("{}", format!("Session Changes: {}", record.title()).cyan().bold());
+    println!("{}", "=".repeat(40).cyan());
+    println!();
+    println!("{}: {} ({})", "Session".bold(), record.id, record.status);
+    println!("{}: {}", "Repository".bold(), git.repo_root.display());
+    for (label, snapshot) in [("Start", &git.start), ("End", &git.end)] {
+        if let Some(snapshot) = snapshot {
+            println!("{}: {} on {}{}",
+                label.bold(),
+                snapshot.head.as_deref().map(|h| &h[..h.len().min(10)]).unwrap_or("(no commits)"),
+                snapshot.branch.as_deref().unwrap_or("detached HEAD"),
+                if snapshot.dirty { " (uncommitted changes)".yellow().to_string() } else { String::new() }
+            );
+        }
+    }
+    println!();
+    
+    let commits = range.as_deref()
+        .map(|range| commit_summaries(&git.repo_root, range))
+        .unwrap_or_default();
+    if commits.is_empty() {
+        if range.is_none() && git.end.is_none() {
+            println!("{}", "No commits found for this session; its end state was not captured.".yellow());
+        } else {
+            println!("{}", "No commits were made during this session.".yellow());
+        }
+        return Ok(());
+    }
+    
+    // GitOperations works on the current directory
+    std::env::set_current_dir(&git.repo_root)?;
+    let operations = GitOperations::new();
+    let (mut files, mut insertions, mut deletions) = (0, 0, 0);
+    
+    println!("{} {}", "Commits".bold(), range.unwrap_or_default().dimmed());
+    for (hash, subject) in &commits {
+        let stats = operations.get_diff_stats(hash).map_err(|e| anyhow::anyhow!("{}", e))?;
+        files += stats.files_changed;
+        insertions += stats.insertions;
+        deletions += stats.deletions;
+        println!("  {} {} {}",
+            hash[..hash.len().min(10)].yellow(),
+            subject,
+            format!("({} files, +{} -{})", stats.files_changed, stats.insertions, stats.deletions).dimmed()
+        );
+    }
+    println!();
+    
+    let changed = if git.commit_range.is_some() {
+        git.changed_files.clone()
+    } else {
+        changed_files(&git.repo_root, &git.range_to("HEAD"))
+    };
+    println!("{}", "Files Changed:".bold());
+    for file in &changed {
+        println!("  - {}", file);
+    }
+    println!();
+    println!("{} commits, {} file changes, {} insertions(+), {} deletions(-)",
+        commits.len(), files, insertions.to_string().green(), deletions.to_string().red());
+    
+    Ok(())
+}
+
+/// Write a shareable report of a stored session (`ci session export`)
+pub async fn export_session(session_id: &str, format: ExportFormat, output: Option<&Path>, theme: VisualizationTheme) -> Result<()> {
+    let store = open_store()?;
+    let record = store.load(session_id)?;
+    let default_path = store.session_dir(&record.id).join(format!("report.{}", format.extension()));
+
+    let export = SessionExport::build(record).await?;
+    let content = match format {
+        ExportFormat::Html => WebExporter::new(theme).generate_session_html(&export),
+        _ => export.render(format)?,
+    };
+
+    if output.is_some_and(|path| path == Path::new("-")) {
+        print!("{}", content);
+        return Ok(());
+    }
+
+    let path = output.map(Path::to_path_buf).unwrap_or(default_path);
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, content)
+        .with_context(|| format!("Failed to write session report to {}", path.display()))?;
+
+    println!("{} Report for session {} written to {}", "✓".green(), export.session.id, path.display());
+    println!("   {} commits, {} changed files, {} coordination files",
+        export.commits.len(),
+        export.changes.as_ref().map(|c| c.files.len()).unwrap_or(0),
+        export.coordination_files.len()
+    );
+    Ok(())
+}
+
+fn migrate_sessions() -> Result<()> {
+    let ci_root = get_ci_root()?;
+    let store = SessionStore::new(&ci_root);
+    let report = store.migrate(&ci_root, std::env::current_dir().ok().as_deref())?;
     
+    for record in &report.imported {
+        println!("  Imported {} {} ({})", record.id.dimmed(), record.title(), record.kind.label());
+    }
+    println!("{} Imported {} sessions, {} already in the store", "✓".green(), report.imported.len(), report.skipped);
+    
+    Ok(())
+}
+
+/// The session store, importing existing session records the first time it is used
+fn open_store() -> Result<SessionStore> {
+    open_store_at(&get_ci_root()?, std::env::current_dir().ok().as_deref())
+}
+
+fn open_store_at(ci_root: &Path, project_dir: Option<&Path>) -> Result<SessionStore> {
+    let store = SessionStore::new(ci_root);
+    if !store.is_migrated() {
+        let report = store.migrate(ci_root, project_dir)?;
+        if !report.imported.is_empty() {
+            println!("{}", format!("Imported {} existing sessions into the session store", report.imported.len()).dimmed());
+        }
+    }
+    Ok(store)
+}
+
+/// Set the status of the store record mirroring a `ci session create` directory
+fn update_store_status(session_dir: &Path, status: SessionStatus) -> Result<()> {
+    let store = open_store()?;
+    let source = absolute(session_dir).display().to_string();
+    if let Some(record) = store.find_by_source(&source)? {
+        store.update(&record.id, |record| record.status = status)?;
+    }
     Ok(())
 }
 
@@ -256,7 +449,22 @@ fn create_session(matches: &ArgMatches) -> Result<()> {
         tags_str.split(',').map(|s| s.trim().to_string()).collect()
     };
     
-    let ci_root = get_ci_root()?;
+    let project = std::env::current_dir().ok();
+    let (record, session_dir) = create_session_at(&get_ci_root()?, agent_name, session_name, description, tags, project)?;
+    
+    println!("{} Session created successfully!", "✓".green());
+    println!("Session id: {}", record.id);
+    println!("Session directory: {}", session_dir.display());
+    println!();
+    println!("To work with this session:");
+    println!("  cd {}", session_dir.display());
+    println!("  ci agent activate {}", agent_name);
+    
+    Ok(())
+}
+
+/// Create a manual session directory for `agent_name` under `ci_root` and its store record
+fn create_session_at(ci_root: &Path, agent_name: &str, session_name: &str, description: String, tags: Vec<String>, project: Option<PathBuf>) -> Result<(SessionRecord, PathBuf)> {
     let agent_dir = ci_root.join("AGENTS").join(agent_name);
     
     if !agent_dir.exists() {
@@ -276,6 +484,9 @@ fn create_session(matches: &ArgMatches) -> Result<()> {
         )).into());
     }
     
+    // Import existing sessions before the new directory exists, so it is not imported as well
+    let store = open_store_at(ci_root, project.as_deref())?;
+    
     println!("{}", format!("Creating session: {} for {}", session_name, agent_name).cyan().bold());
     
     // Create session directory
@@ -283,8 +494,8 @@ fn create_session(matches: &ArgMatches) -> Result<()> {
     
     // Create session metadata
     let metadata = SessionMetadata {
-        session_name: session_name.clone(),
-        agent_name: agent_name.clone(),
+        session_name: session_name.to_string(),
+        agent_name: agent_name.to_string(),
         created_at: chrono::Utc::now().to_rfc3339(),
         description,
         status: "active".to_string(),
@@ -335,14 +546,17 @@ Document the outcomes and learnings from this session.
     
     fs::write(session_dir.join("README.md"), readme_content)?;
     
-    println!("{} Session created successfully!", "✓".green());
-    println!("Session directory: {}", session_dir.display());
-    println!();
-    println!("To work with this session:");
-    println!("  cd {}", session_dir.display());
-    println!("  ci agent activate {}", agent_name);
-    
-    Ok(())
+    let mut record = SessionRecord::new(SessionKind::Manual, vec![agent_name.to_string()]);
+    record.name = Some(session_name.to_string());
+    record.description = Some(metadata.description.clone()).filter(|d| !d.is_empty());
+    record.tags = metadata.tags.clone();
+    record.project = project;
+    record.git = record.project.as_deref().and_then(GitProvenance::start);
+    record.memory_path = Some(absolute(&session_dir));
+    record.source = Some(absolute(&session_dir).display().to_string());
+    store.save(&record)?;
+    
+    Ok((record, session_dir))
 }
 
 fn show_session_info(matches: &ArgMatches) -> Result<()> {
@@ -447,6 +661,7 @@ fn archive_session(matches: &ArgMatches) -> Result<()> {
             fs::write(&metadata_path, updated_json)?;
         }
     }
+    update_store_status(&session_dir, SessionStatus::Archived)?;
     
     println!("{} Session '{}' archived for agent '{}'", 
         "✓".green(), session_name, agent_name);
@@ -455,21 +670,32 @@ fn archive_session(matches: &ArgMatches) -> Result<()> {
 }
 
 fn cleanup_sessions(matches: &ArgMatches) -> Result<()> {
+    let ci_root = get_ci_root()?;
+    let store = open_store()?;
+    let trash = Trash::new(&store);
+    
+    if let Some(id) = matches.get_one::<String>("restore") {
+        let entry = trash.restore(id)?;
+        println!("{} Restored session {} {} ({})", "✓".green(), entry.id, entry.title, format_file_size(entry.bytes));
+        return Ok(());
+    }
+    
     let days: i64 = matches.get_one::<String>("days")
         .unwrap()
         .parse()
         .with_context(|| "Invalid days value")?;
     let dry_run = matches.get_flag("dry-run");
+    let policy = RetentionPolicy::from_config(&retention_config(&ci_root))?;
     
     let cutoff_date = chrono::Utc::now() - chrono::Duration::days(days);
     
     println!("{}", format!("Cleaning up sessions older than {} days", days).cyan().bold());
+    println!("{}", format!("Retention policy: {}", describe_policy(&policy)).dimmed());
     if dry_run {
         println!("{}", "DRY RUN - No changes will be made".yellow());
     }
     println!();
     
-    let ci_root = get_ci_root()?;
     let agents_dir = ci_root.join("AGENTS");
     
     let mut archived_count = 0;
@@ -492,57 +718,136 @@ fn cleanup_sessions(matches: &ArgMatches) -> Result<()> {
     } else {
         println!("{} Archived {} sessions", "✓".green(), archived_count);
     }
+    println!();
+    
+    let usages: Vec<SessionUsage> = store.all()?.into_iter()
+        .map(|record| SessionUsage::measure(record, &store, &ci_root))
+        .collect();
+    let actions = plan(usages, &policy, chrono::Utc::now());
+    apply_retention(&actions, &store, &ci_root, dry_run)?;
+    
+    let expired_before = days_before(chrono::Utc::now(), policy.trash_days)?;
+    let expired: Vec<TrashEntry> = if dry_run {
+        trash.entries().into_iter().filter(|e| e.trashed_at < expired_before).collect()
+    } else {
+        trash.purge_expired(policy.trash_days, chrono::Utc::now())?
+    };
+    if !expired.is_empty() {
+        println!("{} {} sessions trashed more than {} days ago, reclaiming {}",
+            if dry_run { "Would delete" } else { "Deleted" },
+            expired.len(),
+            policy.trash_days,
+            format_file_size(expired.iter().map(|e| e.bytes).sum())
+        );
+    }
+    
+    let trashed = trash.entries();
+    if !trashed.is_empty() {
+        println!("{}", format!(
+            "Trash: {} sessions ({}); restore one with 'ci session cleanup --restore <id>' within {} days",
+            trashed.len(),
+            format_file_size(trashed.iter().map(|e| e.bytes).sum()),
+            policy.trash_days
+        ).dimmed());
+    }
     
     Ok(())
 }
 
-fn collect_agent_sessions(agent_name: &str, sessions_dir: &Path) -> Result<Vec<SessionMetadata>> {
-    let mut sessions = Vec::new();
+/// Retention settings of the nearest `.ci-config.json`, from the current directory or the CI repository
+fn retention_config(ci_root: &Path) -> RetentionConfig {
+    std::env::current_dir().ok()
+        .and_then(|cwd| find_nearest_config(&cwd))
+        .or_else(|| find_nearest_config(ci_root))
+        .map(|(_, config)| config.sessions.retention)
+        .unwrap_or_default()
+}
+
+fn describe_policy(policy: &RetentionPolicy) -> String {
+    let mut rules = Vec::new();
+    if let Some(keep) = policy.keep_last_per_agent {
+        rules.push(format!("newest {} per agent", keep));
+    }
+    if let Some(days) = policy.max_age_days {
+        rules.push(format!("at most {} days old", days));
+    }
+    if let Some(bytes) = policy.max_total_bytes {
+        rules.push(format!("at most {} in total", format_file_size(bytes)));
+    }
+    if rules.is_empty() {
+        rules.push("no limits".to_string());
+    }
+    match (policy.keep_tagged, policy.keep_archived) {
+        (true, true) => rules.push("tagged and archived sessions kept".to_string()),
+        (true, false) => rules.push("tagged sessions kept".to_string()),
+        (false, true) => rules.push("archived sessions kept".to_string()),
+        (false, false) => {},
+    }
+    if policy.compress_archived {
+        rules.push("archived sessions compressed".to_string());
+    }
+    rules.join(", ")
+}
+
+/// Print the planned removals and compressions and, unless `dry_run`, carry them out
+fn apply_retention(actions: &[PlannedAction], store: &SessionStore, ci_root: &Path, dry_run: bool) -> Result<()> {
+    if actions.is_empty() {
+        println!("No sessions exceed the retention policy");
+        return Ok(());
+    }
     
-    if let Ok(entries) = fs::read_dir(sessions_dir) {
-        for entry in entries.flatten() {
-            if entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false) {
-                let session_name = entry.file_name().to_string_lossy().to_string();
-                let metadata_path = entry.path().join("metadata.json");
-                
-                let metadata = if metadata_path.exists() {
-                    let content = fs::read_to_string(&metadata_path)?;
-                    serde_json::from_str::<SessionMetadata>(&content).unwrap_or_else(|_| {
-                        // Fallback metadata if JSON is invalid
-                        create_fallback_metadata(agent_name, &session_name, &entry.path())
-                    })
-                } else {
-                    // Create metadata for sessions without metadata.json
-                    create_fallback_metadata(agent_name, &session_name, &entry.path())
-                };
-                
-                sessions.push(metadata);
-            }
+    println!("  {:<12}  {:<24}  {:<16}  {:>8}  {:<8}  {}",
+        "SESSION".bold(), "AGENTS".bold(), "STARTED".bold(), "SIZE".bold(), "ACTION".bold(), "REASON".bold());
+    for action in actions {
+        let record = &action.usage.record;
+        let mut agents = record.agents.join(", ");
+        if agents.chars().count() > 24 {
+            agents = agents.chars().take(21).collect::<String>() + "...";
         }
+        println!("  {:<12}  {:<24}  {:<16}  {:>8}  {:<8}  {}",
+            record.id,
+            agents,
+            record.started_at.format("%Y-%m-%d %H:%M"),
+            format_file_size(action.usage.bytes),
+            action.action.label(),
+            action.reason.dimmed()
+        );
     }
+    println!();
     
-    Ok(sessions)
-}
-
-fn create_fallback_metadata(agent_name: &str, session_name: &str, session_path: &Path) -> SessionMetadata {
-    let created_at = if let Ok(metadata) = fs::metadata(session_path) {
-        if let Ok(created) = metadata.created() {
-            chrono::DateTime::<chrono::Utc>::from(created).to_rfc3339()
-        } else {
-            chrono::Utc::now().to_rfc3339()
+    let removals: Vec<&PlannedAction> = actions.iter().filter(|a| a.action == RetentionAction::Remove).collect();
+    let compressions: Vec<&PlannedAction> = actions.iter().filter(|a| a.action == RetentionAction::Compress).collect();
+    let removed_bytes: u64 = removals.iter().map(|a| a.usage.bytes).sum();
+    
+    if dry_run {
+        if !removals.is_empty() {
+            println!("Would move {} sessions to the trash, reclaiming {}", removals.len(), format_file_size(removed_bytes));
         }
-    } else {
-        chrono::Utc::now().to_rfc3339()
-    };
+        if !compressions.is_empty() {
+            println!("Would compress {} archived sessions ({} uncompressed)",
+                compressions.len(), format_file_size(compressions.iter().map(|a| a.usage.bytes).sum()));
+        }
+        return Ok(());
+    }
     
-    SessionMetadata {
-        session_name: session_name.to_string(),
-        agent_name: agent_name.to_string(),
-        created_at,
-        description: "Legacy session (no metadata)".to_string(),
-        status: "completed".to_string(),
-        tags: vec!["legacy".to_string()],
+    let trash = Trash::new(store);
+    for action in &removals {
+        trash.put(&action.usage, store)?;
     }
+    let mut compressed_bytes = 0;
+    for action in &compressions {
+        let (archive_path, reclaimed) = compress(&action.usage, store, ci_root)?;
+        store.update(&action.usage.record.id, |record| record.archive_path = Some(archive_path))?;
+        compressed_bytes += reclaimed;
+    }
+    
+    if !removals.is_empty() {
+        println!("{} Moved {} sessions to the trash, reclaiming {}", "✓".green(), removals.len(), format_file_size(removed_bytes));
+    }
+    if !compressions.is_empty() {
+        println!("{} Compressed {} archived sessions, reclaiming {}", "✓".green(), compressions.len(), format_file_size(compressed_bytes));
+    }
+    Ok(())
 }
 
 fn cleanup_agent_sessions(
@@ -576,6 +881,7 @@ fn cleanup_agent_sessions(
                                         fs::write(&metadata_path, updated_json)?;
                                     }
                                 }
+                                update_store_status(&entry.path(), SessionStatus::Archived)?;
                                 println!("  Archived: {} / {}", agent_name, session_name);
                             }
                             archived_count += 1;
@@ -599,4 +905,24 @@ fn format_file_size(size: u64) -> String {
     } else {
         format!("{:.1}GB", size as f64 / (1024.0 * 1024.0 * 1024.0))
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_in_fresh_root_stores_one_record() {
+        let temp_dir = TempDir::new().unwrap();
+        let ci_root = temp_dir.path();
+        fs::create_dir_all(ci_root.join("AGENTS/Athena")).unwrap();
+
+        let (record, session_dir) = create_session_at(ci_root, "Athena", "planning", String::new(), Vec::new(), None).unwrap();
+        assert!(session_dir.join("metadata.json").is_file());
+
+        let records = SessionStore::new(ci_root).all().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].id, record.id);
+        assert!(create_session_at(ci_root, "Athena", "planning", String::new(), Vec::new(), None).is_err());
+    }
+}
@@ -3,8 +3,10 @@
 //! This module provides commands for managing source control operations,
 //! including git repository management and commit handling.
 
-use crate::config::Config;
+use crate::config::{find_nearest_config, Config};
 use crate::helpers::{CommandHelpers, RepositoryHelpers, CommitAnalyzer};
+use crate::helpers::session_provenance::{active_session, commit_trailers};
+use crate::helpers::session_store::SessionStore;
 use crate::RepoCommands;
 use anyhow::{Result, Context, anyhow};
 use std::path::Path;
@@ -508,9 +510,17 @@ pub async fn commit(message: Option<&str>, _config: &Config) -> Result<()> {
         }
     };
     
+    let full_message = match session_trailers(&repo_path) {
+        Some(trailers) => {
+            CommandHelpers::print_info(&format!("Adding session trailers: {}", trailers.join(", ")));
+            format!("{}\n\n{}", commit_message, trailers.join("\n"))
+        },
+        None => commit_message.clone(),
+    };
+    
     // Create the commit
     let output = Command::new("git")
-        .args(["commit", "-m", &commit_message])
+        .args(["commit", "-m", &full_message])
         .current_dir(&repo_path)
         .output()
         .with_context(|| "Failed to create commit")?;
@@ -525,6 +535,17 @@ pub async fn commit(message: Option<&str>, _config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// `CI-Session:`/`CI-Agent:` trailers when the project enables them
+/// (`sessions.commit_trailers`) and an agent session is active in this repository
+fn session_trailers(repo_path: &Path) -> Option<Vec<String>> {
+    let (_, project_config) = find_nearest_config(repo_path)?;
+    if !project_config.sessions.commit_trailers {
+        return None;
+    }
+    let store = SessionStore::open().ok()?;
+    active_session(&store, repo_path).map(|record| commit_trailers(&record))
+}
+
 /// Enhanced commit with detailed analysis
 #[allow(dead_code)]
 pub async fn commit_enhanced(
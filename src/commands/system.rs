@@ -7,6 +7,7 @@ use crate::KeyCommands;
 use crate::helpers::api_keys::ApiKeyCommands;
 use crate::helpers::command::CommandHelpers;
 use crate::helpers::SystemHelpers;
+use crate::helpers::agent_launcher::{LaunchRequest, configured_launcher};
 use crate::tools::DocumentationGenerator;
 
 pub async fn evolve(__config: &Config) -> Result<()> {
@@ -957,15 +958,14 @@ Let's begin creating your command!"#;
             println!();
             
             // Execute Claude Code with our prompt file
-            let claude_result = Command::new("claude")
-                .arg("code")
-                .arg("--local")
-                .arg(src_dir)
-                .arg(prompt_file)
-                .status()
-                .context("Failed to launch Claude Code")?;
+            let claude_result = configured_launcher()?.launch(&LaunchRequest {
+                agent_name: "CommandBuilder".to_string(),
+                memory_file: Some(prompt_file.clone()),
+                extra_args: vec!["--local".to_string(), src_dir.display().to_string()],
+                ..Default::default()
+            })?;
                 
-            if claude_result.success() {
+            if claude_result.success {
                 CommandHelpers::print_success("Command creation session completed");
                 println!();
                 println!("{} {}", "📝".blue(), "Remember to:".italic());
@@ -1157,15 +1157,14 @@ Let's begin by selecting a command to edit.
             println!();
             
             // Execute Claude Code with our prompt file
-            let claude_result = Command::new("claude")
-                .arg("code")
-                .arg("--local")
-                .arg(src_dir)
-                .arg(prompt_file)
-                .status()
-                .context("Failed to launch Claude Code")?;
+            let claude_result = configured_launcher()?.launch(&LaunchRequest {
+                agent_name: "CommandBuilder".to_string(),
+                memory_file: Some(prompt_file.clone()),
+                extra_args: vec!["--local".to_string(), src_dir.display().to_string()],
+                ..Default::default()
+            })?;
                 
-            if claude_result.success() {
+            if claude_result.success {
                 CommandHelpers::print_success("Command editing session completed");
                 println!();
                 println!("{} {}", "📝".blue(), "Remember to:".italic());
@@ -17,7 +17,7 @@ pub use agents::*;
 pub use workflows::*;
 pub use project::*;
 use ascii_art::AsciiArtGenerator;
-use web_export::WebExporter;
+pub use web_export::WebExporter;
 
 /// Handle visualization commands
 pub async fn handle_visualization_command(
@@ -1,6 +1,9 @@
 use anyhow::{Context, Result};
 use std::fs;
 use crate::config::Config;
+use crate::helpers::parallel_report::escape_html;
+use crate::commands::idea::graph::IdeaGraph;
+use crate::helpers::session_export::SessionExport;
 use crate::VisualizationTheme;
 
 pub struct WebExporter {
@@ -1629,6 +1632,129 @@ impl WebExporter {
         Ok(svg)
     }
     
+    pub fn generate_session_html(&self, export: &SessionExport) -> String {
+        self.generate_report_html(&export.title(), &export.html_body())
+    }
+    
+    pub fn generate_idea_graph_html(&self, graph: &IdeaGraph) -> String {
+        self.generate_report_html("Idea Graph", &graph.html_body())
+    }
+    
+    /// Themed page around a pre-rendered HTML body
+    fn generate_report_html(&self, title: &str, body: &str) -> String {
+        let (primary_color, secondary_color, accent_color) = self.get_theme_colors();
+        
+        format!(r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>{title}</title>
+    <style>
+        body {{
+            font-family: 'Courier New', monospace;
+            margin: 0;
+            padding: 20px;
+            background: linear-gradient(135deg, #f5f7fa 0%, #c3cfe2 100%);
+            color: #333;
+        }}
+        
+        .container {{
+            max-width: 1200px;
+            margin: 0 auto;
+            background: white;
+            border-radius: 12px;
+            box-shadow: 0 10px 30px rgba(0,0,0,0.1);
+            overflow: hidden;
+        }}
+        
+        .header {{
+            background: linear-gradient(135deg, {primary_color} 0%, {accent_color} 100%);
+            color: white;
+            padding: 40px;
+            text-align: center;
+        }}
+        
+        .header h1 {{
+            margin: 0;
+            font-size: 2em;
+            font-weight: bold;
+        }}
+        
+        .content {{
+            padding: 40px;
+        }}
+        
+        .content h2 {{
+            color: {primary_color};
+            border-bottom: 2px solid {accent_color};
+            padding-bottom: 8px;
+            margin-top: 40px;
+        }}
+        
+        .content h3, .content h4 {{
+            color: {accent_color};
+        }}
+        
+        .field {{
+            margin: 6px 0;
+            padding-left: 12px;
+            border-left: 4px solid {secondary_color};
+        }}
+        
+        table {{
+            border-collapse: collapse;
+            margin: 20px 0;
+        }}
+        
+        th {{
+            background: {primary_color};
+            color: white;
+        }}
+        
+        th, td {{
+            border: 1px solid #ddd;
+            padding: 6px 12px;
+            text-align: left;
+        }}
+        
+        pre {{
+            background: #f8f9fa;
+            border: 1px solid {secondary_color};
+            border-radius: 8px;
+            padding: 15px;
+            overflow-x: auto;
+            white-space: pre-wrap;
+        }}
+        
+        summary {{
+            cursor: pointer;
+            color: {primary_color};
+            font-weight: bold;
+        }}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <div class="header">
+            <h1>{title}</h1>
+        </div>
+        
+        <div class="content">
+{body}
+        </div>
+    </div>
+</body>
+</html>
+"#,
+            title = escape_html(title),
+            primary_color = primary_color,
+            secondary_color = secondary_color,
+            accent_color = accent_color,
+            body = body
+        )
+    }
+    
     fn get_theme_colors(&self) -> (&str, &str, &str) {
         match self.theme {
             VisualizationTheme::Dark => ("#2196F3", "#64B5F6", "#1976D2"),
@@ -5,6 +5,7 @@
 
 use anyhow::{anyhow, Context, Result};
 use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 use chrono;
@@ -29,6 +30,182 @@ pub struct AutoAcceptConfig {
     pub global: bool,
 }
 
+/// Configuration for the backend used to launch agent sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherConfig {
+    /// Launcher backend: "claude", "generic" or "recording"
+    #[serde(default = "default_launcher_backend")]
+    pub backend: String,
+    
+    /// Settings for the generic CLI backend
+    #[serde(default)]
+    pub generic: GenericLauncherConfig,
+    
+    /// File the recording backend appends launches to (JSON lines)
+    #[serde(default)]
+    pub record_path: Option<String>,
+}
+
+fn default_launcher_backend() -> String {
+    "claude".to_string()
+}
+
+impl Default for LauncherConfig {
+    fn default() -> Self {
+        LauncherConfig {
+            backend: default_launcher_backend(),
+            generic: GenericLauncherConfig::default(),
+            record_path: None,
+        }
+    }
+}
+
+/// Command template for launching an arbitrary assistant CLI
+///
+/// `args` may reference `{memory_file}`, `{prompt}` and `{agent}`; an argument
+/// consisting only of a placeholder without a value is dropped.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GenericLauncherConfig {
+    /// Program to execute
+    #[serde(default)]
+    pub command: String,
+    
+    /// Argument template
+    #[serde(default)]
+    pub args: Vec<String>,
+    
+    /// How the memory file is passed: "arg" (default), "stdin" or "none"
+    #[serde(default)]
+    pub memory_mode: Option<String>,
+    
+    /// Flag placed before the memory file when passed as an argument
+    #[serde(default)]
+    pub memory_flag: Option<String>,
+    
+    /// Flag placed before the prompt (positional if unset)
+    #[serde(default)]
+    pub prompt_flag: Option<String>,
+    
+    /// Arguments added when permissions should be bypassed
+    #[serde(default)]
+    pub permission_args: Vec<String>,
+    
+    /// Arguments added for headless (non-interactive) runs
+    #[serde(default)]
+    pub headless_args: Vec<String>,
+    
+    /// Extra environment variables for the launched process
+    #[serde(default)]
+    pub env: std::collections::HashMap<String, String>,
+}
+
+/// Session tracking settings
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SessionConfig {
+    /// Add `CI-Session:` and `CI-Agent:` trailers to commits made with `ci commit`
+    /// while an agent session is active
+    #[serde(default)]
+    pub commit_trailers: bool,
+    
+    /// Which sessions `ci session cleanup` keeps
+    #[serde(default)]
+    pub retention: RetentionConfig,
+}
+
+/// Retention policy applied by `ci session cleanup`; unset limits are not enforced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// Newest sessions kept for each agent
+    #[serde(default)]
+    pub keep_last_per_agent: Option<usize>,
+    
+    /// Total size of all session files, e.g. "500MB"; the oldest sessions go first
+    #[serde(default)]
+    pub max_total_size: Option<String>,
+    
+    /// Sessions started more than this many days ago are removed
+    #[serde(default)]
+    pub max_age_days: Option<u64>,
+    
+    /// Never remove sessions with tags
+    #[serde(default = "default_keep")]
+    pub keep_tagged: bool,
+    
+    /// Never remove archived sessions
+    #[serde(default = "default_keep")]
+    pub keep_archived: bool,
+    
+    /// Pack the files of archived sessions into a gzipped tarball
+    #[serde(default)]
+    pub compress_archived: bool,
+    
+    /// Days removed sessions stay in the trash and can be restored
+    #[serde(default = "default_trash_days")]
+    pub trash_days: u64,
+}
+
+fn default_keep() -> bool {
+    true
+}
+
+fn default_trash_days() -> u64 {
+    7
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        RetentionConfig {
+            keep_last_per_agent: None,
+            max_total_size: None,
+            max_age_days: None,
+            keep_tagged: default_keep(),
+            keep_archived: default_keep(),
+            compress_archived: false,
+            trash_days: default_trash_days(),
+        }
+    }
+}
+
+/// Idea storage and backlog settings
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct IdeaConfig {
+    /// Backend of the project's idea store: "json" (`.ci/ideas.json`) or
+    /// "markdown" (`.ci/ideas/`, one file per idea). Unset means whichever
+    /// store already exists, JSON for new projects.
+    #[serde(default)]
+    pub store: Option<String>,
+    
+    /// Scoring model of `ci idea rank`: "rice" (default) or "ice"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoring: Option<String>,
+    
+    /// Saved filters for `ci idea list` and `ci idea rank`, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub views: BTreeMap<String, IdeaView>,
+}
+
+/// Saved idea filter, applied with `--view <name>`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct IdeaView {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    
+    /// Text matched against title, description and tags
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    
+    /// Scoring model override (rank only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    
+    /// Maximum number of ideas shown (rank only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
 /// Represents the configuration for a CI project
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CIConfig {
@@ -54,6 +231,18 @@ pub struct CIConfig {
     #[serde(default)]
     pub auto_accept: AutoAcceptConfig,
     
+    /// Agent launcher backend configuration
+    #[serde(default)]
+    pub launcher: LauncherConfig,
+    
+    /// Session tracking settings
+    #[serde(default)]
+    pub sessions: SessionConfig,
+    
+    /// Idea storage settings
+    #[serde(default)]
+    pub ideas: IdeaConfig,
+    
     /// Custom project metadata (for extensibility)
     #[serde(default)]
     pub metadata: serde_json::Value,
@@ -95,6 +284,9 @@ impl CIConfig {
             active_agents: vec!["Athena".to_string(), "ProjectArchitect".to_string()],
             fast_activation: true,
             auto_accept: AutoAcceptConfig::default(),
+            launcher: LauncherConfig::default(),
+            sessions: SessionConfig::default(),
+            ideas: IdeaConfig::default(),
             metadata: serde_json::json!({}),
         }
     }
@@ -115,6 +307,9 @@ impl CIConfig {
             active_agents,
             fast_activation,
             auto_accept: AutoAcceptConfig::default(),
+            launcher: LauncherConfig::default(),
+            sessions: SessionConfig::default(),
+            ideas: IdeaConfig::default(),
             metadata: serde_json::json!({}),
         }
     }
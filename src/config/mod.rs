@@ -46,4 +46,4 @@ impl Config {
 }
 
 // Re-export CI config types
-pub use ci_config::{CIConfig, find_nearest_config};
\ No newline at end of file
+pub use ci_config::{CIConfig, LauncherConfig, GenericLauncherConfig, RetentionConfig, IdeaConfig, IdeaView, find_nearest_config};
\ No newline at end of file
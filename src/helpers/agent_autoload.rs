@@ -6,6 +6,8 @@ use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
+use crate::helpers::command::CommandHelpers;
+
 /// Configuration for agent activation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentActivationConfig {
@@ -393,8 +395,8 @@ _CI.load_agents('{}')_
                      is_terminal, has_term_env, in_known_terminal);
         }
         
-        // Set title if we're in a terminal that supports it
-        if is_terminal || has_term_env || in_known_terminal || force_title {
+        // Set title if stdout is a terminal
+        if CommandHelpers::window_title_allowed(is_terminal, force_title) {
             // OSC sequence to set window title: \x1b]0;title\x07
             print!("\x1b]0;CI Agent: {} - {}\x07", agent_name, status);
             let _ = io::stdout().flush();
@@ -415,13 +417,9 @@ _CI.load_agents('{}')_
     /// Restore window title when agent session ends
     pub fn restore_agent_session_title() {
         let is_terminal = atty::is(atty::Stream::Stdout);
-        let has_term_env = env::var("TERM").is_ok();
-        let in_known_terminal = env::var("TERM_PROGRAM").is_ok() || 
-                               env::var("ITERM_SESSION_ID").is_ok() ||
-                               env::var("TERMINAL_EMULATOR").is_ok();
         let force_title = env::var("CI_FORCE_WINDOW_TITLE").unwrap_or_default() == "true";
         
-        if is_terminal || has_term_env || in_known_terminal || force_title {
+        if CommandHelpers::window_title_allowed(is_terminal, force_title) {
             // Restore to generic CI title or terminal default
             print!("\x1b]0;CI - Collaborative Intelligence\x07");
             let _ = io::stdout().flush();
@@ -0,0 +1,426 @@
+//! Portable agent bundles
+//!
+//! `ci agent export` packs an agent directory into a gzipped tarball:
+//!
+//! ```text
+//! Athena.ciagent.tar.gz
+//!   bundle.json      BundleManifest: agent details, per-file SHA-256, checksum
+//!   agent/...        the agent directory (manifest, memory, learning, README, toolkit)
+//! ```
+//!
+//! `ci agent import` extracts the bundle into a temporary directory, verifies
+//! every file against `bundle.json` and only then installs it into AGENTS/.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+use crate::helpers::agent_manifest::{validate_agent_dir, AgentMetadata, ValidationReport, MANIFEST_FILE};
+use crate::helpers::memory_history::hex;
+use crate::helpers::path::PathHelpers;
+
+/// Name of the bundle manifest inside the archive
+pub const BUNDLE_MANIFEST: &str = "bundle.json";
+
+/// File extension of exported bundles
+pub const BUNDLE_EXTENSION: &str = "ciagent.tar.gz";
+
+/// Current bundle format version
+const BUNDLE_FORMAT: u32 = 1;
+
+/// Directory inside the archive holding the agent files
+const AGENT_ROOT: &str = "agent";
+
+/// Agent subdirectories left out of bundles unless requested (`ci load` writes `sessions/`)
+const SESSION_DIRS: &[&str] = &["Sessions", "sessions"];
+
+/// Per-load files `ci load` leaves in the agent directory: usage metadata and
+/// composed working memory, which carries task and context text
+fn is_session_file(name: &str) -> bool {
+    name == "metadata.json" || (name.starts_with("working_") && name.ends_with(".md"))
+}
+
+/// Description of a bundle, stored as `bundle.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format: u32,
+    pub agent: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    pub created_at: String,
+    pub ci_version: String,
+
+    /// Files relative to the agent directory, mapped to their SHA-256
+    pub files: BTreeMap<String, String>,
+
+    /// SHA-256 over the sorted file list and hashes
+    pub checksum: String,
+}
+
+/// How to install a bundle whose agent already exists
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictStrategy {
+    /// Install under a different name
+    Rename(String),
+    /// Replace the existing agent directory
+    Overwrite,
+    /// Add new files, keep local versions of changed files and store incoming ones as `.incoming`
+    Merge,
+}
+
+/// A verified bundle extracted to a temporary directory
+pub struct ExtractedBundle {
+    _temp_dir: TempDir,
+    pub manifest: BundleManifest,
+    pub agent_dir: PathBuf,
+    pub validation: ValidationReport,
+}
+
+/// What an import changed on disk
+#[derive(Debug, Clone, Default)]
+pub struct ImportOutcome {
+    pub agent_name: String,
+    pub agent_dir: PathBuf,
+    pub added: Vec<String>,
+    pub replaced: Vec<String>,
+    pub conflicts: Vec<String>,
+}
+
+/// Pack an agent directory into a bundle at `output`
+pub fn export_agent(agent_dir: &Path, output: &Path, include_sessions: bool) -> Result<BundleManifest> {
+    let agent_name = agent_dir.file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow!("Invalid agent directory: {}", agent_dir.display()))?
+        .to_string();
+
+    let staging = TempDir::new().context("Failed to create staging directory")?;
+    let staged_agent = staging.path().join(AGENT_ROOT);
+    PathHelpers::copy_directory(agent_dir, &staged_agent)?;
+
+    if !include_sessions {
+        for dir in SESSION_DIRS {
+            let path = staged_agent.join(dir);
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            }
+        }
+        for entry in fs::read_dir(&staged_agent)?.flatten() {
+            if entry.path().is_file() && is_session_file(&entry.file_name().to_string_lossy()) {
+                fs::remove_file(entry.path())?;
+            }
+        }
+    }
+
+    let files = hash_files(&staged_agent)?;
+    let metadata = AgentMetadata::from_manifest(agent_dir)?.unwrap_or_default();
+    let manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        checksum: checksum(&files),
+        agent: agent_name,
+        description: metadata.description,
+        version: metadata.version,
+        capabilities: metadata.capabilities,
+        created_at: Utc::now().to_rfc3339(),
+        ci_version: env!("CARGO_PKG_VERSION").to_string(),
+        files,
+    };
+
+    let manifest_path = staging.path().join(BUNDLE_MANIFEST);
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    let file = fs::File::create(output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    archive.append_path_with_name(&manifest_path, BUNDLE_MANIFEST)?;
+    archive.append_dir_all(AGENT_ROOT, &staged_agent)?;
+    archive.into_inner()?.finish()?;
+
+    Ok(manifest)
+}
+
+/// Extract a bundle and verify its files against the manifest
+pub fn open_bundle(path: &Path) -> Result<ExtractedBundle> {
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open bundle {}", path.display()))?;
+    let temp_dir = TempDir::new().context("Failed to create extraction directory")?;
+    tar::Archive::new(GzDecoder::new(file))
+        .unpack(temp_dir.path())
+        .with_context(|| format!("{} is not a valid agent bundle", path.display()))?;
+
+    let manifest_path = temp_dir.path().join(BUNDLE_MANIFEST);
+    let manifest: BundleManifest = serde_json::from_str(
+        &fs::read_to_string(&manifest_path).with_context(|| format!("Bundle has no {}", BUNDLE_MANIFEST))?
+    ).with_context(|| format!("Invalid {}", BUNDLE_MANIFEST))?;
+
+    if manifest.format > BUNDLE_FORMAT {
+        return Err(anyhow!("Bundle format {} is newer than supported ({}); upgrade CI", manifest.format, BUNDLE_FORMAT));
+    }
+    if !is_valid_agent_name(&manifest.agent) {
+        return Err(anyhow!("Bundle has an invalid agent name '{}'", manifest.agent));
+    }
+
+    let extracted = temp_dir.path().join(AGENT_ROOT);
+    let files = if extracted.is_dir() { hash_files(&extracted)? } else { BTreeMap::new() };
+
+    for (path, hash) in &manifest.files {
+        match files.get(path) {
+            None => return Err(anyhow!("Bundle is missing {}", path)),
+            Some(actual) if actual != hash => return Err(anyhow!("Checksum mismatch for {}", path)),
+            _ => {}
+        }
+    }
+    if let Some(extra) = files.keys().find(|path| !manifest.files.contains_key(*path)) {
+        return Err(anyhow!("Bundle contains unlisted file {}", extra));
+    }
+    if checksum(&files) != manifest.checksum {
+        return Err(anyhow!("Bundle checksum does not match its contents"));
+    }
+
+    // Give the directory the agent's name so validation sees the real layout
+    let agent_dir = temp_dir.path().join(&manifest.agent);
+    fs::rename(&extracted, &agent_dir)?;
+    let validation = validate_agent_dir(&agent_dir);
+
+    Ok(ExtractedBundle {
+        _temp_dir: temp_dir,
+        manifest,
+        agent_dir,
+        validation,
+    })
+}
+
+/// Install an extracted bundle into `agents_dir`.
+/// `strategy` is required when the agent already exists; `Rename` always installs under the new name.
+pub fn install_bundle(bundle: &ExtractedBundle, agents_dir: &Path, strategy: Option<&ConflictStrategy>) -> Result<ImportOutcome> {
+    let source = &bundle.agent_dir;
+    let original_name = &bundle.manifest.agent;
+    let agent_name = match strategy {
+        Some(ConflictStrategy::Rename(name)) => name.clone(),
+        _ => original_name.clone(),
+    };
+    if !is_valid_agent_name(&agent_name) {
+        return Err(anyhow!("Invalid agent name '{}'", agent_name));
+    }
+
+    let target = agents_dir.join(&agent_name);
+    let mut outcome = ImportOutcome {
+        agent_name: agent_name.clone(),
+        agent_dir: target.clone(),
+        ..Default::default()
+    };
+    let files: Vec<String> = bundle.manifest.files.keys().cloned().collect();
+
+    if !target.exists() {
+        PathHelpers::copy_directory(source, &target)?;
+        if agent_name != *original_name {
+            rename_agent_files(&target, original_name, &agent_name)?;
+        }
+        outcome.added = files;
+        return Ok(outcome);
+    }
+
+    match strategy {
+        None => Err(anyhow!("Agent '{}' already exists", agent_name)),
+        Some(ConflictStrategy::Rename(_)) => Err(anyhow!("Agent '{}' already exists; choose another name", agent_name)),
+        Some(ConflictStrategy::Overwrite) => {
+            fs::remove_dir_all(&target)
+                .with_context(|| format!("Failed to remove {}", target.display()))?;
+            PathHelpers::copy_directory(source, &target)?;
+            outcome.replaced = files;
+            Ok(outcome)
+        }
+        Some(ConflictStrategy::Merge) => {
+            for relative in files {
+                let incoming = source.join(&relative);
+                let local = target.join(&relative);
+                if !local.exists() {
+                    PathHelpers::copy_file(&incoming, &local)?;
+                    outcome.added.push(relative);
+                } else if fs::read(&local)? != fs::read(&incoming)? {
+                    let mut incoming_name = local.clone().into_os_string();
+                    incoming_name.push(".incoming");
+                    PathHelpers::copy_file(&incoming, Path::new(&incoming_name))?;
+                    outcome.conflicts.push(relative);
+                }
+            }
+            Ok(outcome)
+        }
+    }
+}
+
+/// Agent names become directory names, so keep them to a single safe path component
+pub fn is_valid_agent_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && !name.starts_with('_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Rename memory files and the manifest name after installing under a new name
+fn rename_agent_files(agent_dir: &Path, old_name: &str, new_name: &str) -> Result<()> {
+    let mut renamed = BTreeMap::new();
+    for suffix in [".md", "_memory.md"] {
+        let old_file = format!("{}{}", old_name, suffix);
+        let new_file = format!("{}{}", new_name, suffix);
+        if agent_dir.join(&old_file).is_file() {
+            fs::rename(agent_dir.join(&old_file), agent_dir.join(&new_file))?;
+            renamed.insert(old_file, new_file);
+        }
+    }
+
+    let manifest_path = agent_dir.join(MANIFEST_FILE);
+    if manifest_path.is_file() {
+        let mut manifest: toml::Table = toml::from_str(&fs::read_to_string(&manifest_path)?)
+            .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+        if manifest.contains_key("name") {
+            manifest.insert("name".to_string(), toml::Value::String(new_name.to_string()));
+        }
+        if let Some(new_path) = manifest.get("memory_path")
+            .and_then(|p| p.as_str())
+            .and_then(|p| renamed.get(p))
+        {
+            manifest.insert("memory_path".to_string(), toml::Value::String(new_path.clone()));
+        }
+        fs::write(&manifest_path, toml::to_string_pretty(&manifest)?)?;
+    }
+
+    Ok(())
+}
+
+/// SHA-256 of every file below `root`, keyed by `/`-separated relative path
+fn hash_files(root: &Path) -> Result<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let relative = path.strip_prefix(root)?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(relative, hex(&Sha256::digest(fs::read(&path)?)));
+        }
+    }
+
+    Ok(files)
+}
+
+fn checksum(files: &BTreeMap<String, String>) -> String {
+    let mut hasher = Sha256::new();
+    for (path, hash) in files {
+        hasher.update(format!("{}\0{}\n", path, hash));
+    }
+    hex(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(root: &Path, name: &str, memory: &str) -> PathBuf {
+        let dir = root.join(name);
+        fs::create_dir_all(dir.join("Sessions")).unwrap();
+        fs::write(dir.join(format!("{}.md", name)), memory).unwrap();
+        fs::write(dir.join("README.md"), format!("# {}\n", name)).unwrap();
+        fs::write(dir.join(MANIFEST_FILE), format!("name = \"{}\"\nmemory_path = \"{}.md\"\n", name, name)).unwrap();
+        fs::write(dir.join("Sessions").join("log.md"), "private").unwrap();
+        dir
+    }
+
+    /// What `ci load` leaves behind in an agent directory
+    fn add_load_artifacts(dir: &Path) {
+        fs::create_dir_all(dir.join("sessions")).unwrap();
+        fs::write(dir.join("sessions").join("1700000000.json"), "{\"task\": \"private\"}").unwrap();
+        fs::write(dir.join("working_1700000000.md"), "# Working memory\n\nTask: private").unwrap();
+        fs::write(dir.join("metadata.json"), "{\"usage_count\": 3}").unwrap();
+    }
+
+    #[test]
+    fn test_export_and_import_roundtrip() {
+        let source = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let bundle_path = source.path().join(format!("Athena.{}", BUNDLE_EXTENSION));
+
+        let agent_dir = agent(source.path(), "Athena", "# Athena\n");
+        add_load_artifacts(&agent_dir);
+        let manifest = export_agent(&agent_dir, &bundle_path, false).unwrap();
+        let files: Vec<&str> = manifest.files.keys().map(|f| f.as_str()).collect();
+        assert_eq!(files, vec!["Athena.md", "README.md", MANIFEST_FILE]);
+
+        let with_sessions = source.path().join("with-sessions.tar.gz");
+        let manifest = export_agent(&agent_dir, &with_sessions, true).unwrap();
+        assert!(manifest.files.contains_key("sessions/1700000000.json"));
+        assert!(manifest.files.contains_key("working_1700000000.md"));
+        assert!(manifest.files.contains_key("metadata.json"));
+
+        let bundle = open_bundle(&bundle_path).unwrap();
+        assert!(bundle.validation.is_valid(), "{:?}", bundle.validation.errors);
+
+        let outcome = install_bundle(&bundle, target.path(), None).unwrap();
+        assert_eq!(outcome.added.len(), 3);
+        assert!(target.path().join("Athena").join("Athena.md").is_file());
+        assert!(install_bundle(&bundle, target.path(), None).is_err());
+
+        let renamed = install_bundle(&bundle, target.path(), Some(&ConflictStrategy::Rename("Pallas".into()))).unwrap();
+        assert!(renamed.agent_dir.join("Pallas.md").is_file());
+        assert!(validate_agent_dir(&renamed.agent_dir).is_valid());
+    }
+
+    #[test]
+    fn test_merge_keeps_local_changes() {
+        let source = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let bundle_path = source.path().join("bundle.tar.gz");
+        let dir = agent(source.path(), "Athena", "# Athena\nincoming\n");
+        fs::write(dir.join("Toolkit.md"), "tools").unwrap();
+        export_agent(&dir, &bundle_path, false).unwrap();
+
+        agent(target.path(), "Athena", "# Athena\nlocal\n");
+        let bundle = open_bundle(&bundle_path).unwrap();
+        let outcome = install_bundle(&bundle, target.path(), Some(&ConflictStrategy::Merge)).unwrap();
+
+        assert_eq!(outcome.added, vec!["Toolkit.md"]);
+        assert_eq!(outcome.conflicts, vec!["Athena.md"]);
+        let local = target.path().join("Athena");
+        assert!(fs::read_to_string(local.join("Athena.md")).unwrap().contains("local"));
+        assert!(fs::read_to_string(local.join("Athena.md.incoming")).unwrap().contains("incoming"));
+    }
+
+    #[test]
+    fn test_tampered_bundle_is_rejected() {
+        let source = TempDir::new().unwrap();
+        let bundle_path = source.path().join("bundle.tar.gz");
+        let dir = agent(source.path(), "Athena", "# Athena\n");
+        let mut manifest = export_agent(&dir, &bundle_path, false).unwrap();
+
+        // Rebuild the archive with a manifest that no longer matches the files
+        manifest.files.insert("README.md".to_string(), "0".repeat(64));
+        let staging = TempDir::new().unwrap();
+        fs::write(staging.path().join(BUNDLE_MANIFEST), serde_json::to_string(&manifest).unwrap()).unwrap();
+        let mut archive = tar::Builder::new(GzEncoder::new(fs::File::create(&bundle_path).unwrap(), Compression::default()));
+        archive.append_path_with_name(staging.path().join(BUNDLE_MANIFEST), BUNDLE_MANIFEST).unwrap();
+        archive.append_dir_all(AGENT_ROOT, &dir).unwrap();
+        archive.into_inner().unwrap().finish().unwrap();
+
+        let error = open_bundle(&bundle_path).err().unwrap();
+        assert_eq!(error.to_string(), "Checksum mismatch for README.md");
+    }
+}
@@ -0,0 +1,506 @@
+//! Pluggable launcher backends for agent sessions
+//!
+//! Every command that hands an agent to an assistant CLI describes the launch
+//! as a `LaunchRequest`. The configured `AgentLauncher` turns it into a
+//! `LaunchSpec`, so the Claude CLI, any other assistant CLI or a recording
+//! no-op backend can be swapped in through `.ci-config.json`:
+//!
+//! ```json
+//! "launcher": {
+//!   "backend": "generic",
+//!   "generic": {
+//!     "command": "aider",
+//!     "args": ["--read", "{memory_file}"],
+//!     "prompt_flag": "--message",
+//!     "permission_args": ["--yes-always"]
+//!   }
+//! }
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use crate::config::{find_nearest_config, GenericLauncherConfig, LauncherConfig};
+use crate::helpers::agent_supervisor::LaunchSpec;
+use crate::helpers::session_provenance::SESSION_ID_VAR;
+use crate::helpers::transcript;
+
+/// What a command wants launched, independent of the backend
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchRequest {
+    /// Agent (or agent team) being launched
+    pub agent_name: String,
+
+    /// Window/session title shown by the assistant, if supported
+    pub title: Option<String>,
+
+    /// Memory file handed to the assistant
+    pub memory_file: Option<PathBuf>,
+
+    /// Prefer piping the memory file into stdin over passing it as an argument
+    pub memory_via_stdin: bool,
+
+    /// Initial prompt
+    pub prompt: Option<String>,
+
+    /// Skip permission prompts
+    pub bypass_permissions: bool,
+
+    /// Backend-specific arguments placed before the memory file
+    pub extra_args: Vec<String>,
+
+    /// Working directory for the assistant
+    pub cwd: Option<PathBuf>,
+
+    /// Run non-interactively: answer the prompt and exit
+    #[serde(default)]
+    pub headless: bool,
+
+    /// Record the terminal session into this transcript file
+    #[serde(default)]
+    pub transcript: Option<PathBuf>,
+
+    /// Session store id, exported to the assistant as `CI_SESSION_ID`
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+/// Result of a foreground launch
+#[derive(Debug, Clone, Copy)]
+pub struct LaunchOutcome {
+    pub success: bool,
+    pub exit_code: Option<i32>,
+}
+
+/// Backend that starts an assistant CLI for an agent
+pub trait AgentLauncher: Send + Sync {
+    /// Human readable backend name used in messages
+    fn display_name(&self) -> String;
+
+    /// Whether the backend can launch on this system
+    fn is_available(&self) -> bool;
+
+    /// Turn a request into the process to run
+    fn prepare(&self, request: &LaunchRequest) -> Result<LaunchSpec>;
+
+    /// Command a user can run by hand to start the session
+    fn manual_hint(&self, memory_file: &Path) -> String;
+
+    /// Launch in the foreground and wait for the assistant to exit,
+    /// recording a transcript when the request asks for one
+    fn launch(&self, request: &LaunchRequest) -> Result<LaunchOutcome> {
+        let spec = self.prepare(request)?;
+        let status = match &request.transcript {
+            Some(transcript) => transcript::run_recorded(&spec, transcript),
+            None => spec.status().map_err(anyhow::Error::from),
+        }.with_context(|| format!("Failed to launch {}", self.display_name()))?;
+
+        Ok(LaunchOutcome {
+            success: status.success(),
+            exit_code: status.code(),
+        })
+    }
+}
+
+/// Launcher for the Claude Code CLI
+#[derive(Debug, Default)]
+pub struct ClaudeLauncher;
+
+impl AgentLauncher for ClaudeLauncher {
+    fn display_name(&self) -> String {
+        "Claude Code".to_string()
+    }
+
+    fn is_available(&self) -> bool {
+        command_exists("claude")
+    }
+
+    fn prepare(&self, request: &LaunchRequest) -> Result<LaunchSpec> {
+        let mut spec = LaunchSpec::new("claude");
+
+        // Headless runs use print mode; memory sessions go through `claude code`;
+        // prompt-only sessions start interactive mode directly
+        if request.headless {
+            spec.args.push("-p".to_string());
+        } else if request.memory_file.is_some() || request.prompt.is_none() {
+            spec.args.push("code".to_string());
+        }
+
+        spec.args.extend(request.extra_args.iter().cloned());
+
+        if let Some(memory_file) = &request.memory_file {
+            // Print mode reads context from stdin
+            if request.memory_via_stdin || request.headless {
+                spec.stdin_file = Some(memory_file.clone());
+            } else {
+                spec.args.push(memory_file.display().to_string());
+            }
+        }
+
+        if request.bypass_permissions {
+            spec.args.push("--permission-mode".to_string());
+            spec.args.push("bypassPermissions".to_string());
+        }
+
+        if let Some(prompt) = &request.prompt {
+            spec.args.push(prompt.clone());
+        }
+
+        if let Some(title) = &request.title {
+            spec.envs.push(("CLAUDE_WINDOW_TITLE".to_string(), title.clone()));
+        }
+        spec.envs.extend(session_env(request));
+
+        spec.cwd = request.cwd.clone();
+        Ok(spec)
+    }
+
+    fn manual_hint(&self, memory_file: &Path) -> String {
+        format!("cat {} | claude code", memory_file.display())
+    }
+}
+
+/// Launcher for any assistant CLI described by a command template
+#[derive(Debug, Clone)]
+pub struct GenericCliLauncher {
+    config: GenericLauncherConfig,
+}
+
+impl GenericCliLauncher {
+    pub fn new(config: GenericLauncherConfig) -> Self {
+        Self { config }
+    }
+
+    fn memory_mode(&self) -> &str {
+        self.config.memory_mode.as_deref().unwrap_or("arg")
+    }
+
+    /// Substitute placeholders in a template argument.
+    /// Returns `None` when the argument is a bare placeholder without a value.
+    fn render_arg(arg: &str, request: &LaunchRequest) -> Option<String> {
+        let memory_file = request.memory_file.as_ref().map(|p| p.display().to_string());
+        let values = [
+            ("{memory_file}", memory_file),
+            ("{prompt}", request.prompt.clone()),
+            ("{agent}", Some(request.agent_name.clone())),
+        ];
+
+        let mut rendered = arg.to_string();
+        for (placeholder, value) in &values {
+            if !rendered.contains(placeholder) {
+                continue;
+            }
+            match value {
+                Some(value) => rendered = rendered.replace(placeholder, value),
+                None if arg == *placeholder => return None,
+                None => rendered = rendered.replace(placeholder, ""),
+            }
+        }
+
+        Some(rendered)
+    }
+}
+
+impl AgentLauncher for GenericCliLauncher {
+    fn display_name(&self) -> String {
+        if self.config.command.is_empty() {
+            "generic launcher".to_string()
+        } else {
+            self.config.command.clone()
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        !self.config.command.is_empty() && command_exists(&self.config.command)
+    }
+
+    fn prepare(&self, request: &LaunchRequest) -> Result<LaunchSpec> {
+        if self.config.command.is_empty() {
+            return Err(anyhow::anyhow!("launcher.generic.command is not set in .ci-config.json"));
+        }
+
+        let mut spec = LaunchSpec::new(&self.config.command);
+        let template_uses = |placeholder: &str| self.config.args.iter().any(|a| a.contains(placeholder));
+
+        spec.args.extend(self.config.args.iter().filter_map(|arg| Self::render_arg(arg, request)));
+        spec.args.extend(request.extra_args.iter().cloned());
+
+        if let Some(memory_file) = &request.memory_file {
+            match self.memory_mode() {
+                "stdin" => spec.stdin_file = Some(memory_file.clone()),
+                "none" => {}
+                _ if template_uses("{memory_file}") => {}
+                _ => {
+                    if let Some(flag) = &self.config.memory_flag {
+                        spec.args.push(flag.clone());
+                    }
+                    spec.args.push(memory_file.display().to_string());
+                }
+            }
+        }
+
+        if request.bypass_permissions {
+            spec.args.extend(self.config.permission_args.iter().cloned());
+        }
+
+        if request.headless {
+            spec.args.extend(self.config.headless_args.iter().cloned());
+        }
+
+        if let Some(prompt) = &request.prompt {
+            if !template_uses("{prompt}") {
+                if let Some(flag) = &self.config.prompt_flag {
+                    spec.args.push(flag.clone());
+                }
+                spec.args.push(prompt.clone());
+            }
+        }
+
+        let mut env: Vec<_> = self.config.env.iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        env.sort();
+        env.extend(session_env(request));
+        spec.envs = env;
+        spec.cwd = request.cwd.clone();
+
+        Ok(spec)
+    }
+
+    fn manual_hint(&self, memory_file: &Path) -> String {
+        let request = LaunchRequest {
+            memory_file: Some(memory_file.to_path_buf()),
+            ..Default::default()
+        };
+
+        match self.prepare(&request) {
+            Ok(spec) => {
+                let mut hint = std::iter::once(spec.program)
+                    .chain(spec.args)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if let Some(stdin_file) = spec.stdin_file {
+                    hint.push_str(&format!(" < {}", stdin_file.display()));
+                }
+                hint
+            }
+            Err(_) => format!("<assistant> {}", memory_file.display()),
+        }
+    }
+}
+
+/// One launch captured by the recording launcher
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedLaunch {
+    pub timestamp: String,
+    pub request: LaunchRequest,
+}
+
+/// No-op launcher that records requests instead of starting anything.
+/// Useful for dry runs and for exercising launch paths offline.
+#[derive(Debug, Default)]
+pub struct RecordingLauncher {
+    record_path: Option<PathBuf>,
+    records: Mutex<Vec<RecordedLaunch>>,
+}
+
+impl RecordingLauncher {
+    pub fn new(record_path: Option<PathBuf>) -> Self {
+        Self {
+            record_path,
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Launches recorded by this instance
+    pub fn records(&self) -> Vec<RecordedLaunch> {
+        self.records.lock().map(|r| r.clone()).unwrap_or_default()
+    }
+
+    fn record(&self, request: &LaunchRequest) -> Result<()> {
+        let entry = RecordedLaunch {
+            timestamp: Utc::now().to_rfc3339(),
+            request: request.clone(),
+        };
+
+        if let Some(path) = &self.record_path {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Failed to open launch record: {}", path.display()))?;
+            writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        }
+
+        if let Ok(mut records) = self.records.lock() {
+            records.push(entry);
+        }
+
+        Ok(())
+    }
+}
+
+impl AgentLauncher for RecordingLauncher {
+    fn display_name(&self) -> String {
+        "recording launcher".to_string()
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn prepare(&self, request: &LaunchRequest) -> Result<LaunchSpec> {
+        self.record(request)?;
+        // Supervised launches still need a process; `true` exits immediately with success
+        Ok(LaunchSpec::new("true"))
+    }
+
+    fn manual_hint(&self, memory_file: &Path) -> String {
+        format!("(recording only) {}", memory_file.display())
+    }
+
+    fn launch(&self, request: &LaunchRequest) -> Result<LaunchOutcome> {
+        self.record(request)?;
+        Ok(LaunchOutcome {
+            success: true,
+            exit_code: Some(0),
+        })
+    }
+}
+
+/// Build the launcher described by a launcher configuration
+pub fn launcher_from_config(config: &LauncherConfig) -> Result<Box<dyn AgentLauncher>> {
+    match config.backend.to_lowercase().as_str() {
+        "claude" => Ok(Box::new(ClaudeLauncher)),
+        "generic" | "cli" => Ok(Box::new(GenericCliLauncher::new(config.generic.clone()))),
+        "recording" | "record" | "noop" => {
+            let record_path = config.record_path.as_ref()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| std::env::temp_dir().join("ci_launcher_record.jsonl"));
+            Ok(Box::new(RecordingLauncher::new(Some(record_path))))
+        }
+        other => Err(anyhow!("Unknown launcher backend '{}' (use claude, generic or recording)", other)),
+    }
+}
+
+/// Load the launcher configured in the nearest `.ci-config.json`, defaulting to Claude
+pub fn configured_launcher() -> Result<Box<dyn AgentLauncher>> {
+    let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+
+    match find_nearest_config(&current_dir) {
+        Some((_, config)) => launcher_from_config(&config.launcher),
+        None => Ok(Box::new(ClaudeLauncher)),
+    }
+}
+
+/// Environment telling the assistant which session it runs in
+fn session_env(request: &LaunchRequest) -> Option<(String, String)> {
+    request.session_id.clone().map(|id| (SESSION_ID_VAR.to_string(), id))
+}
+
+/// Check whether a program is on the PATH
+fn command_exists(program: &str) -> bool {
+    Command::new("which")
+        .arg(program)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn memory_request() -> LaunchRequest {
+        LaunchRequest {
+            agent_name: "Athena".to_string(),
+            memory_file: Some(PathBuf::from("/tmp/athena.md")),
+            bypass_permissions: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_claude_launcher_args() {
+        let spec = ClaudeLauncher.prepare(&memory_request()).unwrap();
+        assert_eq!(spec.program, "claude");
+        assert_eq!(spec.args, vec!["code", "/tmp/athena.md", "--permission-mode", "bypassPermissions"]);
+
+        let mut request = memory_request();
+        request.memory_via_stdin = true;
+        let spec = ClaudeLauncher.prepare(&request).unwrap();
+        assert_eq!(spec.stdin_file, Some(PathBuf::from("/tmp/athena.md")));
+        assert!(!spec.args.iter().any(|a| a.ends_with("athena.md")));
+
+        let mut request = memory_request();
+        request.headless = true;
+        request.prompt = Some("fix the build".to_string());
+        let spec = ClaudeLauncher.prepare(&request).unwrap();
+        assert_eq!(spec.args, vec!["-p", "--permission-mode", "bypassPermissions", "fix the build"]);
+        assert_eq!(spec.stdin_file, Some(PathBuf::from("/tmp/athena.md")));
+    }
+
+    #[test]
+    fn test_generic_launcher_template() {
+        let launcher = GenericCliLauncher::new(GenericLauncherConfig {
+            command: "assistant".to_string(),
+            args: vec!["--agent={agent}".to_string(), "{prompt}".to_string()],
+            memory_flag: Some("--system".to_string()),
+            permission_args: vec!["--yes".to_string()],
+            ..Default::default()
+        });
+
+        let spec = launcher.prepare(&memory_request()).unwrap();
+        assert_eq!(spec.program, "assistant");
+        assert_eq!(spec.args, vec!["--agent=Athena", "--system", "/tmp/athena.md", "--yes"]);
+
+        let mut request = memory_request();
+        request.prompt = Some("fix the build".to_string());
+        request.bypass_permissions = false;
+        let spec = launcher.prepare(&request).unwrap();
+        assert_eq!(spec.args, vec!["--agent=Athena", "fix the build", "--system", "/tmp/athena.md"]);
+        assert!(spec.envs.is_empty());
+
+        request.session_id = Some("abc123".to_string());
+        let expected = (SESSION_ID_VAR.to_string(), "abc123".to_string());
+        assert!(launcher.prepare(&request).unwrap().envs.contains(&expected));
+        assert!(ClaudeLauncher.prepare(&request).unwrap().envs.contains(&expected));
+    }
+
+    #[test]
+    fn test_recording_launcher_writes_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let record_path = temp_dir.path().join("launches.jsonl");
+        let launcher = RecordingLauncher::new(Some(record_path.clone()));
+
+        let outcome = launcher.launch(&memory_request()).unwrap();
+        assert!(outcome.success);
+        assert_eq!(launcher.prepare(&memory_request()).unwrap().program, "true");
+
+        assert_eq!(launcher.records().len(), 2);
+        let content = fs::read_to_string(&record_path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert!(content.contains("Athena"));
+    }
+
+    #[test]
+    fn test_launcher_from_config_rejects_unknown_backends() {
+        let mut config = LauncherConfig { backend: "Generic".to_string(), ..Default::default() };
+        assert_eq!(launcher_from_config(&config).unwrap().display_name(), GenericCliLauncher::new(config.generic.clone()).display_name());
+
+        config.backend = "cluade".to_string();
+        let error = launcher_from_config(&config).err().unwrap().to_string();
+        assert!(error.contains("'cluade'"));
+        assert!(error.contains("claude, generic or recording"));
+    }
+}
@@ -0,0 +1,347 @@
+//! Agent manifests (`agent.toml`)
+//!
+//! An agent directory may carry an `agent.toml` manifest describing the agent.
+//! It deserializes into `AgentMetadata`; paths are relative to the agent
+//! directory. The manifest is preferred over README scraping wherever agent
+//! details are shown, and `ci agent validate` checks it against the files on disk.
+//!
+//! ```toml
+//! name = "Athena"
+//! description = "System architect and memory steward"
+//! version = "1.2.0"
+//! capabilities = ["architecture", "planning", "memory"]
+//! memory_path = "Athena_memory.md"
+//! learning_path = "ContinuousLearning.md"
+//!
+//! [attributes]
+//! color = "blue"
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the manifest inside an agent directory
+pub const MANIFEST_FILE: &str = "agent.toml";
+
+/// Capabilities an agent may declare; custom ones must use an `x-` prefix
+pub const KNOWN_CAPABILITIES: &[&str] = &[
+    "analysis",
+    "architecture",
+    "code-review",
+    "coordination",
+    "data",
+    "debugging",
+    "design",
+    "devops",
+    "documentation",
+    "learning",
+    "memory",
+    "performance",
+    "planning",
+    "refactoring",
+    "research",
+    "security",
+    "testing",
+    "writing",
+];
+
+/// Structure to represent agent metadata
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AgentMetadata {
+    /// Agent name
+    #[serde(default)]
+    pub name: String,
+
+    /// Agent role/description
+    #[serde(default)]
+    pub description: String,
+
+    /// Agent capabilities
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+
+    /// Agent creation date
+    #[serde(default)]
+    pub created_at: String,
+
+    /// Agent last used date
+    #[serde(default)]
+    pub last_used: Option<String>,
+
+    /// Number of times the agent has been used
+    #[serde(default)]
+    pub usage_count: usize,
+
+    /// Agent version
+    #[serde(default = "default_version")]
+    pub version: String,
+
+    /// Agent toolkit path
+    #[serde(default)]
+    pub toolkit_path: String,
+
+    /// Agent memory path
+    #[serde(default)]
+    pub memory_path: String,
+
+    /// Agent continuous learning path
+    #[serde(default)]
+    pub learning_path: Option<String>,
+
+    /// Custom attributes
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+}
+
+fn default_version() -> String {
+    "1.0".to_string()
+}
+
+impl AgentMetadata {
+    /// Load the manifest of an agent directory, if it has one.
+    /// Relative paths are resolved against the agent directory.
+    pub fn from_manifest(agent_dir: &Path) -> Result<Option<Self>> {
+        let manifest_path = agent_dir.join(MANIFEST_FILE);
+        if !manifest_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let mut metadata: AgentMetadata = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+        if metadata.name.is_empty() {
+            metadata.name = dir_name(agent_dir);
+        }
+
+        let toolkit_path = resolve_path(agent_dir, &metadata.toolkit_path);
+        metadata.toolkit_path = toolkit_path.display().to_string();
+
+        if metadata.memory_path.is_empty() {
+            if let Some(default_memory) = default_memory_file(agent_dir, &metadata.name) {
+                metadata.memory_path = default_memory.display().to_string();
+            }
+        } else {
+            metadata.memory_path = resolve_path(&toolkit_path, &metadata.memory_path).display().to_string();
+        }
+
+        metadata.learning_path = metadata.learning_path
+            .as_ref()
+            .map(|p| resolve_path(&toolkit_path, p).display().to_string());
+
+        Ok(Some(metadata))
+    }
+
+    /// Overlay the descriptive fields of a manifest, keeping usage data
+    pub fn apply_manifest(&mut self, manifest: &AgentMetadata) {
+        self.name = manifest.name.clone();
+        if !manifest.description.is_empty() {
+            self.description = manifest.description.clone();
+        }
+        self.capabilities = manifest.capabilities.clone();
+        self.version = manifest.version.clone();
+        if !manifest.memory_path.is_empty() {
+            self.memory_path = manifest.memory_path.clone();
+        }
+        if manifest.learning_path.is_some() {
+            self.learning_path = manifest.learning_path.clone();
+        }
+        self.attributes.extend(manifest.attributes.clone());
+    }
+}
+
+/// Outcome of validating one agent directory
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    pub agent_name: String,
+    pub has_manifest: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Validate an agent directory and its manifest
+pub fn validate_agent_dir(agent_dir: &Path) -> ValidationReport {
+    let agent_name = dir_name(agent_dir);
+    let mut report = ValidationReport {
+        agent_name: agent_name.clone(),
+        has_manifest: agent_dir.join(MANIFEST_FILE).exists(),
+        ..Default::default()
+    };
+
+    if !agent_dir.join("README.md").exists() {
+        report.warnings.push("README.md is missing".to_string());
+    }
+
+    let metadata = match AgentMetadata::from_manifest(agent_dir) {
+        Ok(Some(metadata)) => metadata,
+        Ok(None) => {
+            report.warnings.push(format!("No {} manifest", MANIFEST_FILE));
+            if default_memory_file(agent_dir, &agent_name).is_none() {
+                report.errors.push(format!(
+                    "No memory file found ({0}.md, {0}_memory.md or MEMORY.md)", agent_name
+                ));
+            }
+            return report;
+        }
+        Err(e) => {
+            report.errors.push(format!("{:#}", e));
+            return report;
+        }
+    };
+
+    if metadata.name != agent_name {
+        report.errors.push(format!(
+            "Manifest name '{}' does not match directory '{}'", metadata.name, agent_name
+        ));
+    }
+
+    if metadata.description.trim().is_empty() {
+        report.warnings.push("Manifest has no description".to_string());
+    }
+
+    if let Err(e) = parse_version(&metadata.version) {
+        report.errors.push(format!("Invalid version '{}': {}", metadata.version, e));
+    }
+
+    for capability in &metadata.capabilities {
+        if !is_known_capability(capability) {
+            report.errors.push(format!(
+                "Unknown capability '{}' (use one of: {}, or an x- prefix)",
+                capability,
+                KNOWN_CAPABILITIES.join(", ")
+            ));
+        }
+    }
+
+    if !Path::new(&metadata.toolkit_path).is_dir() {
+        report.errors.push(format!("toolkit_path does not exist: {}", metadata.toolkit_path));
+    }
+
+    if metadata.memory_path.is_empty() {
+        report.errors.push("No memory_path set and no default memory file found".to_string());
+    } else if !Path::new(&metadata.memory_path).is_file() {
+        report.errors.push(format!("memory_path does not exist: {}", metadata.memory_path));
+    }
+
+    if let Some(learning_path) = &metadata.learning_path {
+        if !Path::new(learning_path).is_file() {
+            report.errors.push(format!("learning_path does not exist: {}", learning_path));
+        }
+    }
+
+    report
+}
+
+/// Parse a `major[.minor[.patch]][-suffix]` version into its numeric parts
+pub fn parse_version(version: &str) -> std::result::Result<Vec<u64>, String> {
+    let core = version.trim().split(['-', '+']).next().unwrap_or_default();
+    if core.is_empty() {
+        return Err("empty version".to_string());
+    }
+
+    let parts = core.split('.')
+        .map(|part| part.parse::<u64>().map_err(|_| format!("'{}' is not a number", part)))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    if parts.len() > 3 {
+        return Err("expected at most three components".to_string());
+    }
+
+    Ok(parts)
+}
+
+/// Whether a capability is in the known vocabulary (or a custom `x-` capability)
+pub fn is_known_capability(capability: &str) -> bool {
+    let capability = capability.trim().to_lowercase();
+    capability.starts_with("x-") || KNOWN_CAPABILITIES.contains(&capability.as_str())
+}
+
+/// Locate the conventional memory file of an agent directory
+pub fn default_memory_file(agent_dir: &Path, agent_name: &str) -> Option<PathBuf> {
+    [
+        format!("{}.md", agent_name),
+        format!("{}_memory.md", agent_name),
+        "MEMORY.md".to_string(),
+    ]
+    .iter()
+    .map(|name| agent_dir.join(name))
+    .find(|path| path.is_file())
+}
+
+fn resolve_path(base: &Path, path: &str) -> PathBuf {
+    if path.is_empty() || path == "." {
+        base.to_path_buf()
+    } else if Path::new(path).is_absolute() {
+        PathBuf::from(path)
+    } else {
+        base.join(path)
+    }
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn agent_dir(manifest: &str) -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("Athena");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("README.md"), "# Athena\n").unwrap();
+        fs::write(dir.join("Athena_memory.md"), "# Memory\n").unwrap();
+        fs::write(dir.join(MANIFEST_FILE), manifest).unwrap();
+        (temp_dir, dir)
+    }
+
+    #[test]
+    fn test_manifest_resolves_paths() {
+        let (_temp, dir) = agent_dir(
+            "description = \"Architect\"\nversion = \"1.2.0\"\ncapabilities = [\"architecture\"]\nmemory_path = \"Athena_memory.md\"\n"
+        );
+
+        let metadata = AgentMetadata::from_manifest(&dir).unwrap().unwrap();
+        assert_eq!(metadata.name, "Athena");
+        assert_eq!(PathBuf::from(&metadata.memory_path), dir.join("Athena_memory.md"));
+
+        let report = validate_agent_dir(&dir);
+        assert!(report.is_valid(), "{:?}", report.errors);
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let (_temp, dir) = agent_dir(
+            "name = \"Zeus\"\nversion = \"one\"\ncapabilities = [\"telepathy\", \"x-custom\"]\nlearning_path = \"missing.md\"\n"
+        );
+
+        let report = validate_agent_dir(&dir);
+        assert_eq!(report.errors.len(), 5, "{:?}", report.errors);
+        assert!(report.errors.iter().any(|e| e.contains("Zeus")));
+        assert!(report.errors.iter().any(|e| e.contains("telepathy")));
+        assert!(!report.errors.iter().any(|e| e.contains("x-custom")));
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("1.2.3-beta").unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_version("2").unwrap(), vec![2]);
+        assert!(parse_version("1.x").is_err());
+        assert!(parse_version("1.2.3.4").is_err());
+    }
+}
@@ -0,0 +1,313 @@
+//! Capability-based agent selection
+//!
+//! Ranks the agents in an AGENTS directory against a free-text task using
+//! BM25 over each agent's manifest (capabilities, description), README and
+//! memory headings. Fields are weighted by repeating their terms, so a
+//! capability match counts more than a word buried in the README.
+
+use anyhow::Result;
+use colored::*;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::helpers::agent_manifest::{default_memory_file, AgentMetadata};
+
+/// BM25 term frequency saturation
+const K1: f64 = 1.2;
+
+/// BM25 document length normalization
+const B: f64 = 0.75;
+
+/// Field weights, applied as term repetitions
+const CAPABILITY_WEIGHT: usize = 3;
+const DESCRIPTION_WEIGHT: usize = 2;
+const HEADING_WEIGHT: usize = 1;
+const README_WEIGHT: usize = 1;
+
+/// Words too common to say anything about a task
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "into", "is", "it",
+    "of", "on", "or", "our", "that", "the", "this", "to", "we", "with", "you", "your", "all",
+    "agent", "agents", "will", "can", "should", "please", "make", "use", "using",
+];
+
+/// A ranked agent with the terms that matched
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentMatch {
+    pub name: String,
+    pub description: String,
+    pub score: f64,
+    pub reasons: Vec<String>,
+}
+
+/// Searchable text of one agent, split by field
+#[derive(Debug, Clone, Default)]
+struct AgentProfile {
+    name: String,
+    description: String,
+    capabilities: Vec<String>,
+    headings: Vec<String>,
+    readme: String,
+}
+
+impl AgentProfile {
+    fn load(agent_dir: &Path, name: &str) -> Self {
+        let mut profile = AgentProfile {
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        let mut memory_path = default_memory_file(agent_dir, name);
+        if let Ok(Some(manifest)) = AgentMetadata::from_manifest(agent_dir) {
+            profile.description = manifest.description;
+            profile.capabilities = manifest.capabilities;
+            if !manifest.memory_path.is_empty() {
+                memory_path = Some(manifest.memory_path.into());
+            }
+        }
+
+        if let Ok(readme) = fs::read_to_string(agent_dir.join("README.md")) {
+            if profile.description.is_empty() {
+                profile.description = readme.lines()
+                    .map(str::trim)
+                    .find(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with("---"))
+                    .unwrap_or_default()
+                    .to_string();
+            }
+            profile.readme = readme;
+        }
+
+        if let Some(memory) = memory_path.and_then(|p| fs::read_to_string(p).ok()) {
+            profile.headings = memory.lines()
+                .filter(|l| l.starts_with('#'))
+                .map(|l| l.trim_start_matches('#').trim().to_string())
+                .filter(|h| !h.is_empty())
+                .collect();
+        }
+
+        profile
+    }
+
+    /// Weighted bag of terms for BM25
+    fn terms(&self) -> Vec<String> {
+        let mut terms = Vec::new();
+        let mut add = |text: &str, weight: usize| {
+            for term in tokenize(text) {
+                for _ in 0..weight {
+                    terms.push(term.clone());
+                }
+            }
+        };
+
+        add(&self.name, CAPABILITY_WEIGHT);
+        for capability in &self.capabilities {
+            add(capability, CAPABILITY_WEIGHT);
+        }
+        add(&self.description, DESCRIPTION_WEIGHT);
+        for heading in &self.headings {
+            add(heading, HEADING_WEIGHT);
+        }
+        add(&self.readme, README_WEIGHT);
+
+        terms
+    }
+
+    /// Explain which fields contain a query term
+    fn reasons_for(&self, term: &str) -> Vec<String> {
+        let mut reasons = Vec::new();
+
+        for capability in &self.capabilities {
+            if tokenize(capability).iter().any(|t| t == term) {
+                reasons.push(format!("capability '{}'", capability));
+            }
+        }
+        if tokenize(&self.description).iter().any(|t| t == term) {
+            reasons.push(format!("description mentions '{}'", term));
+        }
+        if let Some(heading) = self.headings.iter().find(|h| tokenize(h).iter().any(|t| t == term)) {
+            reasons.push(format!("memory section '{}'", heading));
+        }
+        if reasons.is_empty() && tokenize(&self.readme).iter().any(|t| t == term) {
+            reasons.push(format!("README mentions '{}'", term));
+        }
+
+        reasons
+    }
+}
+
+/// Rank every agent in `agents_dir` against a task description, best first.
+/// Agents that share no terms with the task are left out.
+pub fn rank_agents(agents_dir: &Path, task: &str) -> Result<Vec<AgentMatch>> {
+    let mut profiles = Vec::new();
+    for entry in fs::read_dir(agents_dir)?.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if !path.is_dir() || name.starts_with('.') || name.starts_with('_') || name.starts_with("parallel_session_") {
+            continue;
+        }
+        profiles.push(AgentProfile::load(&path, &name));
+    }
+
+    Ok(rank_profiles(&profiles, task))
+}
+
+fn rank_profiles(profiles: &[AgentProfile], task: &str) -> Vec<AgentMatch> {
+    let mut query: Vec<String> = tokenize(task);
+    let mut seen = HashSet::new();
+    query.retain(|t| seen.insert(t.clone()));
+
+    let documents: Vec<HashMap<String, usize>> = profiles.iter()
+        .map(|profile| {
+            let mut counts = HashMap::new();
+            for term in profile.terms() {
+                *counts.entry(term).or_insert(0) += 1;
+            }
+            counts
+        })
+        .collect();
+    let lengths: Vec<usize> = documents.iter().map(|d| d.values().sum()).collect();
+    let average_length = (lengths.iter().sum::<usize>() as f64 / lengths.len().max(1) as f64).max(1.0);
+    let total = profiles.len() as f64;
+
+    let mut matches: Vec<AgentMatch> = profiles.iter()
+        .zip(&documents)
+        .zip(&lengths)
+        .filter_map(|((profile, counts), &length)| {
+            let mut score = 0.0;
+            let mut reasons = Vec::new();
+
+            for term in &query {
+                let Some(&frequency) = counts.get(term) else {
+                    continue;
+                };
+                let containing = documents.iter().filter(|d| d.contains_key(term)).count() as f64;
+                let idf = ((total - containing + 0.5) / (containing + 0.5) + 1.0).ln();
+                let frequency = frequency as f64;
+                let norm = K1 * (1.0 - B + B * length as f64 / average_length);
+                score += idf * frequency * (K1 + 1.0) / (frequency + norm);
+
+                for reason in profile.reasons_for(term) {
+                    if !reasons.contains(&reason) {
+                        reasons.push(reason);
+                    }
+                }
+            }
+
+            (score > 0.0).then(|| AgentMatch {
+                name: profile.name.clone(),
+                description: profile.description.clone(),
+                score,
+                reasons,
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+    matches
+}
+
+/// Pick a team from ranked matches: the best agent plus those scoring at least half as well
+pub fn suggested_team(matches: &[AgentMatch], max_size: usize) -> Vec<String> {
+    let Some(best) = matches.first() else {
+        return Vec::new();
+    };
+
+    matches.iter()
+        .take(max_size.max(1))
+        .filter(|m| m.score >= best.score * 0.5)
+        .map(|m| m.name.clone())
+        .collect()
+}
+
+/// Print ranked matches with their reasons
+pub fn print_matches(matches: &[AgentMatch], limit: usize) {
+    for (index, agent) in matches.iter().take(limit).enumerate() {
+        println!("{}. {} {}  {}",
+            index + 1,
+            agent.name.cyan().bold(),
+            format!("({:.2})", agent.score).dimmed(),
+            agent.description
+        );
+        for reason in agent.reasons.iter().take(4) {
+            println!("     {} {}", "•".dimmed(), reason);
+        }
+    }
+}
+
+/// Lowercase words with stop words removed and common suffixes stripped
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| w.len() > 1 && !STOP_WORDS.contains(&w.as_str()))
+        .map(|w| stem(&w))
+        .collect()
+}
+
+/// Minimal suffix stripping so "documentation", "documenting" and "documents" meet
+fn stem(word: &str) -> String {
+    for suffix in ["ation", "ing", "ers", "ors", "er", "or", "es", "ed", "s"] {
+        if let Some(stripped) = word.strip_suffix(suffix) {
+            if stripped.len() >= 4 {
+                return stripped.to_string();
+            }
+        }
+    }
+    word.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str, description: &str, capabilities: &[&str], headings: &[&str]) -> AgentProfile {
+        AgentProfile {
+            name: name.to_string(),
+            description: description.to_string(),
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            headings: headings.iter().map(|h| h.to_string()).collect(),
+            readme: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_ranking_prefers_capability_matches() {
+        let profiles = vec![
+            profile("Documentor", "Writes technical documentation", &["documentation", "writing"], &["API Reference"]),
+            profile("Tester", "Writes and runs tests", &["testing"], &["HTTP Fixtures"]),
+            profile("Designer", "Visual design", &["design"], &[]),
+        ];
+
+        let matches = rank_profiles(&profiles, "Document the HTTP layer");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].name, "Documentor");
+        assert!(matches[0].reasons.iter().any(|r| r.contains("capability 'documentation'")));
+        assert!(matches[1].reasons.iter().any(|r| r.contains("HTTP Fixtures")));
+    }
+
+    #[test]
+    fn test_suggested_team_cutoff() {
+        let matches: Vec<AgentMatch> = [("A", 4.0), ("B", 2.5), ("C", 1.0)]
+            .iter()
+            .map(|(name, score)| AgentMatch {
+                name: name.to_string(),
+                description: String::new(),
+                score: *score,
+                reasons: Vec::new(),
+            })
+            .collect();
+
+        assert_eq!(suggested_team(&matches, 3), vec!["A", "B"]);
+        assert_eq!(suggested_team(&matches, 1), vec!["A"]);
+        assert!(suggested_team(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_stemming_joins_word_forms() {
+        assert_eq!(tokenize("documentation documenting documents"), vec!["document"; 3]);
+        assert_eq!(tokenize("the API"), vec!["api"]);
+    }
+}
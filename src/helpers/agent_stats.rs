@@ -0,0 +1,331 @@
+//! Agent usage analytics
+//!
+//! Aggregates the session records written by `ci load`:
+//!
+//! - `AGENTS/<Name>/sessions/<timestamp>.json` for single-agent sessions
+//! - `AGENTS/<session>_session.json` for combined multi-agent sessions
+//! - `AGENTS/parallel_session_*/supervisor_state.json` for parallel sessions
+//!
+//! into per-agent launch counts, durations, co-loaded agents and last use.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::helpers::agent_manifest::AgentMetadata;
+use crate::helpers::agent_supervisor::SupervisorState;
+
+/// Prefix of the combined session name recorded for multi-agent sessions
+const MULTI_AGENT_PREFIX: &str = "MultiAgent[";
+
+/// Structure for agent session tracking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentSession {
+    /// Agent name
+    pub agent_name: String,
+
+    /// Session start time
+    pub start_time: String,
+
+    /// Context used for session
+    pub context: Option<String>,
+
+    /// Session end time (if completed)
+    pub end_time: Option<String>,
+
+    /// Session output path
+    pub output_path: Option<String>,
+}
+
+impl AgentSession {
+    /// Agents taking part in the session; multi-agent sessions are recorded as `MultiAgent[A,B]`
+    pub fn agent_names(&self) -> Vec<String> {
+        match self.agent_name.strip_prefix(MULTI_AGENT_PREFIX).and_then(|rest| rest.strip_suffix(']')) {
+            Some(list) => list.split(',').map(|name| name.trim().to_string()).filter(|n| !n.is_empty()).collect(),
+            None => vec![self.agent_name.clone()],
+        }
+    }
+
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        parse_timestamp(&self.start_time)
+    }
+
+    pub fn duration_secs(&self) -> Option<u64> {
+        let end = parse_timestamp(self.end_time.as_deref()?)?;
+        u64::try_from((end - self.started_at()?).num_seconds()).ok()
+    }
+}
+
+/// Usage figures for one agent
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AgentUsage {
+    pub name: String,
+
+    /// Sessions the agent took part in (a parallel session counts once)
+    pub launches: usize,
+
+    /// Instances started in parallel sessions
+    pub parallel_instances: usize,
+
+    /// Sum of all finished session durations
+    pub total_duration_secs: u64,
+
+    /// Median duration of finished sessions
+    pub median_duration_secs: Option<u64>,
+
+    pub last_used: Option<DateTime<Utc>>,
+
+    /// Agents most often loaded in the same session, with counts
+    pub co_loaded: Vec<(String, usize)>,
+
+    #[serde(skip)]
+    durations: Vec<u64>,
+
+    #[serde(skip)]
+    partners: HashMap<String, usize>,
+}
+
+/// Aggregated usage across all recorded sessions
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsageStats {
+    pub since: Option<DateTime<Utc>>,
+    pub sessions: usize,
+    pub agents: Vec<AgentUsage>,
+}
+
+/// One session reduced to what the statistics need
+struct SessionSample {
+    started_at: DateTime<Utc>,
+    /// (agent, instance duration) for every participant
+    participants: Vec<(String, Option<u64>)>,
+    parallel: bool,
+}
+
+/// Collect usage statistics from every session record under `agents_dir`
+pub fn collect_usage(agents_dir: &Path, since: Option<DateTime<Utc>>) -> Result<UsageStats> {
+    let samples: Vec<SessionSample> = read_sessions(agents_dir)?
+        .into_iter()
+        .filter(|s| since.is_none_or(|since| s.started_at >= since))
+        .collect();
+
+    let mut usage: BTreeMap<String, AgentUsage> = BTreeMap::new();
+    for sample in &samples {
+        let mut names: Vec<&String> = sample.participants.iter().map(|(name, _)| name).collect();
+        names.sort();
+        names.dedup();
+
+        for name in &names {
+            let entry = usage.entry(name.to_string()).or_insert_with(|| AgentUsage {
+                name: name.to_string(),
+                ..Default::default()
+            });
+            entry.launches += 1;
+            entry.last_used = entry.last_used.max(Some(sample.started_at));
+            for partner in names.iter().filter(|partner| partner != &name) {
+                *entry.partners.entry(partner.to_string()).or_insert(0) += 1;
+            }
+        }
+
+        for (name, duration) in &sample.participants {
+            let entry = usage.get_mut(name).unwrap();
+            if sample.parallel {
+                entry.parallel_instances += 1;
+            }
+            if let Some(duration) = duration {
+                entry.durations.push(*duration);
+                entry.total_duration_secs += duration;
+            }
+        }
+    }
+
+    let mut agents: Vec<AgentUsage> = usage.into_values()
+        .map(|mut agent| {
+            agent.durations.sort_unstable();
+            agent.median_duration_secs = median(&agent.durations);
+            let mut partners: Vec<(String, usize)> = agent.partners.drain().collect();
+            partners.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            partners.truncate(3);
+            agent.co_loaded = partners;
+            agent
+        })
+        .collect();
+    agents.sort_by(|a, b| b.launches.cmp(&a.launches).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(UsageStats {
+        since,
+        sessions: samples.len(),
+        agents,
+    })
+}
+
+/// Write launch counts and last-used dates into each agent's `metadata.json`.
+/// Returns the number of agents updated.
+pub fn sync_usage_metadata(agents_dir: &Path, stats: &UsageStats) -> Result<usize> {
+    let mut updated = 0;
+    for agent in &stats.agents {
+        let agent_dir = agents_dir.join(&agent.name);
+        if !agent_dir.is_dir() {
+            continue;
+        }
+
+        let metadata_path = agent_dir.join("metadata.json");
+        let mut metadata = fs::read_to_string(&metadata_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<AgentMetadata>(&content).ok())
+            .unwrap_or_else(|| AgentMetadata {
+                name: agent.name.clone(),
+                toolkit_path: agent_dir.display().to_string(),
+                created_at: Utc::now().to_rfc3339(),
+                ..Default::default()
+            });
+
+        let last_used = agent.last_used.map(|t| t.to_rfc3339());
+        if metadata.usage_count == agent.launches && metadata.last_used == last_used {
+            continue;
+        }
+
+        metadata.usage_count = agent.launches;
+        metadata.last_used = last_used;
+        fs::write(&metadata_path, serde_json::to_string_pretty(&metadata)?)?;
+        updated += 1;
+    }
+
+    Ok(updated)
+}
+
+/// Parse `--since` values: a date (`2024-05-01`), an RFC 3339 timestamp or a relative age (`12h`, `7d`, `4w`)
+pub fn parse_since(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Some(timestamp) = parse_timestamp(value) {
+        return Ok(timestamp);
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc());
+    }
+
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount.parse().map_err(|_| format!("Invalid --since value '{}'", value))?;
+    let age = match unit {
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => return Err(format!("Invalid --since value '{}' (use YYYY-MM-DD or e.g. 12h, 7d, 4w)", value)),
+    };
+
+    age.and_then(|age| Utc::now().checked_sub_signed(age))
+        .ok_or_else(|| format!("--since value '{}' is too far in the past", value))
+}
+
+fn read_sessions(agents_dir: &Path) -> Result<Vec<SessionSample>> {
+    let mut samples = Vec::new();
+
+    for entry in fs::read_dir(agents_dir)?.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if path.is_file() && name.ends_with("_session.json") {
+            samples.extend(read_session_file(&path));
+        } else if path.is_dir() && name.starts_with("parallel_session_") {
+            if let Ok(state) = SupervisorState::load(&path) {
+                samples.push(SessionSample {
+                    started_at: state.started_at,
+                    participants: state.instances.iter()
+                        .map(|instance| (instance.agent_name.clone(), instance.duration_secs))
+                        .collect(),
+                    parallel: true,
+                });
+            }
+        } else if path.is_dir() {
+            let Ok(files) = fs::read_dir(path.join("sessions")) else {
+                continue;
+            };
+            for file in files.flatten() {
+                if file.path().extension().is_some_and(|ext| ext == "json") {
+                    samples.extend(read_session_file(&file.path()));
+                }
+            }
+        }
+    }
+
+    Ok(samples)
+}
+
+fn read_session_file(path: &Path) -> Option<SessionSample> {
+    let session: AgentSession = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    let duration = session.duration_secs();
+    Some(SessionSample {
+        started_at: session.started_at()?,
+        participants: session.agent_names().into_iter().map(|name| (name, duration)).collect(),
+        parallel: false,
+    })
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+fn median(sorted: &[u64]) -> Option<u64> {
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(sorted[n / 2]),
+        n => Some((sorted[n / 2 - 1] + sorted[n / 2]) / 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_session(path: &Path, agent_name: &str, start: &str, end: Option<&str>) {
+        let session = AgentSession {
+            agent_name: agent_name.to_string(),
+            start_time: start.to_string(),
+            context: None,
+            end_time: end.map(str::to_string),
+            output_path: None,
+        };
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, serde_json::to_string(&session).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_usage_is_aggregated_across_session_kinds() {
+        let temp_dir = TempDir::new().unwrap();
+        let agents = temp_dir.path();
+        write_session(&agents.join("Athena/sessions/1.json"), "Athena", "2024-05-01T10:00:00Z", Some("2024-05-01T10:10:00Z"));
+        write_session(&agents.join("Athena/sessions/2.json"), "Athena", "2024-05-03T10:00:00Z", Some("2024-05-03T10:30:00Z"));
+        write_session(&agents.join("team_session.json"), "MultiAgent[Athena,Tester]", "2024-05-02T10:00:00Z", None);
+
+        let stats = collect_usage(agents, None).unwrap();
+        assert_eq!(stats.sessions, 3);
+
+        let athena = &stats.agents[0];
+        assert_eq!(athena.name, "Athena");
+        assert_eq!(athena.launches, 3);
+        assert_eq!(athena.total_duration_secs, 40 * 60);
+        assert_eq!(athena.median_duration_secs, Some(20 * 60));
+        assert_eq!(athena.co_loaded, vec![("Tester".to_string(), 1)]);
+        assert_eq!(athena.last_used, parse_timestamp("2024-05-03T10:00:00Z"));
+
+        let since = collect_usage(agents, parse_since("2024-05-02").ok()).unwrap();
+        assert_eq!(since.sessions, 2);
+
+        assert_eq!(sync_usage_metadata(agents, &stats).unwrap(), 1);
+        let metadata: AgentMetadata = serde_json::from_str(&fs::read_to_string(agents.join("Athena/metadata.json")).unwrap()).unwrap();
+        assert_eq!(metadata.usage_count, 3);
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert!(parse_since("7d").unwrap() < Utc::now() - Duration::days(6));
+        assert_eq!(parse_since("2024-01-02").unwrap().to_rfc3339(), "2024-01-02T00:00:00+00:00");
+        assert!(parse_since("soon").is_err());
+        assert!(parse_since("999999999999d").is_err());
+        assert!(parse_since("99999999999999999999w").is_err());
+    }
+}
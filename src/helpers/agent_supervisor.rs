@@ -0,0 +1,501 @@
+//! Supervisor for parallel agent sessions
+//!
+//! Keeps the child processes launched by `ci load --parallel`, records live
+//! per-instance state in the session coordination directory and optionally
+//! waits for every instance to finish, restarting failed instances on request.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus};
+use std::time::{Duration, Instant};
+
+use crate::helpers::git::git_value;
+
+/// File name of the supervisor state inside a coordination directory
+pub const SUPERVISOR_STATE_FILE: &str = "supervisor_state.json";
+
+/// Everything needed to (re)build the process for one agent instance
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchSpec {
+    /// Program to execute
+    pub program: String,
+
+    /// Arguments passed to the program
+    pub args: Vec<String>,
+
+    /// Extra environment variables
+    pub envs: Vec<(String, String)>,
+
+    /// Working directory (inherits the current one if unset)
+    pub cwd: Option<PathBuf>,
+
+    /// File piped into the process' stdin (inherits stdin if unset)
+    #[serde(default)]
+    pub stdin_file: Option<PathBuf>,
+}
+
+impl LaunchSpec {
+    pub fn new(program: &str) -> Self {
+        Self {
+            program: program.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Build a `std::process::Command` from this spec
+    pub fn to_command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args);
+        for (key, value) in &self.envs {
+            cmd.env(key, value);
+        }
+        if let Some(cwd) = &self.cwd {
+            cmd.current_dir(cwd);
+        }
+        cmd
+    }
+
+    /// Build the command with stdin attached to `stdin_file` when set
+    pub fn to_command_with_stdin(&self) -> std::io::Result<Command> {
+        let mut cmd = self.to_command();
+        if let Some(stdin_file) = &self.stdin_file {
+            cmd.stdin(fs::File::open(stdin_file)?);
+        }
+        Ok(cmd)
+    }
+
+    /// Spawn the process described by this spec
+    pub fn spawn(&self) -> std::io::Result<Child> {
+        self.to_command_with_stdin()?.spawn()
+    }
+
+    /// Run the process in the foreground and wait for it to exit
+    pub fn status(&self) -> std::io::Result<ExitStatus> {
+        self.to_command_with_stdin()?.status()
+    }
+}
+
+/// Lifecycle state of a supervised agent instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceStatus {
+    /// Process is running
+    Running,
+
+    /// Process exited successfully
+    Exited,
+
+    /// Process exited with a non-zero status or could not be started
+    Failed,
+
+    /// Process was killed because the overall timeout elapsed
+    TimedOut,
+
+    /// Process is no longer alive but its exit code was not observed
+    Unknown,
+}
+
+impl InstanceStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InstanceStatus::Running => "running",
+            InstanceStatus::Exited => "exited",
+            InstanceStatus::Failed => "failed",
+            InstanceStatus::TimedOut => "timed out",
+            InstanceStatus::Unknown => "unknown",
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, InstanceStatus::Running)
+    }
+}
+
+/// Persisted state of a single agent instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceState {
+    /// Display name, e.g. "Documentor (Instance-2)"
+    pub display_name: String,
+
+    /// Underlying agent name
+    pub agent_name: String,
+
+    /// File-safe instance key, unique within the session
+    pub instance_key: String,
+
+    /// Current status
+    pub status: InstanceStatus,
+
+    /// Process id of the most recent launch
+    pub pid: Option<u32>,
+
+    /// Exit code of the most recent launch
+    pub exit_code: Option<i32>,
+
+    /// Start time of the most recent launch
+    pub started_at: DateTime<Utc>,
+
+    /// End time of the most recent launch
+    pub ended_at: Option<DateTime<Utc>>,
+
+    /// Duration of the most recent launch in seconds
+    pub duration_secs: Option<u64>,
+
+    /// Number of times the instance was restarted
+    pub restarts: u32,
+
+    /// Last launch error, if the process could not be started
+    pub error: Option<String>,
+}
+
+/// Persisted state of a supervised parallel session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorState {
+    pub session_id: String,
+    pub task: String,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub instances: Vec<InstanceState>,
+
+    /// Repository the instances worked in, when launched inside one
+    #[serde(default)]
+    pub repo_root: Option<PathBuf>,
+
+    /// HEAD of that repository when the session started
+    #[serde(default)]
+    pub base_commit: Option<String>,
+}
+
+impl SupervisorState {
+    /// Load the supervisor state stored in a coordination directory
+    pub fn load(coordination_dir: &Path) -> Result<Self> {
+        let path = coordination_dir.join(SUPERVISOR_STATE_FILE);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read supervisor state: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse supervisor state: {}", path.display()))
+    }
+
+    /// Write the state to the coordination directory
+    pub fn save(&mut self, coordination_dir: &Path) -> Result<()> {
+        self.updated_at = Utc::now();
+        let path = coordination_dir.join(SUPERVISOR_STATE_FILE);
+        let content = serde_json::to_string_pretty(self)
+            .context("Failed to serialize supervisor state")?;
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write supervisor state: {}", path.display()))
+    }
+
+    /// Mark running instances whose process has disappeared as `Unknown`.
+    /// Used when viewing the state of a session whose supervisor is gone.
+    pub fn refresh_liveness(&mut self) -> bool {
+        let mut changed = false;
+        for instance in &mut self.instances {
+            if instance.status == InstanceStatus::Running {
+                let alive = instance.pid.map(is_process_alive).unwrap_or(false);
+                if !alive {
+                    instance.status = InstanceStatus::Unknown;
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Count instances with the given status
+    pub fn count(&self, status: InstanceStatus) -> usize {
+        self.instances.iter().filter(|i| i.status == status).count()
+    }
+}
+
+/// Restart and timeout behaviour for `AgentSupervisor::wait`
+#[derive(Debug, Clone, Default)]
+pub struct WaitOptions {
+    /// Kill remaining instances after this long
+    pub timeout: Option<Duration>,
+
+    /// Relaunch instances that exit with a non-zero status
+    pub restart_on_failure: bool,
+
+    /// Maximum restarts per instance
+    pub max_restarts: u32,
+}
+
+/// Supervises the child processes of a parallel session
+pub struct AgentSupervisor {
+    coordination_dir: PathBuf,
+    state: SupervisorState,
+    children: HashMap<String, Child>,
+    specs: HashMap<String, LaunchSpec>,
+}
+
+impl AgentSupervisor {
+    pub fn new(session_id: &str, task: &str, coordination_dir: &Path) -> Self {
+        let now = Utc::now();
+        Self {
+            coordination_dir: coordination_dir.to_path_buf(),
+            state: SupervisorState {
+                session_id: session_id.to_string(),
+                task: task.to_string(),
+                started_at: now,
+                updated_at: now,
+                instances: Vec::new(),
+                repo_root: None,
+                base_commit: None,
+            },
+            children: HashMap::new(),
+            specs: HashMap::new(),
+        }
+    }
+
+    /// Remember the repository containing `dir` and its HEAD as the session's starting point
+    pub fn record_git_base(&mut self, dir: &Path) {
+        self.state.repo_root = git_value(dir, &["rev-parse", "--show-toplevel"]).map(PathBuf::from);
+        self.state.base_commit = git_value(dir, &["rev-parse", "HEAD"]);
+    }
+
+    pub fn state(&self) -> &SupervisorState {
+        &self.state
+    }
+
+    /// Number of instances currently running
+    pub fn running_count(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Spawn an agent instance and record its state.
+    /// A failed spawn is recorded as `Failed` and returned as an error.
+    pub fn spawn(&mut self, display_name: &str, agent_name: &str, instance_key: &str, spec: LaunchSpec) -> Result<u32> {
+        let result = spec.spawn();
+        let now = Utc::now();
+
+        let (status, pid, error) = match &result {
+            Ok(child) => (InstanceStatus::Running, Some(child.id()), None),
+            Err(e) => (InstanceStatus::Failed, None, Some(e.to_string())),
+        };
+
+        self.state.instances.push(InstanceState {
+            display_name: display_name.to_string(),
+            agent_name: agent_name.to_string(),
+            instance_key: instance_key.to_string(),
+            status,
+            pid,
+            exit_code: None,
+            started_at: now,
+            ended_at: if error.is_some() { Some(now) } else { None },
+            duration_secs: None,
+            restarts: 0,
+            error,
+        });
+        self.specs.insert(instance_key.to_string(), spec);
+        self.state.save(&self.coordination_dir)?;
+
+        let child = result.with_context(|| format!("Failed to launch {}", display_name))?;
+        let pid = child.id();
+        self.children.insert(instance_key.to_string(), child);
+        Ok(pid)
+    }
+
+    /// Check every running child once, updating and persisting state.
+    /// Returns the keys of instances that finished during this poll.
+    pub fn poll(&mut self) -> Result<Vec<String>> {
+        let mut finished = Vec::new();
+
+        for (key, child) in self.children.iter_mut() {
+            if let Some(exit_status) = child.try_wait()? {
+                if let Some(instance) = self.state.instances.iter_mut().find(|i| &i.instance_key == key) {
+                    let now = Utc::now();
+                    instance.exit_code = exit_status.code();
+                    instance.status = if exit_status.success() {
+                        InstanceStatus::Exited
+                    } else {
+                        InstanceStatus::Failed
+                    };
+                    instance.ended_at = Some(now);
+                    instance.duration_secs = Some((now - instance.started_at).num_seconds().max(0) as u64);
+                }
+                finished.push(key.clone());
+            }
+        }
+
+        for key in &finished {
+            self.children.remove(key);
+        }
+
+        if !finished.is_empty() {
+            self.state.save(&self.coordination_dir)?;
+        }
+
+        Ok(finished)
+    }
+
+    /// Relaunch a finished instance from its stored launch spec
+    fn restart(&mut self, key: &str) -> Result<()> {
+        let spec = self.specs.get(key)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No launch spec recorded for {}", key))?;
+
+        let instance = self.state.instances.iter_mut()
+            .find(|i| i.instance_key == key)
+            .ok_or_else(|| anyhow::anyhow!("Unknown instance: {}", key))?;
+
+        instance.restarts += 1;
+        instance.started_at = Utc::now();
+        instance.ended_at = None;
+        instance.duration_secs = None;
+        instance.exit_code = None;
+
+        match spec.spawn() {
+            Ok(child) => {
+                instance.status = InstanceStatus::Running;
+                instance.pid = Some(child.id());
+                instance.error = None;
+                self.children.insert(key.to_string(), child);
+            }
+            Err(e) => {
+                instance.status = InstanceStatus::Failed;
+                instance.pid = None;
+                instance.ended_at = Some(Utc::now());
+                instance.error = Some(e.to_string());
+            }
+        }
+
+        self.state.save(&self.coordination_dir)
+    }
+
+    /// Wait until all instances have finished, honouring restart and timeout options.
+    /// `on_finish` is called for every instance that finishes (including ones about to be restarted).
+    pub async fn wait<F>(&mut self, options: &WaitOptions, mut on_finish: F) -> Result<&SupervisorState>
+    where
+        F: FnMut(&InstanceState),
+    {
+        let started = Instant::now();
+
+        while !self.children.is_empty() {
+            for key in self.poll()? {
+                let instance = match self.state.instances.iter().find(|i| i.instance_key == key) {
+                    Some(instance) => instance.clone(),
+                    None => continue,
+                };
+                on_finish(&instance);
+
+                if options.restart_on_failure
+                    && instance.status == InstanceStatus::Failed
+                    && instance.restarts < options.max_restarts
+                {
+                    self.restart(&key)?;
+                }
+            }
+
+            if let Some(timeout) = options.timeout {
+                if started.elapsed() >= timeout {
+                    self.kill_all(InstanceStatus::TimedOut)?;
+                    break;
+                }
+            }
+
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+
+        Ok(&self.state)
+    }
+
+    /// Kill every running child and mark it with the given status
+    pub fn kill_all(&mut self, status: InstanceStatus) -> Result<()> {
+        let now = Utc::now();
+
+        for (key, mut child) in self.children.drain() {
+            let _ = child.kill();
+            let exit_status = child.wait().ok();
+
+            if let Some(instance) = self.state.instances.iter_mut().find(|i| i.instance_key == key) {
+                instance.status = status;
+                instance.exit_code = exit_status.and_then(|s| s.code());
+                instance.ended_at = Some(now);
+                instance.duration_secs = Some((now - instance.started_at).num_seconds().max(0) as u64);
+            }
+        }
+
+        self.state.save(&self.coordination_dir)
+    }
+}
+
+/// Check whether a process with the given pid is still alive
+pub fn is_process_alive(pid: u32) -> bool {
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Format a duration in seconds as a short human-readable string
+pub fn format_duration_secs(secs: u64) -> String {
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn shell(script: &str) -> LaunchSpec {
+        let mut spec = LaunchSpec::new("sh");
+        spec.args = vec!["-c".to_string(), script.to_string()];
+        spec
+    }
+
+    #[tokio::test]
+    async fn test_wait_collects_exit_codes() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut supervisor = AgentSupervisor::new("s1", "task", temp_dir.path());
+
+        supervisor.spawn("Ok", "Ok", "Ok_1", shell("exit 0")).unwrap();
+        supervisor.spawn("Bad", "Bad", "Bad_1", shell("exit 3")).unwrap();
+
+        let state = supervisor.wait(&WaitOptions::default(), |_| {}).await.unwrap();
+        assert_eq!(state.count(InstanceStatus::Exited), 1);
+        assert_eq!(state.count(InstanceStatus::Failed), 1);
+
+        let bad = state.instances.iter().find(|i| i.instance_key == "Bad_1").unwrap();
+        assert_eq!(bad.exit_code, Some(3));
+
+        let stored = SupervisorState::load(temp_dir.path()).unwrap();
+        assert_eq!(stored.instances.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_restart_on_failure_and_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut supervisor = AgentSupervisor::new("s2", "task", temp_dir.path());
+
+        supervisor.spawn("Flaky", "Flaky", "Flaky_1", shell("exit 1")).unwrap();
+        supervisor.spawn("Slow", "Slow", "Slow_1", shell("sleep 30")).unwrap();
+
+        let options = WaitOptions {
+            timeout: Some(Duration::from_secs(2)),
+            restart_on_failure: true,
+            max_restarts: 2,
+        };
+        let state = supervisor.wait(&options, |_| {}).await.unwrap();
+
+        let flaky = state.instances.iter().find(|i| i.instance_key == "Flaky_1").unwrap();
+        assert_eq!(flaky.restarts, 2);
+        assert_eq!(flaky.status, InstanceStatus::Failed);
+
+        let slow = state.instances.iter().find(|i| i.instance_key == "Slow_1").unwrap();
+        assert_eq!(slow.status, InstanceStatus::TimedOut);
+    }
+}
@@ -0,0 +1,619 @@
+//! Agent template registry
+//!
+//! Templates scaffold the files of a new agent. Built-in templates ship with
+//! CI; user templates live in `~/.config/ci/agent-templates/<name>/` (or
+//! `$CI_AGENT_TEMPLATES_PATH`) and override built-ins of the same name. A user
+//! template is a directory holding a `template.toml` plus the files to render:
+//!
+//! ```toml
+//! description = "Domain researcher"
+//!
+//! [[variables]]
+//! name = "domain"
+//! description = "Research domain"
+//!
+//! [[variables]]
+//! name = "with_sources"
+//! default = "yes"
+//! required = false
+//! ```
+//!
+//! File names and contents may use `{{variable}}` placeholders and
+//! `{{#if variable}}...{{else}}...{{/if}}` / `{{#unless variable}}...{{/unless}}`
+//! sections. `agent_name`, `date` and `datetime` are always defined.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Manifest file of a user template directory
+pub const TEMPLATE_MANIFEST: &str = "template.toml";
+
+/// Template used when `ci agent create` is given no `--template`
+pub const DEFAULT_TEMPLATE: &str = "basic";
+
+/// Variables every template can use without declaring them
+const BUILTIN_VARIABLES: &[&str] = &["agent_name", "date", "datetime"];
+
+/// A variable a template expects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    #[serde(default)]
+    pub default: Option<String>,
+
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TemplateManifest {
+    #[serde(default)]
+    description: String,
+
+    #[serde(default)]
+    variables: Vec<TemplateVariable>,
+}
+
+/// Where a template was found
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateSource {
+    BuiltIn,
+    User(PathBuf),
+}
+
+/// An agent template: variables plus files to render
+#[derive(Debug, Clone)]
+pub struct AgentTemplate {
+    pub name: String,
+    pub description: String,
+    pub source: TemplateSource,
+    pub variables: Vec<TemplateVariable>,
+    /// (relative path, content), both may contain placeholders
+    pub files: Vec<(String, String)>,
+}
+
+impl AgentTemplate {
+    /// Load a user template directory
+    pub fn load_dir(dir: &Path) -> Result<Self> {
+        let name = dir.file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| anyhow!("Invalid template directory: {}", dir.display()))?
+            .to_string();
+
+        let manifest_path = dir.join(TEMPLATE_MANIFEST);
+        let manifest: TemplateManifest = match fs::read_to_string(&manifest_path) {
+            Ok(content) => toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", manifest_path.display()))?,
+            Err(_) => TemplateManifest::default(),
+        };
+
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            for entry in fs::read_dir(&current)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path != manifest_path {
+                    let relative = path.strip_prefix(dir)?.to_string_lossy().replace('\\', "/");
+                    let content = fs::read_to_string(&path)
+                        .with_context(|| format!("Template files must be UTF-8 text: {}", path.display()))?;
+                    files.push((relative, content));
+                }
+            }
+        }
+        files.sort();
+
+        Ok(AgentTemplate {
+            name,
+            description: manifest.description,
+            source: TemplateSource::User(dir.to_path_buf()),
+            variables: manifest.variables,
+            files,
+        })
+    }
+
+    /// Declared variables not yet present in `vars`
+    pub fn missing_variables(&self, vars: &HashMap<String, String>) -> Vec<&TemplateVariable> {
+        self.variables.iter()
+            .filter(|variable| !vars.contains_key(&variable.name))
+            .collect()
+    }
+
+    /// Render every file for `agent_name`; empty rendered files are skipped
+    pub fn render(&self, agent_name: &str, vars: &HashMap<String, String>) -> Result<Vec<(PathBuf, String)>> {
+        let mut context = vars.clone();
+        let now = Utc::now();
+        context.insert("agent_name".to_string(), agent_name.to_string());
+        context.entry("date".to_string()).or_insert_with(|| now.format("%Y-%m-%d").to_string());
+        context.entry("datetime".to_string()).or_insert_with(|| now.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+        for variable in &self.variables {
+            if let Some(default) = &variable.default {
+                context.entry(variable.name.clone()).or_insert_with(|| default.clone());
+            }
+        }
+
+        let mut rendered = Vec::new();
+        for (path, content) in &self.files {
+            let path = render(path, &context).with_context(|| format!("In file name {}", path))?;
+            let content = render(content, &context).with_context(|| format!("In {}", path))?;
+            if path.trim().is_empty() || path.contains("..") || Path::new(&path).is_absolute() {
+                return Err(anyhow!("Template '{}' renders an invalid file path '{}'", self.name, path));
+            }
+            if !content.trim().is_empty() {
+                rendered.push((PathBuf::from(path), content));
+            }
+        }
+
+        Ok(rendered)
+    }
+
+    /// Write the unrendered template into `dir` so it can be edited as a user template
+    pub fn write_to(&self, dir: &Path, description: &str) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        let manifest = TemplateManifest {
+            description: description.to_string(),
+            variables: self.variables.clone(),
+        };
+        fs::write(dir.join(TEMPLATE_MANIFEST), toml::to_string_pretty(&manifest)?)?;
+
+        for (path, content) in &self.files {
+            let target = dir.join(path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, content)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Directory holding user templates
+pub fn user_templates_dir() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("CI_AGENT_TEMPLATES_PATH") {
+        return Some(PathBuf::from(path));
+    }
+    dirs::config_dir().map(|dir| dir.join("ci").join("agent-templates"))
+}
+
+/// All available templates, user templates replacing built-ins of the same name
+pub fn list_templates() -> Result<Vec<AgentTemplate>> {
+    list_templates_in(user_templates_dir().as_deref())
+}
+
+/// Built-in templates, replaced or extended by those under `user_dir`
+pub fn list_templates_in(user_dir: Option<&Path>) -> Result<Vec<AgentTemplate>> {
+    let mut templates = builtin_templates();
+
+    if let Some(dir) = user_dir.filter(|dir| dir.is_dir()) {
+        for entry in fs::read_dir(dir)?.flatten() {
+            if !entry.path().is_dir() {
+                continue;
+            }
+            let template = AgentTemplate::load_dir(&entry.path())?;
+            templates.retain(|t| t.name != template.name);
+            templates.push(template);
+        }
+    }
+
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// Look up a template by name
+pub fn find_template(name: &str) -> Result<AgentTemplate> {
+    find_template_in(name, user_templates_dir().as_deref())
+}
+
+/// Look up a template by name among the built-ins and those under `user_dir`
+pub fn find_template_in(name: &str, user_dir: Option<&Path>) -> Result<AgentTemplate> {
+    list_templates_in(user_dir)?
+        .into_iter()
+        .find(|t| t.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| anyhow!("Template '{}' not found. Use 'ci agent template list' to see available templates.", name))
+}
+
+/// Parse a `key=value` command line variable
+pub fn parse_var(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, val)) if !key.trim().is_empty() => Ok((key.trim().to_string(), val.to_string())),
+        _ => Err(format!("Expected key=value, got '{}'", value)),
+    }
+}
+
+/// Read variables from a TOML file of `key = value` pairs
+pub fn load_vars_file(path: &Path) -> Result<HashMap<String, String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read vars file {}", path.display()))?;
+    let table: toml::Table = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse vars file {}", path.display()))?;
+
+    Ok(table.into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                toml::Value::String(s) => s,
+                other => other.to_string(),
+            };
+            (key, value)
+        })
+        .collect())
+}
+
+/// Whether a variable counts as true in `{{#if}}`
+fn is_truthy(value: Option<&String>) -> bool {
+    value.is_some_and(|v| {
+        let v = v.trim().to_lowercase();
+        !v.is_empty() && v != "false" && v != "no" && v != "0"
+    })
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Text(String),
+    Var(String),
+    If(String, bool),
+    Else,
+    End,
+}
+
+/// Render template text with variables and conditional sections
+pub fn render(text: &str, vars: &HashMap<String, String>) -> Result<String> {
+    let tokens = tokenize(text)?;
+    let mut position = 0;
+    let output = render_tokens(&tokens, &mut position, vars)?;
+    if position < tokens.len() {
+        return Err(anyhow!("Unexpected {{{{else}}}} or closing tag"));
+    }
+    Ok(output)
+}
+
+fn render_tokens(tokens: &[Token], position: &mut usize, vars: &HashMap<String, String>) -> Result<String> {
+    let mut output = String::new();
+
+    while *position < tokens.len() {
+        match &tokens[*position] {
+            Token::Text(text) => output.push_str(text),
+            Token::Var(name) => {
+                let value = vars.get(name)
+                    .ok_or_else(|| anyhow!("Undefined template variable '{}'", name))?;
+                output.push_str(value);
+            }
+            Token::If(name, negate) => {
+                *position += 1;
+                let condition = is_truthy(vars.get(name)) != *negate;
+                let then_branch = render_tokens(tokens, position, vars)?;
+                let else_branch = if tokens.get(*position) == Some(&Token::Else) {
+                    *position += 1;
+                    render_tokens(tokens, position, vars)?
+                } else {
+                    String::new()
+                };
+                if tokens.get(*position) != Some(&Token::End) {
+                    return Err(anyhow!("Unclosed {{{{#if {}}}}}", name));
+                }
+                output.push_str(if condition { &then_branch } else { &else_branch });
+            }
+            Token::Else | Token::End => return Ok(output),
+        }
+        *position += 1;
+    }
+
+    Ok(output)
+}
+
+/// Split text into tokens. Block tags standing alone on a line take the whole line with them.
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..].find("}}")
+            .map(|e| start + e)
+            .ok_or_else(|| anyhow!("Unclosed '{{{{' in template"))?;
+        let tag = rest[start + 2..end].trim();
+
+        let token = if let Some(name) = tag.strip_prefix("#if ") {
+            Token::If(name.trim().to_string(), false)
+        } else if let Some(name) = tag.strip_prefix("#unless ") {
+            Token::If(name.trim().to_string(), true)
+        } else if tag == "else" {
+            Token::Else
+        } else if tag == "/if" || tag == "/unless" {
+            Token::End
+        } else if !tag.is_empty() && tag.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+            Token::Var(tag.to_string())
+        } else {
+            return Err(anyhow!("Invalid template tag '{{{{{}}}}}'", tag));
+        };
+
+        let mut before = &rest[..start];
+        let mut after = &rest[end + 2..];
+        if !matches!(token, Token::Var(_)) {
+            let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let standalone = before[line_start..].trim().is_empty()
+                && (after.starts_with('\n') || after.starts_with("\r\n") || after.is_empty());
+            if standalone {
+                before = &before[..line_start];
+                after = after.strip_prefix("\r\n").or_else(|| after.strip_prefix('\n')).unwrap_or(after);
+            }
+        }
+
+        if !before.is_empty() {
+            tokens.push(Token::Text(before.to_string()));
+        }
+        tokens.push(token);
+        rest = after;
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+
+    Ok(tokens)
+}
+
+/// Variables a template uses that it neither declares nor gets for free
+pub fn undeclared_variables(template: &AgentTemplate) -> Vec<String> {
+    let mut undeclared = Vec::new();
+    for (path, content) in &template.files {
+        for token in tokenize(path).into_iter().chain(tokenize(content)).flatten() {
+            let name = match token {
+                Token::Var(name) | Token::If(name, _) => name,
+                _ => continue,
+            };
+            let declared = BUILTIN_VARIABLES.contains(&name.as_str())
+                || template.variables.iter().any(|v| v.name == name);
+            if !declared && !undeclared.contains(&name) {
+                undeclared.push(name);
+            }
+        }
+    }
+    undeclared
+}
+
+fn builtin(name: &str, description: &str, variables: Vec<TemplateVariable>, files: &[(&str, &str)]) -> AgentTemplate {
+    AgentTemplate {
+        name: name.to_string(),
+        description: description.to_string(),
+        source: TemplateSource::BuiltIn,
+        variables,
+        files: files.iter().map(|(p, c)| (p.to_string(), c.to_string())).collect(),
+    }
+}
+
+fn variable(name: &str, description: &str, default: Option<&str>) -> TemplateVariable {
+    TemplateVariable {
+        name: name.to_string(),
+        description: description.to_string(),
+        default: default.map(str::to_string),
+        required: default.is_none(),
+    }
+}
+
+fn builtin_templates() -> Vec<AgentTemplate> {
+    vec![
+        builtin("basic", "General-purpose agent with README, memory and learning files", vec![], &[
+            ("README.md", BASIC_README),
+            ("MEMORY.md", BASIC_MEMORY),
+            ("ContinuousLearning.md", LEARNING),
+        ]),
+        builtin("researcher", "Domain researcher that gathers, weighs and cites sources", vec![
+            variable("domain", "Research domain (e.g. payments)", None),
+            variable("focus", "Specific questions or focus areas", Some("")),
+            variable("with_sources", "Keep a source log (yes/no)", Some("yes")),
+        ], &[
+            ("agent.toml", RESEARCHER_MANIFEST),
+            ("README.md", RESEARCHER_README),
+            ("{{agent_name}}.md", RESEARCHER_MEMORY),
+            ("ContinuousLearning.md", LEARNING),
+            ("Sources.md", RESEARCHER_SOURCES),
+        ]),
+        builtin("reviewer", "Code reviewer for a language or codebase", vec![
+            variable("language", "Primary language reviewed (e.g. Rust)", None),
+            variable("strict", "Block on style issues (yes/no)", Some("no")),
+        ], &[
+            ("agent.toml", REVIEWER_MANIFEST),
+            ("README.md", REVIEWER_README),
+            ("{{agent_name}}.md", REVIEWER_MEMORY),
+            ("ContinuousLearning.md", LEARNING),
+        ]),
+    ]
+}
+
+const BASIC_README: &str = r#"# {{agent_name}}
+
+This agent is part of the Collaborative Intelligence system.
+
+## Capabilities
+
+- Add agent capabilities here
+
+## Usage
+
+To use this agent, type `{{agent_name}}` in a Claude Code session.
+
+## Sessions
+
+Session records are stored in the Sessions directory.
+"#;
+
+const BASIC_MEMORY: &str = r#"# {{agent_name}} Memory
+
+This file stores the long-term memory for the {{agent_name}} agent.
+
+## Core Knowledge
+
+- Agent Name: {{agent_name}}
+- Creation Date: {{datetime}}
+- Primary Function: [Define primary function]
+
+## System Integration
+
+The {{agent_name}} agent is part of the Collaborative Intelligence ecosystem and follows the standard agent communication protocols.
+
+## Expertise
+
+[Define areas of expertise]
+"#;
+
+const LEARNING: &str = r#"# {{agent_name}} Continuous Learning
+
+This file documents the learning progress of the {{agent_name}} agent.
+
+## Learning Record
+
+### {{date}}
+
+- Agent created
+- Initial memory structure established
+- Basic capabilities defined
+"#;
+
+const RESEARCHER_MANIFEST: &str = r#"name = "{{agent_name}}"
+description = "Researcher for {{domain}}"
+version = "0.1.0"
+capabilities = ["research", "analysis", "writing"]
+learning_path = "ContinuousLearning.md"
+"#;
+
+const RESEARCHER_README: &str = r#"# {{agent_name}}
+
+Research specialist for {{domain}}.
+
+## Usage
+
+`ci load {{agent_name}} -t "Research <question>"`
+"#;
+
+const RESEARCHER_MEMORY: &str = r#"# {{agent_name}}
+
+## Identity
+
+I am {{agent_name}}, a research specialist for {{domain}}. I gather evidence, weigh it and report findings with their confidence.
+
+{{#if focus}}
+## Focus Areas
+
+{{focus}}
+
+{{/if}}
+## Research Protocol
+
+1. Restate the question and what a good answer looks like
+2. Collect evidence from code, documentation and prior findings
+3. Separate facts from inferences and state confidence
+{{#if with_sources}}
+4. Record every source in Sources.md
+{{/if}}
+
+## Domain Knowledge: {{domain}}
+
+[Accumulate key facts about {{domain}} here]
+"#;
+
+const RESEARCHER_SOURCES: &str = r#"{{#if with_sources}}
+# {{agent_name}} Sources
+
+| Date | Source | Notes |
+|------|--------|-------|
+{{/if}}
+"#;
+
+const REVIEWER_MANIFEST: &str = r#"name = "{{agent_name}}"
+description = "{{language}} code reviewer"
+version = "0.1.0"
+capabilities = ["code-review", "testing", "refactoring"]
+learning_path = "ContinuousLearning.md"
+"#;
+
+const REVIEWER_README: &str = r#"# {{agent_name}}
+
+Reviews {{language}} changes for correctness, clarity and maintainability.
+"#;
+
+const REVIEWER_MEMORY: &str = r#"# {{agent_name}}
+
+## Identity
+
+I am {{agent_name}}, a {{language}} code reviewer.
+
+## Review Checklist
+
+- Correctness and edge cases
+- Error handling and failure modes
+- Tests covering the change
+- Idiomatic {{language}}
+{{#if strict}}
+- Style and formatting issues block approval
+{{else}}
+- Style comments are suggestions, not blockers
+{{/if}}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_render_variables_and_conditionals() {
+        let text = "# {{name}}\n{{#if focus}}\nFocus: {{focus}}\n{{else}}\nNo focus\n{{/if}}\n{{#unless quiet}}loud{{/unless}}\n";
+        assert_eq!(
+            render(text, &vars(&[("name", "Ada"), ("focus", "payments")])).unwrap(),
+            "# Ada\nFocus: payments\nloud\n"
+        );
+        assert_eq!(
+            render(text, &vars(&[("name", "Ada"), ("focus", ""), ("quiet", "yes")])).unwrap(),
+            "# Ada\nNo focus\n\n"
+        );
+        assert!(render("{{missing}}", &HashMap::new()).is_err());
+        assert!(render("{{#if a}}open", &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_builtin_researcher_renders_files() {
+        let template = find_template_in("researcher", None).unwrap();
+        assert_eq!(template.missing_variables(&HashMap::new()).len(), 3);
+        assert!(undeclared_variables(&template).is_empty());
+
+        let files = template.render("Scout", &vars(&[("domain", "payments"), ("with_sources", "no")])).unwrap();
+        let names: Vec<String> = files.iter().map(|(p, _)| p.display().to_string()).collect();
+        assert!(names.contains(&"Scout.md".to_string()));
+        assert!(!names.contains(&"Sources.md".to_string()));
+
+        let memory = &files.iter().find(|(p, _)| p == Path::new("Scout.md")).unwrap().1;
+        assert!(memory.contains("research specialist for payments"));
+        assert!(!memory.contains("Focus Areas"));
+    }
+
+    #[test]
+    fn test_user_template_roundtrip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path().join("reviewer");
+        find_template_in("reviewer", None).unwrap().write_to(&dir, "Custom reviewer").unwrap();
+
+        // A user template replaces the built-in of the same name
+        let template = find_template_in("reviewer", Some(temp_dir.path())).unwrap();
+        assert_eq!(list_templates_in(Some(temp_dir.path())).unwrap().len(), list_templates_in(None).unwrap().len());
+        assert_eq!(template.description, "Custom reviewer");
+        assert_eq!(template.variables.len(), 2);
+        assert_eq!(template.files.len(), 4);
+        assert!(template.render("Rex", &vars(&[("language", "Rust")])).is_ok());
+    }
+}
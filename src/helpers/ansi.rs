@@ -0,0 +1,53 @@
+//! ANSI escape sequence handling shared by commands that measure or store terminal output
+
+/// Strip ANSI escape sequences, for accurate length calculation and plain-text
+/// transcripts: CSI (`ESC [ ... final`, colours as well as cursor and private
+/// modes), OSC (`ESC ] ... BEL` or `ESC ] ... ESC \`) and two-character escapes
+pub fn strip_ansi_codes(input: &str) -> String {
+    let mut result = String::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            result.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        break;
+                    }
+                }
+            },
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi_codes_removes_colours() {
+        assert_eq!(strip_ansi_codes("\x1b[1;32mok\x1b[0m"), "ok");
+        assert_eq!(strip_ansi_codes("plain"), "plain");
+    }
+
+    #[test]
+    fn test_strip_ansi_codes_handles_cursor_and_osc() {
+        assert_eq!(strip_ansi_codes("\x1b[?25lhidden\x1b[2Kline"), "hiddenline");
+        assert_eq!(strip_ansi_codes("\x1b]0;CI: load\x07title"), "title");
+        assert_eq!(strip_ansi_codes("\x1b]0;t\x1b\\bye\x1b=!"), "bye!");
+    }
+}
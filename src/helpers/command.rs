@@ -337,6 +337,13 @@ impl CommandHelpers {
         env::var("CI_DEBUG").unwrap_or_default() == "true"
     }
     
+    /// Whether window title escapes may be written to stdout. Redirected or
+    /// piped stdout never gets them unless forced: they would corrupt files
+    /// and other programs' input, such as `ci idea rank --format json > out.json`.
+    pub fn window_title_allowed(stdout_is_terminal: bool, forced: bool) -> bool {
+        stdout_is_terminal || forced
+    }
+    
     /// Set terminal window title with CI command info
     pub fn set_window_title(command: &str) {
         let is_tty_atty = atty::is(atty::Stream::Stdout);
@@ -360,10 +367,9 @@ impl CommandHelpers {
         }
         
         // Set title if:
-        // 1. We're in an interactive terminal OR
-        // 2. We have terminal environment variables OR  
-        // 3. It's forced via env var
-        if is_tty_atty || is_tty_is_terminal || has_term_env || in_known_terminal || force_title {
+        // 1. stdout is an interactive terminal OR
+        // 2. It's forced via env var
+        if Self::window_title_allowed(is_tty_atty || is_tty_is_terminal, force_title) {
             // OSC sequence to set window title: \x1b]0;title\x07
             print!("\x1b]0;CI: {}\x07", command);
             let _ = io::stdout().flush();
@@ -382,14 +388,8 @@ impl CommandHelpers {
         let is_tty_is_terminal = io::stdout().is_terminal();
         let force_title = env::var("CI_FORCE_WINDOW_TITLE").unwrap_or_default() == "true";
         
-        // Check for common terminal environments that support OSC sequences
-        let has_term_env = env::var("TERM").is_ok();
-        let in_known_terminal = env::var("TERM_PROGRAM").is_ok() || 
-                               env::var("ITERM_SESSION_ID").is_ok() ||
-                               env::var("TERMINAL_EMULATOR").is_ok();
-        
-        // Set title if terminal supports it
-        if is_tty_atty || is_tty_is_terminal || has_term_env || in_known_terminal || force_title {
+        // Set title if stdout is a terminal
+        if Self::window_title_allowed(is_tty_atty || is_tty_is_terminal, force_title) {
             // OSC sequence to set window title: \x1b]0;title\x07
             print!("\x1b]0;CI: {} - {}\x07", command, status);
             let _ = io::stdout().flush();
@@ -433,7 +433,7 @@ impl CommandHelpers {
         }
         
         // Restore title using same logic as set_window_title
-        if is_tty_atty || is_tty_is_terminal || has_term_env || in_known_terminal || force_title {
+        if Self::window_title_allowed(is_tty_atty || is_tty_is_terminal, force_title) {
             if claude_detected {
                 // Don't restore to blank when Claude Code is running - set a meaningful title instead
                 print!("\x1b]0;CI - Command Complete\x07");
@@ -530,4 +530,29 @@ mod tests {
         let formatted = CommandHelpers::format_file_list(&files);
         assert_eq!(formatted, "  • file1.rs\n  • file2.rs\n  • file3.rs");
     }
+    
+    #[test]
+    fn test_piped_output_has_no_title_escapes() {
+        // Run the ignored test below in a child process whose stdout is a pipe
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["helpers::command::tests::print_json_with_window_title", "--exact", "--ignored", "--nocapture", "--test-threads=1"])
+            .env("TERM", "xterm-256color")
+            .env_remove("CI_FORCE_WINDOW_TITLE")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        
+        let payload = stdout.lines().find(|line| line.contains("\"rank\"")).unwrap();
+        let json: serde_json::Value = serde_json::from_str(payload).unwrap();
+        assert_eq!(json["rank"], 1);
+        assert!(!stdout.contains("\x1b]"));
+    }
+    
+    #[test]
+    #[ignore = "run by test_piped_output_has_no_title_escapes"]
+    fn print_json_with_window_title() {
+        println!();
+        CommandHelpers::with_window_title("idea", || println!("{{\"rank\": 1}}"));
+        println!();
+    }
 }
\ No newline at end of file
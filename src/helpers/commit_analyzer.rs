@@ -51,21 +51,55 @@ pub struct CommitAnalyzer;
 impl CommitAnalyzer {
     /// Analyze staged changes and generate a detailed commit message
     pub async fn analyze_staged_changes(repo_path: &Path) -> Result<CommitAnalysis> {
-        // Get staged files list
+        let file_changes = Self::diff_file_changes(repo_path, &["--staged"])?;
+        if file_changes.is_empty() {
+            return Err(anyhow!("No staged changes found"));
+        }
+        
+        Self::analyze_file_changes(file_changes)
+    }
+    
+    /// Analyze everything that changed in the working tree since `base`:
+    /// commits, uncommitted edits and untracked files, skipping files under `excluded`
+    pub async fn analyze_changes_since(repo_path: &Path, base: &str, excluded: &[&Path]) -> Result<CommitAnalysis> {
+        let mut file_changes = Self::diff_file_changes(repo_path, &[base])?;
+        file_changes.extend(Self::untracked_file_changes(repo_path)?);
+        
+        let root = repo_path.canonicalize().unwrap_or_else(|_| repo_path.to_path_buf());
+        let excluded: Vec<_> = excluded.iter().filter_map(|dir| dir.canonicalize().ok()).collect();
+        file_changes.retain(|change| !excluded.iter().any(|dir| root.join(&change.file_path).starts_with(dir)));
+        
+        if file_changes.is_empty() {
+            return Err(anyhow!("No changes found since {}", base));
+        }
+        
+        Self::analyze_file_changes(file_changes)
+    }
+    
+    /// Analyze the changes between two revisions
+    pub async fn analyze_range(repo_path: &Path, from: &str, to: &str) -> Result<CommitAnalysis> {
+        let file_changes = Self::diff_file_changes(repo_path, &[from, to])?;
+        if file_changes.is_empty() {
+            return Err(anyhow!("No changes found between {} and {}", from, to));
+        }
+        
+        Self::analyze_file_changes(file_changes)
+    }
+    
+    /// Classify the files of `git diff <diff_args>`
+    fn diff_file_changes(repo_path: &Path, diff_args: &[&str]) -> Result<Vec<FileChange>> {
         let output = Command::new("git")
-            .args(["diff", "--name-status", "--staged"])
+            .args(["diff", "--name-status"])
+            .args(diff_args)
             .current_dir(repo_path)
             .output()
-            .with_context(|| "Failed to get staged files")?;
+            .with_context(|| "Failed to get changed files")?;
             
         if !output.status.success() {
-            return Err(anyhow!("Failed to get staged files"));
+            return Err(anyhow!("Failed to get changed files"));
         }
         
         let status_output = String::from_utf8_lossy(&output.stdout);
-        if status_output.trim().is_empty() {
-            return Err(anyhow!("No staged changes found"));
-        }
         
         // Parse file changes with status (A: added, M: modified, D: deleted, R: renamed)
         let mut file_changes = Vec::new();
@@ -91,12 +125,12 @@ impl CommitAnalyzer {
             
             let file_path = parts[1];
             
-            // Determine change type from status code
+            // Determine change type from status code (renames carry a similarity score, e.g. R087)
             let change_type = match status {
                 "A" => ChangeType::Addition,
                 "M" => ChangeType::Modification,
                 "D" => ChangeType::Removal,
-                "R" => ChangeType::Rename,
+                status if status.starts_with('R') => ChangeType::Rename,
                 _ => ChangeType::Modification, // default case
             };
             
@@ -109,7 +143,7 @@ impl CommitAnalyzer {
             
             // Get file stats (additions/deletions) for non-deleted files
             let (additions, deletions) = if change_type != ChangeType::Removal {
-                Self::get_file_diff_stats(repo_path, file_path)?
+                Self::get_file_diff_stats(repo_path, diff_args, file_path)?
             } else {
                 (0, 0) // Deleted files don't have additions/deletions in the staged diff
             };
@@ -130,6 +164,37 @@ impl CommitAnalyzer {
             });
         }
         
+        Ok(file_changes)
+    }
+    
+    /// Untracked, non-ignored files counted as all-new lines
+    fn untracked_file_changes(repo_path: &Path) -> Result<Vec<FileChange>> {
+        let output = Command::new("git")
+            .args(["ls-files", "--others", "--exclude-standard"])
+            .current_dir(repo_path)
+            .output()
+            .with_context(|| "Failed to list untracked files")?;
+            
+        if !output.status.success() {
+            return Err(anyhow!("Failed to list untracked files"));
+        }
+        
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|file_path| FileChange {
+                file_path: file_path.to_string(),
+                change_type: ChangeType::Untracked,
+                additions: std::fs::read_to_string(repo_path.join(file_path)).map(|c| c.lines().count()).unwrap_or(0),
+                deletions: 0,
+                language: Self::detect_language(file_path),
+                component: Self::detect_component(file_path),
+            })
+            .collect())
+    }
+    
+    /// Summarize classified file changes and suggest a commit message
+    fn analyze_file_changes(file_changes: Vec<FileChange>) -> Result<CommitAnalysis> {
         // Calculate totals and analyze
         let (languages, components, total_additions, total_deletions) = Self::calculate_totals(&file_changes);
         
@@ -209,9 +274,11 @@ impl CommitAnalyzer {
     }
     
     /// Get additions and deletions for a file
-    fn get_file_diff_stats(repo_path: &Path, file_path: &str) -> Result<(usize, usize)> {
+    fn get_file_diff_stats(repo_path: &Path, diff_args: &[&str], file_path: &str) -> Result<(usize, usize)> {
         let output = Command::new("git")
-            .args(["diff", "--staged", "--numstat", file_path])
+            .arg("diff")
+            .args(diff_args)
+            .args(["--numstat", "--", file_path])
             .current_dir(repo_path)
             .output()
             .with_context(|| format!("Failed to get stats for {}", file_path))?;
@@ -500,6 +567,7 @@ impl CommitAnalyzer {
         let modifications_count = files.iter().filter(|f| f.change_type == ChangeType::Modification).count();
         let removals_count = files.iter().filter(|f| f.change_type == ChangeType::Removal).count();
         let renames_count = files.iter().filter(|f| f.change_type == ChangeType::Rename).count();
+        let untracked_count = files.iter().filter(|f| f.change_type == ChangeType::Untracked).count();
         
         // Format summary
         if additions_count > 0 {
@@ -527,6 +595,13 @@ impl CommitAnalyzer {
             summary.push_str(&format!("{} file{} renamed", renames_count, if renames_count == 1 { "" } else { "s" }));
         }
         
+        if untracked_count > 0 {
+            if !summary.is_empty() {
+                summary.push_str(", ");
+            }
+            summary.push_str(&format!("{} untracked file{}", untracked_count, if untracked_count == 1 { "" } else { "s" }));
+        }
+        
         // Add stats summary
         if total_additions > 0 || total_deletions > 0 {
             summary.push_str(&format!(" with {} addition{} and {} deletion{}", 
@@ -0,0 +1,521 @@
+//! Structured coordination between parallel agent instances
+//!
+//! Every instance of a parallel session has an inbox and an outbox under the
+//! session's coordination directory:
+//!
+//! ```text
+//! AGENTS/parallel_session_<id>/
+//!   messages/
+//!     .lock                 held while any mailbox is written
+//!     <instance>/outbox.json  messages the instance sent
+//!     <instance>/inbox.json   messages addressed to it (or broadcast)
+//!   board.md                claims, progress and open blockers, regenerated on every send
+//! ```
+//!
+//! Messages are typed (claim, progress, finding, blocker, handoff, done) so the
+//! board can be derived from the outboxes alone.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::helpers::agent_supervisor::SupervisorState;
+
+/// Directory holding the per-instance mailboxes
+pub const MESSAGES_DIR: &str = "messages";
+
+/// Rendered board, kept next to `task_coordination.md`
+pub const BOARD_FILE: &str = "board.md";
+
+const LOCK_FILE: &str = ".lock";
+const INBOX_FILE: &str = "inbox.json";
+const OUTBOX_FILE: &str = "outbox.json";
+
+/// Sender name used for messages sent by people rather than instances
+pub const HUMAN_SENDER: &str = "human";
+
+/// Kind of coordination message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageKind {
+    /// Taking ownership of paths or a piece of the task
+    Claim,
+    Progress,
+    Finding,
+    /// Stuck; needs someone else to act
+    Blocker,
+    /// Passing claimed work to another instance
+    Handoff,
+    Done,
+}
+
+impl MessageKind {
+    pub const ALL: [MessageKind; 6] = [
+        MessageKind::Claim,
+        MessageKind::Progress,
+        MessageKind::Finding,
+        MessageKind::Blocker,
+        MessageKind::Handoff,
+        MessageKind::Done,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageKind::Claim => "claim",
+            MessageKind::Progress => "progress",
+            MessageKind::Finding => "finding",
+            MessageKind::Blocker => "blocker",
+            MessageKind::Handoff => "handoff",
+            MessageKind::Done => "done",
+        }
+    }
+}
+
+impl fmt::Display for MessageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for MessageKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        MessageKind::ALL.iter()
+            .find(|kind| kind.as_str() == value.to_lowercase())
+            .copied()
+            .ok_or_else(|| format!("Unknown message kind '{}' (use claim, progress, finding, blocker, handoff or done)", value))
+    }
+}
+
+/// A message between instances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoordMessage {
+    pub id: String,
+    pub kind: MessageKind,
+    pub from: String,
+
+    /// Recipient instance; `None` broadcasts to every other instance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+
+    pub body: String,
+
+    /// Paths claimed, handed off or reported on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+
+    /// Id of the blocker this message resolves
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolves: Option<String>,
+
+    pub sent_at: DateTime<Utc>,
+
+    /// Set on inbox copies once the recipient has read them
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read: bool,
+}
+
+impl CoordMessage {
+    pub fn new(kind: MessageKind, from: &str, body: &str) -> Self {
+        CoordMessage {
+            id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            kind,
+            from: from.to_string(),
+            to: None,
+            body: body.to_string(),
+            paths: Vec::new(),
+            resolves: None,
+            sent_at: Utc::now(),
+            read: false,
+        }
+    }
+}
+
+/// Mailboxes of one parallel session
+pub struct Mailboxes {
+    coordination_dir: PathBuf,
+}
+
+impl Mailboxes {
+    pub fn new(coordination_dir: &Path) -> Self {
+        Mailboxes { coordination_dir: coordination_dir.to_path_buf() }
+    }
+
+    fn messages_dir(&self) -> PathBuf {
+        self.coordination_dir.join(MESSAGES_DIR)
+    }
+
+    /// Instance keys registered by the supervisor, if the session was supervised
+    fn registered_instances(&self) -> Option<Vec<String>> {
+        SupervisorState::load(&self.coordination_dir).ok()
+            .map(|state| state.instances.into_iter().map(|i| i.instance_key).collect())
+    }
+
+    /// Instance keys of the session, from the supervisor state or existing mailboxes
+    pub fn instances(&self) -> Vec<String> {
+        if let Some(instances) = self.registered_instances() {
+            return instances;
+        }
+
+        let mut instances: Vec<String> = fs::read_dir(self.messages_dir())
+            .map(|entries| entries.flatten()
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|name| name != HUMAN_SENDER)
+                .collect())
+            .unwrap_or_default();
+        instances.sort();
+        instances
+    }
+
+    /// Deliver a message: append it to the sender's outbox and the recipients' inboxes.
+    /// Returns the recipients.
+    pub fn send(&self, message: &CoordMessage) -> Result<Vec<String>> {
+        validate_instance_name(&message.from)?;
+        if let Some(to) = &message.to {
+            validate_instance_name(to)?;
+        }
+
+        if let Some(registered) = self.registered_instances() {
+            let known = |name: &String| name == HUMAN_SENDER || registered.contains(name);
+            if !known(&message.from) {
+                return Err(anyhow!("Unknown sender '{}' (instances: {})", message.from, registered.join(", ")));
+            }
+            if let Some(to) = message.to.as_ref().filter(|to| !known(to)) {
+                return Err(anyhow!("Unknown recipient '{}' (instances: {})", to, registered.join(", ")));
+            }
+        }
+
+        let recipients: Vec<String> = match &message.to {
+            Some(to) => vec![to.clone()],
+            None => self.instances().into_iter().filter(|i| i != &message.from).collect(),
+        };
+
+        self.locked(|| {
+            if let Some(blocker) = &message.resolves {
+                if !self.all_messages()?.iter().any(|m| &m.id == blocker && m.kind == MessageKind::Blocker) {
+                    return Err(anyhow!("No blocker with id '{}'", blocker));
+                }
+            }
+
+            self.update(&message.from, OUTBOX_FILE, |outbox| outbox.push(message.clone()))?;
+            for recipient in &recipients {
+                self.update(recipient, INBOX_FILE, |inbox| inbox.push(message.clone()))?;
+            }
+
+            let board = Board::from_messages(&self.all_messages()?);
+            write_atomic(&self.coordination_dir.join(BOARD_FILE), &board.render())
+        })?;
+
+        Ok(recipients)
+    }
+
+    /// Messages in an instance's inbox; unread ones only unless `all`. Marks them read.
+    /// An instance without a mailbox has no messages; none is created for it.
+    pub fn read(&self, instance: &str, all: bool) -> Result<Vec<CoordMessage>> {
+        validate_instance_name(instance)?;
+        if let Some(registered) = self.registered_instances() {
+            if instance != HUMAN_SENDER && !registered.iter().any(|name| name == instance) {
+                return Err(anyhow!("Unknown instance '{}' (instances: {})", instance, registered.join(", ")));
+            }
+        }
+
+        self.locked(|| {
+            if !self.messages_dir().join(instance).join(INBOX_FILE).exists() {
+                return Ok(Vec::new());
+            }
+            let mut shown = Vec::new();
+            self.update(instance, INBOX_FILE, |inbox| {
+                for message in inbox.iter_mut().filter(|m| all || !m.read) {
+                    shown.push(message.clone());
+                    message.read = true;
+                }
+            })?;
+            Ok(shown)
+        })
+    }
+
+    /// Board derived from every outbox of the session
+    pub fn board(&self) -> Result<Board> {
+        self.locked(|| Ok(Board::from_messages(&self.all_messages()?)))
+    }
+
+    /// Every message sent in the session, oldest first
+    pub fn messages(&self) -> Result<Vec<CoordMessage>> {
+        self.locked(|| self.all_messages())
+    }
+
+    /// Every sent message, oldest first
+    fn all_messages(&self) -> Result<Vec<CoordMessage>> {
+        let mut messages = Vec::new();
+        if let Ok(entries) = fs::read_dir(self.messages_dir()) {
+            for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+                messages.extend(read_mailbox(&entry.path().join(OUTBOX_FILE))?);
+            }
+        }
+        messages.sort_by(|a, b| a.sent_at.cmp(&b.sent_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(messages)
+    }
+
+    fn update(&self, instance: &str, file: &str, change: impl FnOnce(&mut Vec<CoordMessage>)) -> Result<()> {
+        validate_instance_name(instance)?;
+        let path = self.messages_dir().join(instance).join(file);
+        let mut messages = read_mailbox(&path)?;
+        change(&mut messages);
+        fs::create_dir_all(path.parent().unwrap())?;
+        write_atomic(&path, &serde_json::to_string_pretty(&messages)?)
+    }
+
+    /// Run `action` while holding the session's mailbox lock
+    fn locked<T>(&self, action: impl FnOnce() -> Result<T>) -> Result<T> {
+        fs::create_dir_all(self.messages_dir())?;
+        let lock_path = self.messages_dir().join(LOCK_FILE);
+        let lock = OpenOptions::new().create(true).truncate(false).write(true).open(&lock_path)
+            .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+        lock.lock().with_context(|| format!("Failed to lock {}", lock_path.display()))?;
+        let result = action();
+        let _ = lock.unlock();
+        result
+    }
+}
+
+/// State of one instance on the board
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InstanceEntry {
+    pub instance: String,
+
+    /// Kind of the instance's latest message
+    pub status: Option<MessageKind>,
+    pub last_progress: Option<String>,
+    pub claims: Vec<String>,
+    pub findings: usize,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Summary of claims, progress and blockers of a session
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Board {
+    pub instances: Vec<InstanceEntry>,
+
+    /// Paths claimed by more than one instance, with the claimants
+    pub conflicts: Vec<(String, Vec<String>)>,
+    pub open_blockers: Vec<CoordMessage>,
+    pub messages: usize,
+}
+
+impl Board {
+    pub fn from_messages(messages: &[CoordMessage]) -> Self {
+        let mut entries: BTreeMap<String, InstanceEntry> = BTreeMap::new();
+        let mut owners: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for message in messages {
+            let entry = entries.entry(message.from.clone()).or_insert_with(|| InstanceEntry {
+                instance: message.from.clone(),
+                ..Default::default()
+            });
+            entry.status = Some(message.kind);
+            entry.updated_at = Some(message.sent_at);
+
+            match message.kind {
+                MessageKind::Claim => {
+                    for path in &message.paths {
+                        let claimants = owners.entry(path.clone()).or_default();
+                        if !claimants.contains(&message.from) {
+                            claimants.push(message.from.clone());
+                        }
+                    }
+                },
+                MessageKind::Progress => entry.last_progress = Some(message.body.clone()),
+                MessageKind::Finding => entry.findings += 1,
+                MessageKind::Handoff => {
+                    // Listed paths (or every claim of the sender) move to the recipient
+                    for (path, claimants) in owners.iter_mut() {
+                        if claimants.contains(&message.from) && (message.paths.is_empty() || message.paths.contains(path)) {
+                            claimants.retain(|c| c != &message.from);
+                            if let Some(to) = &message.to {
+                                claimants.push(to.clone());
+                            }
+                        }
+                    }
+                },
+                MessageKind::Blocker | MessageKind::Done => {},
+            }
+        }
+
+        for (path, claimants) in &owners {
+            for claimant in claimants {
+                entries.entry(claimant.clone())
+                    .or_insert_with(|| InstanceEntry { instance: claimant.clone(), ..Default::default() })
+                    .claims.push(path.clone());
+            }
+        }
+
+        let open_blockers = messages.iter()
+            .filter(|m| m.kind == MessageKind::Blocker)
+            .filter(|blocker| !messages.iter().any(|m| m.resolves.as_ref() == Some(&blocker.id)))
+            .cloned()
+            .collect();
+
+        Board {
+            instances: entries.into_values().collect(),
+            conflicts: owners.into_iter().filter(|(_, claimants)| claimants.len() > 1).collect(),
+            open_blockers,
+            messages: messages.len(),
+        }
+    }
+
+    /// Markdown rendering, also written to `board.md`
+    pub fn render(&self) -> String {
+        let mut text = String::from("# Coordination Board\n\n");
+        text.push_str(&format!("_{} messages, updated {}_\n\n", self.messages, Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
+
+        text.push_str("## Instances\n\n");
+        if self.instances.is_empty() {
+            text.push_str("No messages yet.\n");
+        }
+        for entry in &self.instances {
+            text.push_str(&format!("### {} — {}\n\n",
+                entry.instance,
+                entry.status.map(|s| s.as_str()).unwrap_or("idle")
+            ));
+            if !entry.claims.is_empty() {
+                text.push_str(&format!("- Claims: {}\n", entry.claims.iter().map(|c| format!("`{}`", c)).collect::<Vec<_>>().join(", ")));
+            }
+            if let Some(progress) = &entry.last_progress {
+                text.push_str(&format!("- Progress: {}\n", progress));
+            }
+            if entry.findings > 0 {
+                text.push_str(&format!("- Findings: {}\n", entry.findings));
+            }
+            text.push('\n');
+        }
+
+        if !self.conflicts.is_empty() {
+            text.push_str("## Conflicting Claims\n\n");
+            for (path, claimants) in &self.conflicts {
+                text.push_str(&format!("- `{}`: {}\n", path, claimants.join(", ")));
+            }
+            text.push('\n');
+        }
+
+        text.push_str("## Open Blockers\n\n");
+        if self.open_blockers.is_empty() {
+            text.push_str("None.\n");
+        }
+        for blocker in &self.open_blockers {
+            text.push_str(&format!("- [{}] {}: {}\n", blocker.id, blocker.from, blocker.body));
+        }
+        text
+    }
+}
+
+/// Mailbox names become directory names, so keep them to plain identifiers
+fn validate_instance_name(name: &str) -> Result<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(anyhow!("Invalid instance name '{}' (use letters, digits, '_' and '-')", name));
+    }
+    Ok(())
+}
+
+fn read_mailbox(path: &Path) -> Result<Vec<CoordMessage>> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse mailbox {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read mailbox {}", path.display())),
+    }
+}
+
+/// Write through a temporary file so readers never see a partial mailbox
+fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, content)?;
+    fs::rename(&temp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn message(kind: MessageKind, from: &str, body: &str, paths: &[&str]) -> CoordMessage {
+        let mut message = CoordMessage::new(kind, from, body);
+        message.paths = paths.iter().map(|p| p.to_string()).collect();
+        message
+    }
+
+    #[test]
+    fn test_concurrent_senders_lose_no_messages() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().to_path_buf();
+
+        let handles: Vec<_> = (1..=4).map(|n| {
+            let dir = dir.clone();
+            std::thread::spawn(move || {
+                let mailboxes = Mailboxes::new(&dir);
+                for i in 0..10 {
+                    let mut msg = message(MessageKind::Progress, &format!("Coder_{}", n), &format!("step {}", i), &[]);
+                    msg.to = Some("human".to_string());
+                    mailboxes.send(&msg).unwrap();
+                }
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mailboxes = Mailboxes::new(&dir);
+        assert_eq!(mailboxes.read("human", false).unwrap().len(), 40);
+        assert!(mailboxes.read("human", false).unwrap().is_empty());
+        assert_eq!(mailboxes.read("human", true).unwrap().len(), 40);
+        assert_eq!(mailboxes.board().unwrap().messages, 40);
+    }
+
+    #[test]
+    fn test_board_tracks_claims_handoffs_and_blockers() {
+        let temp_dir = TempDir::new().unwrap();
+        let mailboxes = Mailboxes::new(temp_dir.path());
+
+        mailboxes.send(&message(MessageKind::Claim, "Coder_1", "taking the parser", &["src/parser", "src/lexer"])).unwrap();
+        mailboxes.send(&message(MessageKind::Claim, "Coder_2", "taking the lexer too", &["src/lexer"])).unwrap();
+        let blocker = message(MessageKind::Blocker, "Coder_2", "tests do not build", &[]);
+        mailboxes.send(&blocker).unwrap();
+
+        let mut handoff = message(MessageKind::Handoff, "Coder_1", "parser is yours", &["src/parser"]);
+        handoff.to = Some("Coder_2".to_string());
+        mailboxes.send(&handoff).unwrap();
+
+        let board = mailboxes.board().unwrap();
+        assert_eq!(board.conflicts, vec![("src/lexer".to_string(), vec!["Coder_1".to_string(), "Coder_2".to_string()])]);
+        assert_eq!(board.instances[1].claims, vec!["src/lexer", "src/parser"]);
+        assert_eq!(board.open_blockers.len(), 1);
+
+        let mut fix = message(MessageKind::Progress, "Coder_1", "fixed the build", &[]);
+        fix.resolves = Some(blocker.id.clone());
+        mailboxes.send(&fix).unwrap();
+        assert!(mailboxes.board().unwrap().open_blockers.is_empty());
+
+        let mut bogus = message(MessageKind::Done, "Coder_1", "", &[]);
+        bogus.resolves = Some("nope".to_string());
+        assert!(mailboxes.send(&bogus).is_err());
+
+        let mut escape = message(MessageKind::Progress, "Coder_1", "hello", &[]);
+        escape.to = Some("../../x".to_string());
+        assert!(mailboxes.send(&escape).is_err());
+        assert!(mailboxes.read("../Coder_1", true).is_err());
+        assert!(mailboxes.read("Coder_3", false).unwrap().is_empty());
+        assert_eq!(mailboxes.instances(), vec!["Coder_1", "Coder_2"]);
+        assert!(!temp_dir.path().parent().unwrap().join("x").exists());
+
+        let rendered = fs::read_to_string(temp_dir.path().join(BOARD_FILE)).unwrap();
+        assert!(rendered.contains("### Coder_2 — blocker") && rendered.contains("Open Blockers\n\nNone."));
+        assert!("Claim".parse::<MessageKind>().is_ok() && "chat".parse::<MessageKind>().is_err());
+    }
+}
@@ -0,0 +1,71 @@
+//! Running git in a given repository
+//!
+//! Helpers that ask git about a repository other than the current directory
+//! go through `git` here instead of building their own `Command`.
+
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Run git in `dir` and return its standard output
+pub fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        return Err(anyhow!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Trimmed output of a git command, or None when it fails or prints nothing
+pub fn git_value(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = git(dir, args).ok()?;
+    let value = output.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Empty repository on `main` with a commit identity, for tests
+#[cfg(test)]
+pub fn init_test_repo(dir: &Path) {
+    std::fs::create_dir_all(dir).unwrap();
+    for args in [
+        vec!["init", "-q", "-b", "main"],
+        vec!["config", "user.name", "CI"],
+        vec!["config", "user.email", "ci@example.com"],
+        vec!["config", "commit.gpgsign", "false"],
+    ] {
+        git(dir, &args).unwrap();
+    }
+}
+
+/// Stage everything in `dir` and commit it, for tests
+#[cfg(test)]
+pub fn commit_all(dir: &Path, message: &str) {
+    git(dir, &["add", "-A"]).unwrap();
+    git(dir, &["commit", "-q", "--allow-empty", "-m", message]).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_git_output_and_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        init_test_repo(repo);
+
+        assert_eq!(git_value(repo, &["symbolic-ref", "--short", "HEAD"]).as_deref(), Some("main"));
+        assert_eq!(git_value(repo, &["status", "--porcelain"]), None);
+        assert!(git(repo, &["rev-parse", "--verify", "HEAD"]).is_err());
+
+        std::fs::write(repo.join("a.txt"), "a").unwrap();
+        commit_all(repo, "Add a");
+        assert_eq!(git(repo, &["log", "--format=%s"]).unwrap(), "Add a\n");
+    }
+}
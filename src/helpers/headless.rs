@@ -0,0 +1,274 @@
+//! Headless agent runs
+//!
+//! Runs an assistant non-interactively, sends its stdout/stderr to a log in
+//! the session directory, enforces an optional timeout and writes a JSON
+//! result for scripts:
+//!
+//! ```text
+//! AGENTS/<Agent>/sessions/<timestamp>/
+//!   memory.md      agent memory plus task handed to the assistant
+//!   output.log     everything the assistant printed
+//!   result.json    HeadlessResult
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use crate::helpers::agent_supervisor::LaunchSpec;
+use crate::helpers::git::{git, git_value};
+use crate::helpers::memory_history::hex;
+
+/// Log file the assistant's output is written to
+pub const OUTPUT_LOG: &str = "output.log";
+
+/// Result file written at the end of a run
+pub const RESULT_FILE: &str = "result.json";
+
+/// How often a running process is polled for exit and timeout
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How a headless run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeadlessStatus {
+    Success,
+    Failed,
+    TimedOut,
+}
+
+/// Structured outcome of a headless run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadlessResult {
+    pub agent: String,
+    pub task: String,
+    pub status: HeadlessStatus,
+    pub exit_code: Option<i32>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub duration_secs: f64,
+    pub timeout_secs: Option<u64>,
+    pub session_dir: PathBuf,
+    pub output_path: PathBuf,
+
+    /// Repository files modified, added or removed during the run (committed or not)
+    pub files_changed: Vec<String>,
+}
+
+/// A headless run of one agent on one task
+pub struct HeadlessRun<'a> {
+    pub agent: &'a str,
+    pub task: &'a str,
+    pub session_dir: &'a Path,
+    pub timeout: Option<Duration>,
+
+    /// Repository whose changes are reported; skipped when not a git work tree
+    pub repo_dir: &'a Path,
+}
+
+impl HeadlessRun<'_> {
+    /// Run the process described by `spec` to completion or timeout
+    pub fn execute(&self, spec: &LaunchSpec) -> Result<HeadlessResult> {
+        fs::create_dir_all(self.session_dir)
+            .with_context(|| format!("Failed to create session directory {}", self.session_dir.display()))?;
+        let output_path = self.session_dir.join(OUTPUT_LOG);
+        let log = fs::File::create(&output_path)
+            .with_context(|| format!("Failed to create {}", output_path.display()))?;
+
+        let before = RepoState::capture(self.repo_dir);
+
+        let mut command = spec.to_command_with_stdin()?;
+        if spec.stdin_file.is_none() {
+            command.stdin(Stdio::null());
+        }
+        command.stdout(log.try_clone()?).stderr(log);
+
+        let started_at = Utc::now();
+        let clock = Instant::now();
+        let mut child = command.spawn()
+            .with_context(|| format!("Failed to start {}", spec.program))?;
+
+        let (status, exit_code) = loop {
+            if let Some(exit) = child.try_wait()? {
+                let status = if exit.success() { HeadlessStatus::Success } else { HeadlessStatus::Failed };
+                break (status, exit.code());
+            }
+            if self.timeout.is_some_and(|timeout| clock.elapsed() >= timeout) {
+                let _ = child.kill();
+                let _ = child.wait();
+                break (HeadlessStatus::TimedOut, None);
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        };
+
+        let files_changed = match (before, RepoState::capture(self.repo_dir)) {
+            (Some(before), Some(after)) => before.changed_files(&after, self.repo_dir),
+            _ => Vec::new(),
+        };
+
+        let result = HeadlessResult {
+            agent: self.agent.to_string(),
+            task: self.task.to_string(),
+            status,
+            exit_code,
+            started_at,
+            finished_at: Utc::now(),
+            duration_secs: clock.elapsed().as_secs_f64(),
+            timeout_secs: self.timeout.map(|t| t.as_secs()),
+            session_dir: self.session_dir.to_path_buf(),
+            output_path,
+            files_changed,
+        };
+
+        fs::write(self.session_dir.join(RESULT_FILE), serde_json::to_string_pretty(&result)?)?;
+        Ok(result)
+    }
+}
+
+/// Parse a timeout: plain seconds or a number with `s`, `m` or `h`
+pub fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| format!("Invalid timeout '{}'", value))?;
+    let seconds = match unit {
+        "" | "s" => Some(amount),
+        "m" => amount.checked_mul(60),
+        "h" => amount.checked_mul(3600),
+        _ => return Err(format!("Invalid timeout '{}' (use e.g. 90, 30s, 15m, 2h)", value)),
+    }
+    .ok_or_else(|| format!("Timeout '{}' is too large", value))?;
+
+    if seconds == 0 {
+        return Err("Timeout must be greater than zero".to_string());
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+/// HEAD and dirty files of a work tree, used to find what a run changed
+struct RepoState {
+    head: Option<String>,
+    /// Dirty paths mapped to a hash of their content (empty when deleted)
+    dirty: BTreeMap<String, String>,
+}
+
+impl RepoState {
+    fn capture(repo_dir: &Path) -> Option<Self> {
+        let status = git(repo_dir, &["status", "--porcelain", "-z", "--untracked-files=all"]).ok()?;
+        let head = git_value(repo_dir, &["rev-parse", "HEAD"]);
+
+        let mut dirty = BTreeMap::new();
+        let mut entries = status.split('\0').filter(|e| !e.is_empty());
+        while let Some(entry) = entries.next() {
+            if entry.len() < 4 {
+                continue;
+            }
+            let (code, path) = entry.split_at(3);
+            // Renames and copies are followed by their source path
+            if code.starts_with('R') || code.starts_with('C') {
+                entries.next();
+            }
+            let hash = fs::read(repo_dir.join(path))
+                .map(|content| hex(&Sha256::digest(content)))
+                .unwrap_or_default();
+            dirty.insert(path.to_string(), hash);
+        }
+
+        Some(RepoState { head, dirty })
+    }
+
+    fn changed_files(&self, after: &RepoState, repo_dir: &Path) -> Vec<String> {
+        let mut changed = BTreeSet::new();
+
+        if let (Some(from), Some(to)) = (&self.head, &after.head) {
+            if from != to {
+                if let Ok(names) = git(repo_dir, &["diff", "--name-only", &format!("{}..{}", from, to)]) {
+                    changed.extend(names.lines().filter(|l| !l.is_empty()).map(str::to_string));
+                }
+            }
+        }
+
+        for (path, hash) in &after.dirty {
+            if self.dirty.get(path) != Some(hash) {
+                changed.insert(path.clone());
+            }
+        }
+        // Dirty before and clean now: reverted or committed
+        for path in self.dirty.keys() {
+            if !after.dirty.contains_key(path) {
+                changed.insert(path.clone());
+            }
+        }
+
+        changed.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn shell(script: &str) -> LaunchSpec {
+        let mut spec = LaunchSpec::new("sh");
+        spec.args = vec!["-c".to_string(), script.to_string()];
+        spec
+    }
+
+    #[test]
+    fn test_run_captures_output_and_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        crate::helpers::git::init_test_repo(&repo);
+        fs::write(repo.join("kept.txt"), "same").unwrap();
+
+        let session_dir = temp_dir.path().join("session");
+        let run = HeadlessRun {
+            agent: "Athena",
+            task: "write notes",
+            session_dir: &session_dir,
+            timeout: None,
+            repo_dir: &repo,
+        };
+        let mut spec = shell("echo working; echo oops >&2; echo notes > notes.md; exit 3");
+        spec.cwd = Some(repo.clone());
+
+        let result = run.execute(&spec).unwrap();
+        assert_eq!(result.status, HeadlessStatus::Failed);
+        assert_eq!(result.exit_code, Some(3));
+        assert_eq!(result.files_changed, vec!["notes.md"]);
+
+        let log = fs::read_to_string(&result.output_path).unwrap();
+        assert!(log.contains("working") && log.contains("oops"));
+        assert!(session_dir.join(RESULT_FILE).exists());
+    }
+
+    #[test]
+    fn test_timeout_kills_process() {
+        let temp_dir = TempDir::new().unwrap();
+        let run = HeadlessRun {
+            agent: "Athena",
+            task: "wait",
+            session_dir: temp_dir.path(),
+            timeout: Some(Duration::from_millis(300)),
+            repo_dir: temp_dir.path(),
+        };
+
+        let result = run.execute(&shell("sleep 10")).unwrap();
+        assert_eq!(result.status, HeadlessStatus::TimedOut);
+        assert!(result.duration_secs < 5.0);
+        assert!(result.files_changed.is_empty());
+
+        assert_eq!(parse_timeout("15m").unwrap(), Duration::from_secs(900));
+        assert_eq!(parse_timeout("90").unwrap(), Duration::from_secs(90));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("soon").is_err());
+        assert!(parse_timeout("18446744073709551615h").is_err());
+    }
+}
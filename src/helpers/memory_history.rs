@@ -0,0 +1,324 @@
+//! Versioned agent memory
+//!
+//! Memory, learning and README files of an agent are snapshotted into a
+//! content-addressed store under `AGENTS/.history/`, independent of whether the
+//! CI repository uses git:
+//!
+//! ```text
+//! AGENTS/.history/
+//!   objects/ab/cdef0123...     file contents, named by their SHA-256
+//!   Athena.jsonl               one MemorySnapshot per line, oldest first
+//! ```
+//!
+//! Identical content is stored once, and a snapshot is only recorded when at
+//! least one tracked file changed since the previous one.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use similar::TextDiff;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::helpers::agent_manifest::{default_memory_file, AgentMetadata};
+
+/// Directory under AGENTS/ holding the snapshot store
+pub const HISTORY_DIR: &str = ".history";
+
+/// Default name of an agent's continuous learning file
+const LEARNING_FILE: &str = "ContinuousLearning.md";
+
+/// One recorded version of an agent's memory files
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemorySnapshot {
+    /// Short content hash identifying the snapshot
+    pub id: String,
+
+    /// Agent name
+    pub agent: String,
+
+    /// When the snapshot was taken (RFC 3339)
+    pub timestamp: String,
+
+    /// Why the snapshot was taken, e.g. "load" or "session end"
+    pub reason: String,
+
+    /// Tracked files relative to the agent directory, mapped to object hashes
+    pub files: BTreeMap<String, String>,
+}
+
+/// Where the contents of one side of a diff come from
+#[derive(Debug, Clone, Copy)]
+pub enum DiffSide<'a> {
+    Snapshot(&'a MemorySnapshot),
+    WorkingCopy,
+}
+
+/// Content-addressed snapshot store for agent memory files
+pub struct MemoryHistory {
+    agents_dir: PathBuf,
+    history_dir: PathBuf,
+}
+
+impl MemoryHistory {
+    pub fn new(agents_dir: &Path) -> Self {
+        MemoryHistory {
+            agents_dir: agents_dir.to_path_buf(),
+            history_dir: agents_dir.join(HISTORY_DIR),
+        }
+    }
+
+    /// Record the current memory files of an agent.
+    /// Returns `None` when nothing changed since the last snapshot.
+    pub fn snapshot(&self, agent: &str, reason: &str) -> Result<Option<MemorySnapshot>> {
+        let mut files = BTreeMap::new();
+        for relative in self.tracked_files(agent) {
+            let content = fs::read(self.agents_dir.join(agent).join(&relative))
+                .with_context(|| format!("Failed to read {} of agent {}", relative, agent))?;
+            files.insert(relative, self.store_object(&content)?);
+        }
+
+        if files.is_empty() {
+            return Ok(None);
+        }
+
+        if self.log(agent)?.last().is_some_and(|last| last.files == files) {
+            return Ok(None);
+        }
+
+        let mut hasher = Sha256::new();
+        for (path, hash) in &files {
+            hasher.update(format!("{}\0{}\n", path, hash));
+        }
+        let timestamp = Utc::now().to_rfc3339();
+        hasher.update(&timestamp);
+
+        let snapshot = MemorySnapshot {
+            id: hex(&hasher.finalize())[..12].to_string(),
+            agent: agent.to_string(),
+            timestamp,
+            reason: reason.to_string(),
+            files,
+        };
+
+        fs::create_dir_all(&self.history_dir)?;
+        let mut log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.log_path(agent))
+            .with_context(|| format!("Failed to open memory log for {}", agent))?;
+        writeln!(log, "{}", serde_json::to_string(&snapshot)?)?;
+
+        Ok(Some(snapshot))
+    }
+
+    /// All snapshots of an agent, oldest first
+    pub fn log(&self, agent: &str) -> Result<Vec<MemorySnapshot>> {
+        let path = self.log_path(agent);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line)
+                .with_context(|| format!("Corrupt entry in {}", path.display())))
+            .collect()
+    }
+
+    /// Find a snapshot by id prefix
+    pub fn find(&self, agent: &str, id: &str) -> Result<MemorySnapshot> {
+        let matches: Vec<MemorySnapshot> = self.log(agent)?
+            .into_iter()
+            .filter(|s| s.id.starts_with(id))
+            .collect();
+
+        match matches.len() {
+            0 => Err(anyhow!("No snapshot '{}' for agent {}", id, agent)),
+            1 => Ok(matches.into_iter().next().unwrap()),
+            n => Err(anyhow!("Snapshot id '{}' is ambiguous ({} matches)", id, n)),
+        }
+    }
+
+    /// Unified diff between two versions of an agent's memory files
+    pub fn diff(&self, agent: &str, from: DiffSide, to: DiffSide) -> Result<String> {
+        let from_files = self.side_files(agent, from);
+        let to_files = self.side_files(agent, to);
+        let paths: BTreeSet<&String> = from_files.iter().chain(&to_files).collect();
+
+        let mut output = String::new();
+        for path in paths {
+            let old = self.side_content(agent, from, path)?;
+            let new = self.side_content(agent, to, path)?;
+            if old == new {
+                continue;
+            }
+
+            let diff = TextDiff::from_lines(&old, &new);
+            output.push_str(&diff.unified_diff()
+                .context_radius(3)
+                .header(&format!("a/{} ({})", path, side_label(from)), &format!("b/{} ({})", path, side_label(to)))
+                .to_string());
+        }
+
+        Ok(output)
+    }
+
+    /// Restore the files of a snapshot, recording the current state first.
+    /// Returns the restored file paths.
+    pub fn revert(&self, agent: &str, snapshot: &MemorySnapshot) -> Result<Vec<String>> {
+        self.snapshot(agent, &format!("before revert to {}", snapshot.id))?;
+
+        let agent_dir = self.agents_dir.join(agent);
+        let mut restored = Vec::new();
+        for (path, hash) in &snapshot.files {
+            let content = self.read_object(hash)?;
+            let target = agent_dir.join(path);
+            if fs::read(&target).ok().as_deref() == Some(content.as_slice()) {
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, content)
+                .with_context(|| format!("Failed to restore {}", target.display()))?;
+            restored.push(path.clone());
+        }
+
+        self.snapshot(agent, &format!("revert to {}", snapshot.id))?;
+        Ok(restored)
+    }
+
+    /// Memory, learning and README files of an agent, relative to its directory
+    pub fn tracked_files(&self, agent: &str) -> Vec<String> {
+        let agent_dir = self.agents_dir.join(agent);
+        let mut candidates: Vec<PathBuf> = vec![agent_dir.join("README.md"), agent_dir.join(LEARNING_FILE)];
+
+        match AgentMetadata::from_manifest(&agent_dir) {
+            Ok(Some(manifest)) => {
+                candidates.push(PathBuf::from(manifest.memory_path));
+                candidates.extend(manifest.learning_path.map(PathBuf::from));
+            }
+            _ => candidates.extend(default_memory_file(&agent_dir, agent)),
+        }
+
+        let mut files: Vec<String> = candidates.iter()
+            .filter(|path| path.is_file())
+            .filter_map(|path| path.strip_prefix(&agent_dir).ok())
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        files.dedup();
+        files
+    }
+
+    fn side_files(&self, agent: &str, side: DiffSide) -> Vec<String> {
+        match side {
+            DiffSide::Snapshot(snapshot) => snapshot.files.keys().cloned().collect(),
+            DiffSide::WorkingCopy => self.tracked_files(agent),
+        }
+    }
+
+    fn side_content(&self, agent: &str, side: DiffSide, path: &str) -> Result<String> {
+        let bytes = match side {
+            DiffSide::Snapshot(snapshot) => match snapshot.files.get(path) {
+                Some(hash) => self.read_object(hash)?,
+                None => Vec::new(),
+            },
+            DiffSide::WorkingCopy => fs::read(self.agents_dir.join(agent).join(path)).unwrap_or_default(),
+        };
+        Ok(String::from_utf8_lossy(&bytes).to_string())
+    }
+
+    fn store_object(&self, content: &[u8]) -> Result<String> {
+        let hash = hex(&Sha256::digest(content));
+        let path = self.object_path(&hash);
+        if !path.exists() {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, content)
+                .with_context(|| format!("Failed to write object {}", path.display()))?;
+        }
+        Ok(hash)
+    }
+
+    fn read_object(&self, hash: &str) -> Result<Vec<u8>> {
+        let path = self.object_path(hash);
+        fs::read(&path).with_context(|| format!("Missing history object {}", hash))
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.history_dir.join("objects").join(&hash[..2]).join(&hash[2..])
+    }
+
+    fn log_path(&self, agent: &str) -> PathBuf {
+        self.history_dir.join(format!("{}.jsonl", agent))
+    }
+}
+
+fn side_label(side: DiffSide) -> String {
+    match side {
+        DiffSide::Snapshot(snapshot) => snapshot.id.clone(),
+        DiffSide::WorkingCopy => "working copy".to_string(),
+    }
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, MemoryHistory, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let agent_dir = temp_dir.path().join("Athena");
+        fs::create_dir_all(&agent_dir).unwrap();
+        fs::write(agent_dir.join("Athena.md"), "# Athena\nv1\n").unwrap();
+        fs::write(agent_dir.join("README.md"), "# README\n").unwrap();
+        let history = MemoryHistory::new(temp_dir.path());
+        (temp_dir, history, agent_dir)
+    }
+
+    #[test]
+    fn test_snapshots_are_deduplicated() {
+        let (_temp, history, agent_dir) = setup();
+
+        let first = history.snapshot("Athena", "load").unwrap().unwrap();
+        assert_eq!(first.files.len(), 2);
+        assert!(history.snapshot("Athena", "load").unwrap().is_none());
+
+        fs::write(agent_dir.join("Athena.md"), "# Athena\nv2\n").unwrap();
+        let second = history.snapshot("Athena", "session end").unwrap().unwrap();
+        assert_eq!(first.files["README.md"], second.files["README.md"]);
+        assert_eq!(history.log("Athena").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_diff_and_revert() {
+        let (_temp, history, agent_dir) = setup();
+        let first = history.snapshot("Athena", "load").unwrap().unwrap();
+        fs::write(agent_dir.join("Athena.md"), "# Athena\nv2\n").unwrap();
+
+        let diff = history.diff("Athena", DiffSide::Snapshot(&first), DiffSide::WorkingCopy).unwrap();
+        assert!(diff.contains("-v1"));
+        assert!(diff.contains("+v2"));
+        assert!(!diff.contains("README"));
+
+        let found = history.find("Athena", &first.id[..6]).unwrap();
+        let restored = history.revert("Athena", &found).unwrap();
+        assert_eq!(restored, vec!["Athena.md"]);
+        assert_eq!(fs::read_to_string(agent_dir.join("Athena.md")).unwrap(), "# Athena\nv1\n");
+
+        // The overwritten version stays recoverable
+        let log = history.log("Athena").unwrap();
+        assert_eq!(log.len(), 3);
+        assert!(log[1].reason.starts_with("before revert"));
+    }
+}
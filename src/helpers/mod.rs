@@ -13,6 +13,27 @@ pub mod progress_indicator;
 pub mod api_client;
 pub mod agent_autoload;
 pub mod agent_colors;
+pub mod agent_supervisor;
+pub mod agent_launcher;
+pub mod agent_manifest;
+pub mod memory_history;
+pub mod agent_matcher;
+pub mod agent_bundle;
+pub mod agent_stats;
+pub mod agent_templates;
+pub mod ansi;
+pub mod coordination;
+pub mod git;
+pub mod headless;
+pub mod parallel_report;
+pub mod session_export;
+pub mod session_provenance;
+pub mod session_resume;
+pub mod session_retention;
+pub mod session_store;
+pub mod sharding;
+pub mod transcript;
+pub mod worktrees;
 
 // Re-export commonly used helpers
 pub use command::CommandHelpers;
@@ -28,6 +49,11 @@ pub use progress_indicator::{Spinner, SpinnerResult, with_spinner, with_progress
 pub use api_client::CIApiClient;
 pub use agent_autoload::{AgentAutoload, AgentActivationConfig};
 pub use agent_colors::{get_agent_color, apply_agent_color, reset_terminal_color, get_color_name};
+pub use agent_supervisor::{AgentSupervisor, LaunchSpec, SupervisorState, InstanceStatus, WaitOptions};
+pub use agent_launcher::{AgentLauncher, LaunchRequest, configured_launcher};
+pub use agent_manifest::{AgentMetadata, ValidationReport, validate_agent_dir};
+pub use memory_history::{MemoryHistory, MemorySnapshot};
+pub use agent_matcher::{AgentMatch, rank_agents};
 
 use colored::*;
 use anyhow::Context;
@@ -0,0 +1,652 @@
+//! Final report of a parallel agent session
+//!
+//! `ci load report <session_id>` gathers what each instance of a parallel
+//! session produced: the files it left in the coordination directory, its
+//! session memory, its coordination messages and the git changes made since
+//! the session started, and renders them into one Markdown or HTML document.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::helpers::agent_supervisor::{format_duration_secs, SupervisorState, SUPERVISOR_STATE_FILE};
+use crate::helpers::commit_analyzer::{ChangeType, CommitAnalysis, CommitAnalyzer};
+use crate::helpers::coordination::{Board, CoordMessage, Mailboxes, BOARD_FILE, MESSAGES_DIR};
+use crate::helpers::git::git_value;
+use crate::helpers::sharding::{ShardPlan, SHARDS_FILE};
+use crate::helpers::worktrees::{WorktreeSession, WORKTREES_FILE};
+
+/// Coordination files embedded in the report are cut off after this many bytes
+const MAX_EMBEDDED_BYTES: usize = 64 * 1024;
+
+/// Files the session itself maintains; everything else was created by instances
+const SESSION_FILES: &[&str] = &[
+    "task_coordination.md",
+    SUPERVISOR_STATE_FILE,
+    SHARDS_FILE,
+    WORKTREES_FILE,
+    BOARD_FILE,
+    "report.md",
+    "report.html",
+];
+
+pub(crate) const SESSION_MEMORY_SUFFIX: &str = "_session_memory.md";
+
+/// Output format of the report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "md" | "markdown" => Ok(ReportFormat::Markdown),
+            "html" => Ok(ReportFormat::Html),
+            _ => Err(format!("Unknown report format '{}' (use md or html)", value)),
+        }
+    }
+}
+
+/// A file changed in the repository
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedFile {
+    pub path: String,
+    pub change: &'static str,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// Changes analyzed by `CommitAnalyzer`
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeSummary {
+    pub summary: String,
+    pub suggested_message: String,
+    pub languages: Vec<(String, usize)>,
+    pub files: Vec<ChangedFile>,
+}
+
+impl From<CommitAnalysis> for ChangeSummary {
+    fn from(analysis: CommitAnalysis) -> Self {
+        let mut languages: Vec<(String, usize)> = analysis.languages.into_iter().collect();
+        languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        ChangeSummary {
+            summary: analysis.change_summary,
+            suggested_message: analysis.suggested_message,
+            languages,
+            files: analysis.files_changed.into_iter()
+                .map(|file| ChangedFile {
+                    change: match file.change_type {
+                        ChangeType::Addition => "added",
+                        ChangeType::Modification => "modified",
+                        ChangeType::Removal => "removed",
+                        ChangeType::Rename => "renamed",
+                        ChangeType::Permission => "mode changed",
+                        ChangeType::Untracked => "untracked",
+                    },
+                    path: file.file_path,
+                    additions: file.additions,
+                    deletions: file.deletions,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Git changes attributed to one instance
+#[derive(Debug, Clone)]
+pub enum InstanceChanges {
+    /// The instance worked on its own branch
+    Branch { branch: String, status: String, changes: Option<ChangeSummary> },
+
+    /// Changed files of the shared working tree inside the instance's claims or shard
+    Shared { files: Vec<ChangedFile> },
+
+    /// Nothing to attribute changes by
+    Unknown,
+}
+
+/// Everything one instance produced
+#[derive(Debug, Clone)]
+pub struct InstanceReport {
+    pub display_name: String,
+    pub instance_key: String,
+    pub status: String,
+    pub exit_code: Option<i32>,
+    pub duration_secs: Option<u64>,
+    pub shard: Option<String>,
+    pub messages: Vec<CoordMessage>,
+    pub files: Vec<(String, String)>,
+    pub session_memory: Option<(String, String)>,
+    pub changes: InstanceChanges,
+}
+
+/// Report of a whole parallel session
+#[derive(Debug, Clone)]
+pub struct SessionReport {
+    pub session_id: String,
+    pub task: String,
+    pub started_at: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub repo_root: Option<PathBuf>,
+    pub base_commit: Option<String>,
+    pub combined: Option<ChangeSummary>,
+    pub open_blockers: Vec<CoordMessage>,
+    pub instances: Vec<InstanceReport>,
+
+    /// Coordination files not attributable to a single instance
+    pub shared_files: Vec<(String, String)>,
+}
+
+impl SessionReport {
+    /// Collect the report of the session stored in `coordination_dir`
+    pub async fn build(coordination_dir: &Path) -> Result<Self> {
+        let state = SupervisorState::load(coordination_dir)
+            .map_err(|_| anyhow!("{} is not a supervised parallel session", coordination_dir.display()))?;
+        let worktrees = WorktreeSession::load(coordination_dir).ok();
+        let shards = ShardPlan::load(coordination_dir).ok();
+        let messages = Mailboxes::new(coordination_dir).messages()?;
+        let board = Board::from_messages(&messages);
+
+        let repo_root = worktrees.as_ref().map(|w| w.repo_root.clone()).or_else(|| state.repo_root.clone());
+        let base_commit = match (&worktrees, &state.base_commit, &repo_root) {
+            (Some(worktrees), _, _) => Some(worktrees.base_commit.clone()),
+            (None, Some(base), _) => Some(base.clone()),
+            // Sessions recorded before the base commit was stored
+            (None, None, Some(root)) => git_value(root, &["rev-list", "-1", &format!("--before={}", state.started_at.to_rfc3339()), "HEAD"]),
+            _ => None,
+        };
+
+        // Session data is not part of the work when the CI data directory lives inside the repository
+        let agents_dir = coordination_dir.parent().unwrap_or(coordination_dir);
+        let combined = match (&repo_root, &base_commit) {
+            (Some(root), Some(base)) => CommitAnalyzer::analyze_changes_since(root, base, &[agents_dir]).await.ok().map(ChangeSummary::from),
+            _ => None,
+        };
+
+        let mut files = coordination_files(coordination_dir);
+        let mut instances = Vec::new();
+        for (index, instance) in state.instances.iter().enumerate() {
+            let key = &instance.instance_key;
+            let (own, rest): (Vec<_>, Vec<_>) = files.into_iter().partition(|(path, _)| belongs_to(path, key));
+            files = rest;
+
+            let claims: Vec<String> = board.instances.iter()
+                .filter(|entry| &entry.instance == key)
+                .flat_map(|entry| entry.claims.clone())
+                .chain(shards.iter()
+                    .filter_map(|plan| plan.shards.get(index))
+                    .flat_map(|shard| shard.units.iter().flat_map(|unit| unit.files.clone())))
+                .collect();
+
+            let changes = match worktrees.as_ref().and_then(|w| w.worktrees.iter().find(|wt| &wt.name == key).map(|wt| (w, wt))) {
+                Some((session, worktree)) => {
+                    let analysis = if worktree.path.exists() {
+                        CommitAnalyzer::analyze_changes_since(&worktree.path, &session.base_commit, &[]).await
+                    } else {
+                        CommitAnalyzer::analyze_range(&session.repo_root, &session.base_commit, &worktree.branch).await
+                    };
+                    InstanceChanges::Branch {
+                        branch: worktree.branch.clone(),
+                        status: format!("{:?}", worktree.status).to_lowercase(),
+                        changes: analysis.ok().map(ChangeSummary::from),
+                    }
+                },
+                None if claims.is_empty() || combined.is_none() => InstanceChanges::Unknown,
+                None => InstanceChanges::Shared {
+                    files: combined.iter()
+                        .flat_map(|c| c.files.iter())
+                        .filter(|file| claims.iter().any(|claim| file.path == *claim || file.path.starts_with(&format!("{}/", claim.trim_end_matches('/')))))
+                        .cloned()
+                        .collect(),
+                },
+            };
+
+            instances.push(InstanceReport {
+                display_name: instance.display_name.clone(),
+                instance_key: key.clone(),
+                status: instance.status.label().to_string(),
+                exit_code: instance.exit_code,
+                duration_secs: instance.duration_secs,
+                shard: shards.as_ref().and_then(|plan| plan.shards.get(index)).map(|shard| shard.summary()),
+                messages: messages.iter().filter(|m| &m.from == key).cloned().collect(),
+                files: own,
+                session_memory: read_embedded(&coordination_dir.join(format!("{}{}", key, SESSION_MEMORY_SUFFIX)))
+                    .map(|content| (format!("{}{}", key, SESSION_MEMORY_SUFFIX), content)),
+                changes,
+            });
+        }
+
+        Ok(SessionReport {
+            session_id: state.session_id,
+            task: state.task,
+            started_at: state.started_at,
+            generated_at: Utc::now(),
+            repo_root,
+            base_commit,
+            combined,
+            open_blockers: board.open_blockers,
+            instances,
+            shared_files: files,
+        })
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => {
+                let mut writer = MarkdownWriter::default();
+                self.write(&mut writer);
+                writer.out
+            },
+            ReportFormat::Html => {
+                let mut writer = HtmlWriter::default();
+                self.write(&mut writer);
+                writer.finish(&format!("Parallel Session Report: {}", self.session_id))
+            },
+        }
+    }
+
+    fn write(&self, w: &mut dyn ReportWriter) {
+        w.heading(1, &format!("Parallel Session Report: {}", self.session_id));
+        w.field("Task", &self.task);
+        w.field("Started", &self.started_at.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+        w.field("Generated", &self.generated_at.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+        w.field("Instances", &self.instances.len().to_string());
+        if let Some(root) = &self.repo_root {
+            let base = self.base_commit.as_deref().map(|b| &b[..b.len().min(8)]).unwrap_or("unknown base");
+            w.field("Repository", &format!("{} (since {})", root.display(), base));
+        }
+
+        w.heading(2, "Combined Changes");
+        match &self.combined {
+            Some(changes) => write_changes(w, changes),
+            None => w.paragraph("No repository changes since the session started."),
+        }
+
+        w.heading(2, "Unresolved Blockers");
+        if self.open_blockers.is_empty() {
+            w.paragraph("None.");
+        } else {
+            let items: Vec<String> = self.open_blockers.iter()
+                .map(|b| format!("[{}] {} at {}: {}", b.id, b.from, b.sent_at.format("%H:%M"), b.body))
+                .collect();
+            w.list(&items);
+        }
+
+        for instance in &self.instances {
+            w.heading(2, &instance.display_name);
+            let mut status = instance.status.clone();
+            if let Some(code) = instance.exit_code {
+                status.push_str(&format!(" (exit code {})", code));
+            }
+            if let Some(secs) = instance.duration_secs {
+                status.push_str(&format!(" after {}", format_duration_secs(secs)));
+            }
+            w.field("Status", &status);
+            if let Some(shard) = &instance.shard {
+                w.field("Shard", shard);
+            }
+
+            w.heading(3, "Messages");
+            if instance.messages.is_empty() {
+                w.paragraph("No coordination messages.");
+            } else {
+                let items: Vec<String> = instance.messages.iter()
+                    .map(|m| {
+                        let mut item = format!("{} {}: {}", m.sent_at.format("%H:%M"), m.kind, m.body);
+                        if !m.paths.is_empty() {
+                            item.push_str(&format!(" ({})", m.paths.join(", ")));
+                        }
+                        item
+                    })
+                    .collect();
+                w.list(&items);
+            }
+
+            w.heading(3, "Git Changes");
+            match &instance.changes {
+                InstanceChanges::Branch { branch, status, changes } => {
+                    w.field("Branch", &format!("{} ({})", branch, status));
+                    match changes {
+                        Some(changes) => write_changes(w, changes),
+                        None => w.paragraph("No changes on this branch."),
+                    }
+                },
+                InstanceChanges::Shared { files } if files.is_empty() => w.paragraph("No changes inside this instance's claims or shard."),
+                InstanceChanges::Shared { files } => {
+                    w.paragraph("Changes in the shared working tree inside this instance's claims or shard:");
+                    write_file_table(w, files);
+                },
+                InstanceChanges::Unknown => w.paragraph("Instances shared one working tree and this one claimed no paths; see the combined changes."),
+            }
+
+            w.heading(3, "Coordination Files");
+            if instance.files.is_empty() {
+                w.paragraph("None.");
+            }
+            for (path, content) in &instance.files {
+                w.file(path, content);
+            }
+
+            if let Some((path, content)) = &instance.session_memory {
+                w.heading(3, "Session Memory");
+                w.details(path, content);
+            }
+        }
+
+        if !self.shared_files.is_empty() {
+            w.heading(2, "Shared Coordination Files");
+            for (path, content) in &self.shared_files {
+                w.file(path, content);
+            }
+        }
+    }
+}
+
+pub(crate) fn write_changes(w: &mut dyn ReportWriter, changes: &ChangeSummary) {
+    w.paragraph(changes.summary.trim());
+    w.field("Suggested commit message", &changes.suggested_message);
+    if !changes.languages.is_empty() {
+        let languages: Vec<String> = changes.languages.iter().map(|(l, n)| format!("{} ({})", l, n)).collect();
+        w.field("Languages", &languages.join(", "));
+    }
+    write_file_table(w, &changes.files);
+}
+
+fn write_file_table(w: &mut dyn ReportWriter, files: &[ChangedFile]) {
+    let rows: Vec<Vec<String>> = files.iter()
+        .map(|f| vec![f.path.clone(), f.change.to_string(), format!("+{}", f.additions), format!("-{}", f.deletions)])
+        .collect();
+    w.table(&["File", "Change", "Added", "Removed"], &rows);
+}
+
+/// Output primitives the report is written with
+pub(crate) trait ReportWriter {
+    fn heading(&mut self, level: usize, text: &str);
+    fn field(&mut self, label: &str, value: &str);
+    fn paragraph(&mut self, text: &str);
+    fn list(&mut self, items: &[String]);
+    fn table(&mut self, headers: &[&str], rows: &[Vec<String>]);
+    /// A file embedded verbatim
+    fn file(&mut self, path: &str, content: &str);
+    /// Collapsed content
+    fn details(&mut self, summary: &str, content: &str);
+}
+
+#[derive(Default)]
+pub(crate) struct MarkdownWriter {
+    pub(crate) out: String,
+}
+
+impl MarkdownWriter {
+    fn fenced(&mut self, path: &str, content: &str) {
+        let language = Path::new(path).extension().and_then(|e| e.to_str()).unwrap_or("");
+        let fence = if content.contains("```") { "````" } else { "```" };
+        let _ = writeln!(self.out, "{}{}\n{}\n{}\n", fence, language, content.trim_end(), fence);
+    }
+}
+
+impl ReportWriter for MarkdownWriter {
+    fn heading(&mut self, level: usize, text: &str) {
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+        let _ = writeln!(self.out, "{} {}\n", "#".repeat(level), text);
+    }
+
+    fn field(&mut self, label: &str, value: &str) {
+        let _ = writeln!(self.out, "**{}**: {}  ", label, value);
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        let _ = writeln!(self.out, "{}\n", text);
+    }
+
+    fn list(&mut self, items: &[String]) {
+        for item in items {
+            let _ = writeln!(self.out, "- {}", item);
+        }
+        self.out.push('\n');
+    }
+
+    fn table(&mut self, headers: &[&str], rows: &[Vec<String>]) {
+        if rows.is_empty() {
+            return;
+        }
+        if !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+        let _ = writeln!(self.out, "| {} |\n|{}", headers.join(" | "), "---|".repeat(headers.len()));
+        for row in rows {
+            let cells: Vec<String> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+            let _ = writeln!(self.out, "| {} |", cells.join(" | "));
+        }
+        self.out.push('\n');
+    }
+
+    fn file(&mut self, path: &str, content: &str) {
+        let _ = writeln!(self.out, "#### `{}`\n", path);
+        self.fenced(path, content);
+    }
+
+    fn details(&mut self, summary: &str, content: &str) {
+        let _ = writeln!(self.out, "<details>\n<summary>{}</summary>\n", summary);
+        self.fenced(summary, content);
+        self.out.push_str("</details>\n\n");
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct HtmlWriter {
+    pub(crate) body: String,
+}
+
+impl HtmlWriter {
+    fn finish(self, title: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n\
+            body {{ font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; max-width: 1100px; margin: 2rem auto; padding: 0 1rem; color: #24292f; }}\n\
+            h2 {{ border-bottom: 1px solid #d0d7de; padding-bottom: .3rem; margin-top: 2.5rem; }}\n\
+            table {{ border-collapse: collapse; margin: 1rem 0; }}\n\
+            th, td {{ border: 1px solid #d0d7de; padding: .3rem .6rem; text-align: left; }}\n\
+            pre {{ background: #f6f8fa; padding: 1rem; overflow-x: auto; }}\n\
+            .field {{ margin: .2rem 0; }}\n\
+            </style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            escape_html(title),
+            self.body
+        )
+    }
+}
+
+impl ReportWriter for HtmlWriter {
+    fn heading(&mut self, level: usize, text: &str) {
+        let _ = writeln!(self.body, "<h{0}>{1}</h{0}>", level, escape_html(text));
+    }
+
+    fn field(&mut self, label: &str, value: &str) {
+        let _ = writeln!(self.body, "<p class=\"field\"><strong>{}</strong>: {}</p>", escape_html(label), escape_html(value));
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        let _ = writeln!(self.body, "<p>{}</p>", escape_html(text).replace('\n', "<br>\n"));
+    }
+
+    fn list(&mut self, items: &[String]) {
+        self.body.push_str("<ul>\n");
+        for item in items {
+            let _ = writeln!(self.body, "<li>{}</li>", escape_html(item));
+        }
+        self.body.push_str("</ul>\n");
+    }
+
+    fn table(&mut self, headers: &[&str], rows: &[Vec<String>]) {
+        if rows.is_empty() {
+            return;
+        }
+        self.body.push_str("<table>\n<tr>");
+        for header in headers {
+            let _ = write!(self.body, "<th>{}</th>", escape_html(header));
+        }
+        self.body.push_str("</tr>\n");
+        for row in rows {
+            self.body.push_str("<tr>");
+            for cell in row {
+                let _ = write!(self.body, "<td>{}</td>", escape_html(cell));
+            }
+            self.body.push_str("</tr>\n");
+        }
+        self.body.push_str("</table>\n");
+    }
+
+    fn file(&mut self, path: &str, content: &str) {
+        let _ = writeln!(self.body, "<h4><code>{}</code></h4>\n<pre>{}</pre>", escape_html(path), escape_html(content));
+    }
+
+    fn details(&mut self, summary: &str, content: &str) {
+        let _ = writeln!(self.body, "<details>\n<summary>{}</summary>\n<pre>{}</pre>\n</details>", escape_html(summary), escape_html(content));
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Files created in the coordination directory, relative path with content
+pub(crate) fn coordination_files(coordination_dir: &Path) -> Vec<(String, String)> {
+    let mut files = Vec::new();
+    let mut pending = vec![coordination_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(relative) = path.strip_prefix(coordination_dir) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if relative == MESSAGES_DIR || SESSION_FILES.contains(&relative.as_str()) || relative.ends_with(SESSION_MEMORY_SUFFIX) {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Some(content) = read_embedded(&path) {
+                files.push((relative, content));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Whether a coordination file is named after (or lives in a directory named after) an instance
+fn belongs_to(relative_path: &str, instance_key: &str) -> bool {
+    let key = instance_key.to_lowercase();
+    let path = relative_path.to_lowercase();
+    path.split('/').next() == Some(key.as_str())
+        || path.strip_prefix(&key).is_some_and(|rest| rest.starts_with(|c: char| !c.is_alphanumeric()))
+}
+
+/// Text content of a file, truncated for embedding; `None` for binary files
+fn read_embedded(path: &Path) -> Option<String> {
+    read_excerpt(path, MAX_EMBEDDED_BYTES)
+}
+
+/// Text content of a file cut off after `max_bytes`; `None` for binary files
+pub(crate) fn read_excerpt(path: &Path, max_bytes: usize) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    let text = String::from_utf8(bytes).ok()?;
+    if text.len() <= max_bytes {
+        return Some(text);
+    }
+    let mut cut = max_bytes;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    Some(format!("{}\n\n[... truncated, {} bytes total]", &text[..cut], text.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::agent_supervisor::AgentSupervisor;
+    use crate::helpers::agent_supervisor::LaunchSpec;
+    use crate::helpers::coordination::MessageKind;
+    use crate::helpers::git::{commit_all, init_test_repo};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_report_collects_instance_outputs() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        fs::create_dir_all(repo.join("src")).unwrap();
+        fs::write(repo.join("src/lib.rs"), "fn a() {}\n").unwrap();
+        init_test_repo(&repo);
+        commit_all(&repo, "base");
+
+        let coordination_dir = temp_dir.path().join("AGENTS/parallel_session_1");
+        fs::create_dir_all(&coordination_dir).unwrap();
+        let mut supervisor = AgentSupervisor::new("parallel_session_1", "document code", &coordination_dir);
+        supervisor.record_git_base(&repo);
+        for key in ["Writer_1", "Writer_2"] {
+            supervisor.spawn(key, "Writer", key, LaunchSpec::new("true")).unwrap();
+        }
+        fs::write(coordination_dir.join("Writer_1_notes.md"), "# Notes <b>").unwrap();
+        fs::write(coordination_dir.join("plan.md"), "shared plan").unwrap();
+        fs::write(coordination_dir.join("Writer_2_session_memory.md"), "memory").unwrap();
+
+        let mailboxes = Mailboxes::new(&coordination_dir);
+        let mut claim = CoordMessage::new(MessageKind::Claim, "Writer_1", "library");
+        claim.paths = vec!["src".to_string()];
+        mailboxes.send(&claim).unwrap();
+        mailboxes.send(&CoordMessage::new(MessageKind::Blocker, "Writer_2", "no access")).unwrap();
+
+        fs::write(repo.join("src/lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        fs::write(repo.join("README.md"), "docs\n").unwrap();
+
+        let report = SessionReport::build(&coordination_dir).await.unwrap();
+        assert_eq!(report.combined.as_ref().unwrap().files.len(), 2);
+        assert_eq!(report.open_blockers.len(), 1);
+        assert_eq!(report.instances[0].files, vec![("Writer_1_notes.md".to_string(), "# Notes <b>".to_string())]);
+        assert_eq!(report.shared_files.len(), 1);
+        assert!(report.instances[1].session_memory.is_some());
+        match &report.instances[0].changes {
+            InstanceChanges::Shared { files } => assert_eq!(files[0].path, "src/lib.rs"),
+            other => panic!("unexpected changes {:?}", other),
+        }
+
+        let markdown = report.render(ReportFormat::Markdown);
+        assert!(markdown.contains("## Writer_1") && markdown.contains("no access"));
+        let html = report.render(ReportFormat::Html);
+        assert!(html.contains("# Notes &lt;b&gt;") && html.starts_with("<!DOCTYPE html>"));
+    }
+
+    #[test]
+    fn test_files_are_attributed_by_instance_key() {
+        assert!(belongs_to("Writer_1_notes.md", "Writer_1"));
+        assert!(belongs_to("writer_1/findings.md", "Writer_1"));
+        assert!(!belongs_to("Writer_10_notes.md", "Writer_1"));
+        assert!(!belongs_to("plan.md", "Writer_1"));
+        assert_eq!("HTML".parse::<ReportFormat>().unwrap(), ReportFormat::Html);
+    }
+}
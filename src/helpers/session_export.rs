@@ -0,0 +1,357 @@
+//! Shareable session reports
+//!
+//! `ci session export <id>` gathers what is known about a stored session:
+//! its agents, task and timing, an excerpt of the memory it was launched
+//! with, the coordination files it left behind and the git changes it
+//! produced. The report is rendered as Markdown or JSON here; the HTML page
+//! is themed by `WebExporter`.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::helpers::agent_supervisor::format_duration_secs;
+use crate::helpers::commit_analyzer::CommitAnalyzer;
+use crate::helpers::parallel_report::{
+    coordination_files, read_excerpt, write_changes, ChangeSummary, HtmlWriter, MarkdownWriter, ReportWriter,
+    SESSION_MEMORY_SUFFIX,
+};
+use crate::helpers::session_provenance::commit_summaries;
+use crate::helpers::session_store::{SessionKind, SessionRecord};
+
+/// Memory files are cut off after this many bytes
+const MEMORY_EXCERPT_BYTES: usize = 8 * 1024;
+
+/// Coordination files are cut off after this many bytes
+const COORDINATION_EXCERPT_BYTES: usize = 32 * 1024;
+
+/// Files of a manual session directory that are not session content
+const MANUAL_SESSION_FILES: &[&str] = &["metadata.json", "README.md"];
+
+/// Output format of an exported session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "html" => Ok(ExportFormat::Html),
+            "json" => Ok(ExportFormat::Json),
+            _ => Err(format!("Unknown export format '{}' (use md, html or json)", value)),
+        }
+    }
+}
+
+/// A file embedded in the report, possibly truncated
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddedFile {
+    pub path: String,
+    pub content: String,
+}
+
+/// A commit made during the session
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedCommit {
+    pub hash: String,
+    pub subject: String,
+}
+
+/// Everything exported about one session
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionExport {
+    pub session: SessionRecord,
+    pub generated_at: DateTime<Utc>,
+    pub duration_secs: Option<u64>,
+
+    /// Memory the agents were launched with (one file per instance for parallel sessions)
+    pub memory: Vec<EmbeddedFile>,
+    pub coordination_files: Vec<EmbeddedFile>,
+
+    /// Revision range the commits and changes were taken from
+    pub commit_range: Option<String>,
+    pub commits: Vec<ExportedCommit>,
+    pub changes: Option<ChangeSummary>,
+}
+
+impl SessionExport {
+    pub async fn build(record: SessionRecord) -> Result<Self> {
+        let (memory, coordination_files) = session_files(&record);
+
+        // While the session runs its range ends at the current HEAD
+        let git = record.git.as_ref();
+        let commit_range = git.and_then(|git| match (&git.commit_range, record.is_running()) {
+            (Some(range), _) => Some(range.clone()),
+            (None, true) => Some(git.range_to("HEAD")),
+            (None, false) => None,
+        });
+        let commits = match (git, &commit_range) {
+            (Some(git), Some(range)) => commit_summaries(&git.repo_root, range).into_iter()
+                .map(|(hash, subject)| ExportedCommit { hash, subject })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let changes = match (git, commit_range.as_deref().and_then(|range| range.split_once(".."))) {
+            (Some(git), Some((from, to))) if !commits.is_empty() =>
+                CommitAnalyzer::analyze_range(&git.repo_root, from, to).await.ok().map(ChangeSummary::from),
+            _ => None,
+        };
+
+        Ok(SessionExport {
+            duration_secs: record.duration_secs(),
+            session: record,
+            generated_at: Utc::now(),
+            memory,
+            coordination_files,
+            commit_range,
+            commits,
+            changes,
+        })
+    }
+
+    pub fn title(&self) -> String {
+        format!("Session Report: {}", self.session.title())
+    }
+
+    /// Markdown or JSON document; HTML pages are rendered by `WebExporter` around `html_body`
+    pub fn render(&self, format: ExportFormat) -> Result<String> {
+        match format {
+            ExportFormat::Markdown => {
+                let mut writer = MarkdownWriter::default();
+                writer.heading(1, &self.title());
+                self.write(&mut writer);
+                Ok(writer.out)
+            },
+            ExportFormat::Html => Ok(self.html_body()),
+            ExportFormat::Json => Ok(serde_json::to_string_pretty(self)? + "\n"),
+        }
+    }
+
+    /// Report sections as HTML, without the page around them
+    pub fn html_body(&self) -> String {
+        let mut writer = HtmlWriter::default();
+        self.write(&mut writer);
+        writer.body
+    }
+
+    fn write(&self, w: &mut dyn ReportWriter) {
+        let record = &self.session;
+        w.field("Session", &format!("{} ({})", record.id, record.kind.label()));
+        w.field("Status", &record.status.to_string());
+        w.field("Agents", &record.agents.join(", "));
+        if let Some(project) = &record.project {
+            w.field("Project", &project.display().to_string());
+        }
+        w.field("Started", &record.started_at.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+        if let Some(ended) = record.ended_at {
+            w.field("Ended", &ended.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+        }
+        if let Some(duration) = self.duration_secs {
+            w.field("Duration", &format_duration_secs(duration));
+        }
+        if !record.tags.is_empty() {
+            w.field("Tags", &record.tags.join(", "));
+        }
+        if let Some(resumed_from) = &record.resumed_from {
+            w.field("Resumed from", resumed_from);
+        }
+        if let Some(transcript) = &record.transcript_path {
+            w.field("Transcript", &transcript.display().to_string());
+        }
+        w.field("Generated", &self.generated_at.format("%Y-%m-%d %H:%M:%S UTC").to_string());
+
+        w.heading(2, "Task");
+        match record.task.as_ref().or(record.description.as_ref().filter(|d| !d.is_empty())) {
+            Some(task) => w.paragraph(task),
+            None => w.paragraph("No task was recorded."),
+        }
+        if let Some(context) = &record.context {
+            w.details("Context", context);
+        }
+
+        w.heading(2, "Memory");
+        if self.memory.is_empty() {
+            w.paragraph("No memory file is available.");
+        }
+        for file in &self.memory {
+            w.file(&file.path, &file.content);
+        }
+
+        w.heading(2, "Coordination Files");
+        if self.coordination_files.is_empty() {
+            w.paragraph("No coordination files.");
+        }
+        for file in &self.coordination_files {
+            w.file(&file.path, &file.content);
+        }
+
+        w.heading(2, "Git Changes");
+        let Some(git) = &record.git else {
+            w.paragraph("The session did not start inside a git repository.");
+            return;
+        };
+        w.field("Repository", &git.repo_root.display().to_string());
+        if let Some(branch) = git.start.as_ref().and_then(|s| s.branch.as_deref()) {
+            w.field("Branch", branch);
+        }
+        if let Some(range) = &self.commit_range {
+            w.field("Range", range);
+        }
+        if self.commits.is_empty() {
+            w.paragraph("No commits were made during this session.");
+            return;
+        }
+        w.heading(3, "Commits");
+        let commits: Vec<String> = self.commits.iter()
+            .map(|c| format!("{} {}", &c.hash[..c.hash.len().min(10)], c.subject))
+            .collect();
+        w.list(&commits);
+        if let Some(changes) = &self.changes {
+            w.heading(3, "Changed Files");
+            write_changes(w, changes);
+        }
+    }
+}
+
+/// Memory and coordination files of a session, by where its kind keeps them
+fn session_files(record: &SessionRecord) -> (Vec<EmbeddedFile>, Vec<EmbeddedFile>) {
+    let Some(memory_path) = record.memory_path.as_deref() else {
+        return (Vec::new(), Vec::new());
+    };
+    if memory_path.is_file() {
+        return (embed(memory_path, &file_name(memory_path), MEMORY_EXCERPT_BYTES).into_iter().collect(), Vec::new());
+    }
+
+    match record.kind {
+        // The coordination directory holds one session memory per instance
+        SessionKind::Parallel => {
+            let memory = sorted_files(memory_path).into_iter()
+                .filter(|path| file_name(path).ends_with(SESSION_MEMORY_SUFFIX))
+                .filter_map(|path| embed(&path, &file_name(&path), MEMORY_EXCERPT_BYTES))
+                .collect();
+            let coordination = embed(&memory_path.join("task_coordination.md"), "task_coordination.md", COORDINATION_EXCERPT_BYTES)
+                .into_iter()
+                .chain(coordination_files(memory_path).into_iter()
+                    .map(|(path, content)| EmbeddedFile { path, content }))
+                .collect();
+            (memory, coordination)
+        },
+        // Manual sessions describe themselves in README.md, everything else is notes
+        SessionKind::Manual => {
+            let memory = embed(&memory_path.join("README.md"), "README.md", MEMORY_EXCERPT_BYTES).into_iter().collect();
+            let coordination = sorted_files(memory_path).into_iter()
+                .filter(|path| !MANUAL_SESSION_FILES.contains(&file_name(path).as_str()))
+                .filter_map(|path| embed(&path, &file_name(&path), COORDINATION_EXCERPT_BYTES))
+                .collect();
+            (memory, coordination)
+        },
+        _ => (Vec::new(), Vec::new()),
+    }
+}
+
+fn embed(path: &Path, name: &str, max_bytes: usize) -> Option<EmbeddedFile> {
+    read_excerpt(path, max_bytes).map(|content| EmbeddedFile { path: name.to_string(), content })
+}
+
+fn sorted_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir).into_iter()
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    files
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::git::{commit_all, init_test_repo};
+    use crate::helpers::session_provenance::GitProvenance;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_export_collects_memory_and_git_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        init_test_repo(&repo);
+        fs::write(repo.join("README.md"), "readme\n").unwrap();
+        commit_all(&repo, "Initial");
+
+        let memory = temp_dir.path().join("combined_memory.md");
+        fs::write(&memory, format!("# Athena\n{}", "x".repeat(MEMORY_EXCERPT_BYTES))).unwrap();
+        let mut record = SessionRecord::new(SessionKind::MultiAgent, vec!["Athena".to_string(), "Tester".to_string()]);
+        record.task = Some("Add login".to_string());
+        record.memory_path = Some(memory);
+        record.git = GitProvenance::start(&repo);
+
+        fs::write(repo.join("login.rs"), "fn login() {}\n").unwrap();
+        commit_all(&repo, "Add login");
+        record.finish(true);
+
+        let export = SessionExport::build(record).await.unwrap();
+        assert_eq!(export.memory.len(), 1);
+        assert!(export.memory[0].content.contains("[... truncated"));
+        assert_eq!(export.commits.len(), 1);
+        assert_eq!(export.commits[0].subject, "Add login");
+        assert_eq!(export.changes.as_ref().unwrap().files[0].path, "login.rs");
+
+        let markdown = export.render(ExportFormat::Markdown).unwrap();
+        assert!(markdown.starts_with("# Session Report: Add login"));
+        assert!(markdown.contains("**Agents**: Athena, Tester"));
+        assert!(markdown.contains("| login.rs | added | +1 | -0 |"));
+
+        let json: serde_json::Value = serde_json::from_str(&export.render(ExportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["session"]["task"], "Add login");
+        assert_eq!(json["commits"][0]["subject"], "Add login");
+    }
+
+    #[tokio::test]
+    async fn test_export_of_manual_session_without_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let session_dir = temp_dir.path().join("AGENTS/Athena/Sessions/review");
+        fs::create_dir_all(&session_dir).unwrap();
+        fs::write(session_dir.join("README.md"), "# review\n").unwrap();
+        fs::write(session_dir.join("metadata.json"), "{}").unwrap();
+        fs::write(session_dir.join("notes.md"), "<b>found it</b>").unwrap();
+
+        let mut record = SessionRecord::new(SessionKind::Manual, vec!["Athena".to_string()]);
+        record.name = Some("review".to_string());
+        record.memory_path = Some(session_dir);
+
+        let export = SessionExport::build(record).await.unwrap();
+        assert_eq!(export.memory[0].path, "README.md");
+        let paths: Vec<&str> = export.coordination_files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["notes.md"]);
+        assert!(export.commits.is_empty() && export.changes.is_none());
+
+        let body = export.html_body();
+        assert!(body.contains("&lt;b&gt;found it&lt;/b&gt;"));
+        assert!(body.contains("did not start inside a git repository"));
+        assert_eq!("json".parse::<ExportFormat>(), Ok(ExportFormat::Json));
+        assert!("pdf".parse::<ExportFormat>().is_err());
+    }
+}
@@ -0,0 +1,261 @@
+//! Git provenance of sessions
+//!
+//! Snapshots `HEAD`, branch and dirty state of a session's repository when
+//! the session starts and ends, so the commits and files it produced can be
+//! traced back to it later (`ci session changes`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::helpers::agent_supervisor::is_process_alive;
+use crate::helpers::git::git_value;
+use crate::helpers::session_store::{SessionRecord, SessionStatus, SessionStore};
+
+/// Environment variable carrying the id of the session an assistant was launched for
+pub const SESSION_ID_VAR: &str = "CI_SESSION_ID";
+
+/// Repository state at one point of a session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitSnapshot {
+    /// Commit checked out (unset in a repository without commits)
+    pub head: Option<String>,
+
+    /// Branch checked out (unset on a detached HEAD)
+    pub branch: Option<String>,
+
+    /// Uncommitted or untracked changes were present
+    pub dirty: bool,
+    pub taken_at: DateTime<Utc>,
+}
+
+impl GitSnapshot {
+    /// Snapshot of the repository containing `dir`, if it is one
+    pub fn capture(dir: &Path) -> Option<Self> {
+        git_value(dir, &["rev-parse", "--git-dir"])?;
+        Some(GitSnapshot {
+            head: git_value(dir, &["rev-parse", "--verify", "-q", "HEAD"]),
+            branch: git_value(dir, &["symbolic-ref", "--short", "-q", "HEAD"]),
+            dirty: git_value(dir, &["status", "--porcelain"]).is_some(),
+            taken_at: Utc::now(),
+        })
+    }
+}
+
+/// Where a session's commits live and what they changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitProvenance {
+    pub repo_root: PathBuf,
+    pub start: Option<GitSnapshot>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<GitSnapshot>,
+
+    /// `<start>..<end>` revision range of the commits made during the session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_range: Option<String>,
+
+    /// Files changed by those commits
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_files: Vec<String>,
+}
+
+impl GitProvenance {
+    /// Start tracking the repository containing `dir`
+    pub fn start(dir: &Path) -> Option<Self> {
+        let repo_root = git_value(dir, &["rev-parse", "--show-toplevel"]).map(PathBuf::from)?;
+        Some(GitProvenance {
+            start: GitSnapshot::capture(&repo_root),
+            repo_root,
+            ..Default::default()
+        })
+    }
+
+    /// Revision range from the start snapshot to `end` (`HEAD` while the session runs)
+    pub fn range_to(&self, end: &str) -> String {
+        match self.start.as_ref().and_then(|s| s.head.as_deref()) {
+            Some(start) => format!("{}..{}", start, end),
+            None => end.to_string(),
+        }
+    }
+
+    /// Take the end snapshot and work out the commits and files of the session.
+    /// Returns the full hashes of the session's commits, oldest first.
+    pub fn finish(&mut self) -> Vec<String> {
+        self.end = GitSnapshot::capture(&self.repo_root);
+        match self.end.as_ref().and_then(|e| e.head.clone()) {
+            Some(end_head) => self.track_until(&end_head),
+            None => Vec::new(),
+        }
+    }
+
+    /// Work out the commits and files of a session that ended at `ended_at` without
+    /// an end snapshot: its range ends at the last commit made before then on the
+    /// branch it started on. No end snapshot is recorded, as the repository state
+    /// at that time is unknown.
+    pub fn finish_at(&mut self, ended_at: DateTime<Utc>) -> Vec<String> {
+        let before = format!("--before={}", ended_at.to_rfc3339());
+        let end_head = self.start.as_ref()
+            .and_then(|s| s.branch.as_deref())
+            .and_then(|branch| git_value(&self.repo_root, &["rev-list", "-1", &before, branch]))
+            .or_else(|| git_value(&self.repo_root, &["rev-list", "-1", &before, "HEAD"]));
+        match end_head {
+            Some(end_head) => self.track_until(&end_head),
+            None => Vec::new(),
+        }
+    }
+
+    fn track_until(&mut self, end_head: &str) -> Vec<String> {
+        if self.start.as_ref().and_then(|s| s.head.as_deref()) == Some(end_head) {
+            self.commit_range = None;
+            self.changed_files.clear();
+            return Vec::new();
+        }
+
+        let range = self.range_to(end_head);
+        self.changed_files = changed_files(&self.repo_root, &range);
+        self.commit_range = Some(range.clone());
+        commits_in(&self.repo_root, &range)
+    }
+}
+
+/// Full hashes of the commits in `range`, oldest first
+pub fn commits_in(repo: &Path, range: &str) -> Vec<String> {
+    git_value(repo, &["rev-list", "--reverse", range])
+        .map(|out| out.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Hash and subject of each commit in `range`, oldest first
+pub fn commit_summaries(repo: &Path, range: &str) -> Vec<(String, String)> {
+    git_value(repo, &["log", "--reverse", "--format=%H %s", range])
+        .map(|out| out.lines()
+            .filter_map(|line| line.split_once(' ').map(|(hash, subject)| (hash.to_string(), subject.to_string())))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// Files touched by the commits in `range`
+pub fn changed_files(repo: &Path, range: &str) -> Vec<String> {
+    let files: BTreeSet<String> = git_value(repo, &["log", "--name-only", "--format=", range])
+        .map(|out| out.lines().filter(|l| !l.is_empty()).map(str::to_string).collect())
+        .unwrap_or_default();
+    files.into_iter().collect()
+}
+
+/// The session a commit in `dir` belongs to: the one the assistant was launched for
+/// (`CI_SESSION_ID`), otherwise the newest active session tracking the same repository
+/// whose launching `ci` process is still waiting for it
+pub fn active_session(store: &SessionStore, dir: &Path) -> Option<SessionRecord> {
+    if let Some(record) = std::env::var(SESSION_ID_VAR).ok().and_then(|id| store.load(&id).ok()) {
+        return Some(record);
+    }
+
+    let repo_root = git_value(dir, &["rev-parse", "--show-toplevel"]).map(PathBuf::from)?;
+    store.all().ok()?.into_iter()
+        .filter(|r| r.status == SessionStatus::Active && r.launcher_pid.is_some_and(is_process_alive))
+        .find(|r| r.git.as_ref().is_some_and(|g| g.repo_root == repo_root))
+}
+
+/// `CI-Session:` and `CI-Agent:` trailers for a commit made during `record`
+pub fn commit_trailers(record: &SessionRecord) -> Vec<String> {
+    let agent = std::env::var("CI_AGENT_NAME").ok()
+        .filter(|agent| record.agents.contains(agent))
+        .unwrap_or_else(|| record.agents.join(", "));
+    let mut trailers = vec![format!("CI-Session: {}", record.id)];
+    if !agent.is_empty() {
+        trailers.push(format!("CI-Agent: {}", agent));
+    }
+    trailers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::git::{commit_all, git, init_test_repo};
+    use crate::helpers::session_store::SessionKind;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_provenance_tracks_commits_between_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        init_test_repo(repo);
+        fs::write(repo.join("README.md"), "readme").unwrap();
+        commit_all(repo, "Initial");
+
+        fs::create_dir_all(repo.join("src")).unwrap();
+        let mut provenance = GitProvenance::start(&repo.join("src")).unwrap();
+        let start = provenance.start.clone().unwrap();
+        assert_eq!(start.branch.as_deref(), Some("main"));
+        assert!(!start.dirty);
+
+        fs::write(repo.join("src/lib.rs"), "pub fn a() {}").unwrap();
+        commit_all(repo, "Add lib");
+        fs::write(repo.join("notes.md"), "wip").unwrap();
+
+        let commits = provenance.finish();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(provenance.changed_files, vec!["src/lib.rs"]);
+        assert_eq!(provenance.commit_range, Some(format!("{}..{}", start.head.unwrap(), commits[0])));
+        assert!(provenance.end.unwrap().dirty);
+    }
+
+    #[test]
+    fn test_finish_at_stops_at_session_end() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        init_test_repo(repo);
+        commit_all(repo, "Initial");
+
+        let mut provenance = GitProvenance::start(repo).unwrap();
+        fs::write(repo.join("during.md"), "during").unwrap();
+        commit_all(repo, "During");
+        let ended_at = Utc::now() + chrono::Duration::seconds(1);
+
+        // Committed after the session ended
+        fs::write(repo.join("after.md"), "after").unwrap();
+        git(repo, &["add", "."]).unwrap();
+        let status = Command::new("git").args(["commit", "-q", "-m", "After"]).current_dir(repo)
+            .env("GIT_COMMITTER_DATE", "2090-01-01T00:00:00Z")
+            .status().unwrap();
+        assert!(status.success());
+
+        let commits = provenance.finish_at(ended_at);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(provenance.changed_files, vec!["during.md"]);
+        assert!(provenance.end.is_none());
+    }
+
+    #[test]
+    fn test_active_session_and_trailers() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        init_test_repo(&repo);
+        let store = SessionStore::new(&temp_dir.path().join("ci"));
+
+        let mut finished = SessionRecord::new(SessionKind::Agent, vec!["Tester".to_string()]);
+        finished.git = GitProvenance::start(&repo);
+        finished.finish(true);
+        store.save(&finished).unwrap();
+        assert!(active_session(&store, &repo).is_none());
+
+        // Never finished, but nothing is waiting for it any more
+        let mut stale = SessionRecord::new(SessionKind::Agent, vec!["Tester".to_string()]);
+        stale.git = GitProvenance::start(&repo);
+        store.save(&stale).unwrap();
+        assert!(active_session(&store, &repo).is_none());
+
+        let mut active = SessionRecord::new(SessionKind::MultiAgent, vec!["Athena".to_string(), "Tester".to_string()]);
+        active.git = GitProvenance::start(&repo);
+        active.launcher_pid = Some(std::process::id());
+        store.save(&active).unwrap();
+
+        let found = active_session(&store, &repo).unwrap();
+        assert_eq!(found.id, active.id);
+        assert_eq!(commit_trailers(&found), vec![format!("CI-Session: {}", active.id), "CI-Agent: Athena, Tester".to_string()]);
+    }
+}
@@ -0,0 +1,231 @@
+//! Resuming stored sessions
+//!
+//! Turns a `SessionRecord` back into the arguments of the load path that
+//! started it, and writes the "previous session summary" handed to the
+//! resumed agents: task, files touched and commits made since the session
+//! began in its project.
+
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use crate::helpers::agent_supervisor::SupervisorState;
+use crate::helpers::git::git;
+use crate::helpers::session_store::{task_objective, ParallelSettings, SessionKind, SessionRecord};
+
+/// Most commits listed in a summary
+const MAX_SUMMARY_COMMITS: usize = 20;
+
+/// Most touched files listed in a summary
+const MAX_SUMMARY_FILES: usize = 50;
+
+/// Everything needed to relaunch a session through `ci load`
+#[derive(Debug, Clone)]
+pub struct ResumePlan {
+    /// Agent specs, with `Agent*N` for parallel instances
+    pub agents: Vec<String>,
+
+    /// Summary followed by the original context
+    pub context: String,
+
+    /// Task to assign when the original context does not already carry it
+    pub task: Option<String>,
+    pub parallel: bool,
+
+    /// Options the parallel session ran with, when recorded
+    pub parallel_settings: Option<ParallelSettings>,
+    pub bypass_permissions: bool,
+
+    /// Directory the session worked in, if it still exists
+    pub cwd: Option<PathBuf>,
+}
+
+impl ResumePlan {
+    pub fn from_record(record: &SessionRecord) -> Result<Self> {
+        if record.kind == SessionKind::Topology {
+            return Err(anyhow!("Session {} is a topology commit session; continue it with 'ci topologist'", record.id));
+        }
+        if record.agents.is_empty() {
+            return Err(anyhow!("Session {} has no agents to resume", record.id));
+        }
+
+        let mut context = SessionSummary::collect(record).render(record);
+        let original = record.context.clone()
+            .or_else(|| record.description.clone().filter(|_| record.kind == SessionKind::Manual));
+        if let Some(original) = &original {
+            context.push_str("\n\n");
+            context.push_str(original);
+        }
+
+        // Contexts built for `ci load --task` already carry the assignment
+        let task = record.task.clone()
+            .filter(|_| original.as_deref().and_then(task_objective).is_none());
+
+        let parallel = record.kind == SessionKind::Parallel;
+        let agents = if parallel {
+            parallel_agent_specs(record).unwrap_or_else(|| record.agents.clone())
+        } else {
+            record.agents.clone()
+        };
+
+        Ok(ResumePlan {
+            agents,
+            context,
+            task,
+            parallel,
+            parallel_settings: record.parallel.clone().filter(|_| parallel),
+            bypass_permissions: record.bypass_permissions,
+            cwd: record.project.clone().filter(|dir| dir.is_dir()),
+        })
+    }
+}
+
+/// What happened in a session's project since it started
+#[derive(Debug, Clone, Default)]
+pub struct SessionSummary {
+    /// `<short hash> <subject>`, oldest first
+    pub commits: Vec<String>,
+
+    /// Files changed by those commits or left uncommitted
+    pub files: Vec<String>,
+}
+
+impl SessionSummary {
+    pub fn collect(record: &SessionRecord) -> Self {
+        let Some(project) = record.project.as_deref().filter(|dir| dir.is_dir()) else {
+            return SessionSummary::default();
+        };
+
+        let since = record.started_at.to_rfc3339();
+        let commits: Vec<String> = git(project, &["log", "--reverse", "--format=%h %s", &format!("--since={}", since)])
+            .map(|out| out.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+
+        let mut files = BTreeSet::new();
+        if let Ok(out) = git(project, &["log", "--name-only", "--format=", &format!("--since={}", since)]) {
+            files.extend(out.lines().filter(|l| !l.is_empty()).map(str::to_string));
+        }
+        if let Ok(out) = git(project, &["status", "--porcelain"]) {
+            files.extend(out.lines().filter_map(|l| l.get(3..)).map(|p| p.rsplit(" -> ").next().unwrap_or(p).to_string()));
+        }
+
+        SessionSummary { commits, files: files.into_iter().collect() }
+    }
+
+    /// Markdown block prepended to the resumed session's context
+    pub fn render(&self, record: &SessionRecord) -> String {
+        let mut out = String::from("# Previous Session Summary\n\n");
+        out.push_str("You are resuming an earlier session. Review what was done and continue from there.\n\n");
+        out.push_str(&format!("- **Session**: {} ({})\n", record.id, record.kind.label()));
+        out.push_str(&format!("- **Agents**: {}\n", record.agents.join(", ")));
+        out.push_str(&format!("- **Started**: {}\n", record.started_at.format("%Y-%m-%d %H:%M UTC")));
+        if let Some(ended) = record.ended_at {
+            out.push_str(&format!("- **Ended**: {} ({})\n", ended.format("%Y-%m-%d %H:%M UTC"), record.status));
+        }
+        if let Some(task) = &record.task {
+            out.push_str(&format!("- **Task**: {}\n", task));
+        }
+        if let Some(project) = &record.project {
+            out.push_str(&format!("- **Project**: {}\n", project.display()));
+        }
+
+        out.push_str("\n## Commits Since Start\n\n");
+        if self.commits.is_empty() {
+            out.push_str("None.\n");
+        }
+        for commit in self.commits.iter().take(MAX_SUMMARY_COMMITS) {
+            out.push_str(&format!("- {}\n", commit));
+        }
+        if self.commits.len() > MAX_SUMMARY_COMMITS {
+            out.push_str(&format!("- ... and {} more\n", self.commits.len() - MAX_SUMMARY_COMMITS));
+        }
+
+        out.push_str("\n## Files Touched\n\n");
+        if self.files.is_empty() {
+            out.push_str("None.\n");
+        }
+        for file in self.files.iter().take(MAX_SUMMARY_FILES) {
+            out.push_str(&format!("- {}\n", file));
+        }
+        if self.files.len() > MAX_SUMMARY_FILES {
+            out.push_str(&format!("- ... and {} more\n", self.files.len() - MAX_SUMMARY_FILES));
+        }
+        out
+    }
+}
+
+/// `Agent*N` specs rebuilt from the instances of a parallel session
+fn parallel_agent_specs(record: &SessionRecord) -> Option<Vec<String>> {
+    let state = SupervisorState::load(record.memory_path.as_deref()?).ok()?;
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for instance in &state.instances {
+        *counts.entry(instance.agent_name.as_str()).or_default() += 1;
+    }
+    Some(counts.into_iter()
+        .map(|(agent, count)| if count > 1 { format!("{}*{}", agent, count) } else { agent.to_string() })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::git::{commit_all, init_test_repo};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_summary_lists_commits_and_files_since_start() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        init_test_repo(repo);
+
+        let mut record = SessionRecord::new(SessionKind::Agent, vec!["Athena".to_string()]);
+        record.project = Some(repo.to_path_buf());
+        record.task = Some("Fix login".to_string());
+        record.started_at -= chrono::Duration::minutes(1);
+
+        fs::write(repo.join("login.rs"), "fn login() {}").unwrap();
+        commit_all(repo, "Fix login redirect");
+        fs::write(repo.join("notes.md"), "todo").unwrap();
+
+        let summary = SessionSummary::collect(&record);
+        assert_eq!(summary.commits.len(), 1);
+        assert!(summary.commits[0].ends_with("Fix login redirect"));
+        assert_eq!(summary.files, vec!["login.rs", "notes.md"]);
+
+        let rendered = summary.render(&record);
+        assert!(rendered.starts_with("# Previous Session Summary"));
+        assert!(rendered.contains("- **Task**: Fix login"));
+        assert!(rendered.contains("- notes.md"));
+    }
+
+    #[test]
+    fn test_plan_keeps_embedded_task_assignment() {
+        let mut record = SessionRecord::new(SessionKind::MultiAgent, vec!["Athena".to_string(), "Tester".to_string()]);
+        record.context = Some("# Agent Task Assignment\n\n**OBJECTIVE**: Ship it\n".to_string());
+        record.task = Some("Ship it".to_string());
+        record.bypass_permissions = true;
+        record.project = Some(PathBuf::from("/nonexistent/project"));
+
+        let plan = ResumePlan::from_record(&record).unwrap();
+        assert_eq!(plan.agents, vec!["Athena", "Tester"]);
+        assert_eq!(plan.task, None);
+        assert!(plan.context.starts_with("# Previous Session Summary"));
+        assert!(plan.context.ends_with("**OBJECTIVE**: Ship it\n"));
+        assert!(plan.bypass_permissions && !plan.parallel && plan.cwd.is_none());
+
+        record.context = None;
+        assert_eq!(ResumePlan::from_record(&record).unwrap().task.as_deref(), Some("Ship it"));
+
+        // A parallel session comes back with the options it ran with
+        let settings = ParallelSettings { worktrees: true, shard_by: Some("dirs".to_string()), ..Default::default() };
+        record.kind = SessionKind::Parallel;
+        record.parallel = Some(settings.clone());
+        let plan = ResumePlan::from_record(&record).unwrap();
+        assert!(plan.parallel);
+        assert_eq!(plan.parallel_settings, Some(settings));
+
+        record.kind = SessionKind::Topology;
+        assert!(ResumePlan::from_record(&record).is_err());
+    }
+}
@@ -0,0 +1,530 @@
+//! Session retention
+//!
+//! Decides which stored sessions `ci session cleanup` removes under the
+//! retention policy of `.ci-config.json`, packs the files of archived sessions
+//! into gzipped tarballs and keeps removed sessions in a trash area inside the
+//! store, from which they can be restored until their grace period runs out.
+//!
+//! Only files inside the CI repository are ever touched: the memory, output
+//! and transcript files a record points to, its legacy source and its store
+//! directory.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::RetentionConfig;
+use crate::helpers::session_store::{SessionRecord, SessionStatus, SessionStore};
+
+/// Tarball an archived session's files are packed into, inside its store directory
+pub const ARCHIVE_FILE: &str = "archive.tar.gz";
+
+const TRASH_MANIFEST: &str = "manifest.json";
+const TRASH_FILES: &str = "files";
+
+/// Retention limits of `RetentionConfig` with the size limit parsed
+#[derive(Debug, Clone)]
+pub struct RetentionPolicy {
+    pub keep_last_per_agent: Option<usize>,
+    pub max_total_bytes: Option<u64>,
+    pub max_age_days: Option<u64>,
+    pub keep_tagged: bool,
+    pub keep_archived: bool,
+    pub compress_archived: bool,
+    pub trash_days: u64,
+}
+
+impl RetentionPolicy {
+    pub fn from_config(config: &RetentionConfig) -> Result<Self> {
+        for (key, days) in [("max_age_days", config.max_age_days), ("trash_days", Some(config.trash_days))] {
+            if let Some(days) = days {
+                days_before(Utc::now(), days).with_context(|| format!("Invalid retention {}", key))?;
+            }
+        }
+
+        Ok(RetentionPolicy {
+            keep_last_per_agent: config.keep_last_per_agent,
+            max_total_bytes: config.max_total_size.as_deref().map(parse_size).transpose()?,
+            max_age_days: config.max_age_days,
+            keep_tagged: config.keep_tagged,
+            keep_archived: config.keep_archived,
+            compress_archived: config.compress_archived,
+            trash_days: config.trash_days,
+        })
+    }
+
+    /// Whether the policy never removes `record`
+    pub fn protects(&self, record: &SessionRecord) -> bool {
+        record.status == SessionStatus::Active
+            || (self.keep_tagged && !record.tags.is_empty())
+            || (self.keep_archived && record.status == SessionStatus::Archived)
+    }
+}
+
+/// The instant `days` days before `now`
+pub fn days_before(now: DateTime<Utc>, days: u64) -> Result<DateTime<Utc>> {
+    i64::try_from(days).ok()
+        .and_then(Duration::try_days)
+        .and_then(|age| now.checked_sub_signed(age))
+        .ok_or_else(|| anyhow!("{} days is out of range", days))
+}
+
+/// Size such as `500MB`, `1.5GB` or `4096` (bytes)
+pub fn parse_size(value: &str) -> Result<u64> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let invalid = || anyhow!("Invalid size '{}' (use e.g. 500MB or 2GB)", value);
+
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+    let multiplier: u64 = match unit.trim().to_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" => 1024,
+        "M" | "MB" => 1024 * 1024,
+        "G" | "GB" => 1024 * 1024 * 1024,
+        _ => return Err(invalid()),
+    };
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// A stored session with the files it owns on disk
+#[derive(Debug, Clone)]
+pub struct SessionUsage {
+    pub record: SessionRecord,
+
+    /// Files and directories owned by the session, none inside another
+    pub paths: Vec<PathBuf>,
+
+    /// What compression packs: the owned paths, with the store directory
+    /// replaced by its contents so the tarball can live there
+    pub packable: Vec<PathBuf>,
+
+    /// Size of the owned paths and the record itself
+    pub bytes: u64,
+}
+
+impl SessionUsage {
+    pub fn measure(record: SessionRecord, store: &SessionStore, ci_root: &Path) -> Self {
+        let root = ci_root.canonicalize().unwrap_or_else(|_| ci_root.to_path_buf());
+        let session_dir = store.session_dir(&record.id).canonicalize().ok();
+
+        let source = record.source.as_deref().filter(|s| !s.contains('#')).map(PathBuf::from);
+        let mut candidates: Vec<PathBuf> = [
+            record.memory_path.clone(),
+            record.output_path.clone(),
+            record.transcript_path.clone(),
+            record.archive_path.clone(),
+            source,
+            session_dir.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|path| path.canonicalize().ok())
+        .filter(|path| owned_by_session(path, &root))
+        .collect();
+        candidates.sort();
+        candidates.dedup();
+
+        // Drop paths inside another owned directory
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for path in candidates {
+            if !paths.iter().any(|kept| path.starts_with(kept)) {
+                paths.push(path);
+            }
+        }
+
+        let mut packable = Vec::new();
+        for path in &paths {
+            if Some(path) == session_dir.as_ref() {
+                packable.extend(fs::read_dir(path).into_iter()
+                    .flat_map(|entries| entries.flatten())
+                    .map(|entry| entry.path())
+                    .filter(|p| p.file_name().is_none_or(|name| name != ARCHIVE_FILE)));
+            } else {
+                packable.push(path.clone());
+            }
+        }
+        packable.sort();
+
+        let bytes = paths.iter().map(|p| disk_usage(p)).sum::<u64>() + disk_usage(&store.record_path(&record.id));
+        SessionUsage { record, paths, packable, bytes }
+    }
+}
+
+/// Paths below the top level of the CI repository; never the repository or a top-level directory
+fn owned_by_session(path: &Path, root: &Path) -> bool {
+    path.strip_prefix(root).is_ok_and(|relative| relative.components().count() >= 2)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionAction {
+    /// Move the session to the trash
+    Remove,
+
+    /// Pack the files of an archived session into a tarball
+    Compress,
+}
+
+impl RetentionAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RetentionAction::Remove => "remove",
+            RetentionAction::Compress => "compress",
+        }
+    }
+}
+
+/// What cleanup does with one session and why
+#[derive(Debug, Clone)]
+pub struct PlannedAction {
+    pub usage: SessionUsage,
+    pub action: RetentionAction,
+    pub reason: String,
+}
+
+/// Sessions the policy removes or compresses; everything else is kept.
+/// Age and per-agent limits apply first, then the oldest remaining sessions
+/// are removed until the total size fits.
+pub fn plan(usages: Vec<SessionUsage>, policy: &RetentionPolicy, now: DateTime<Utc>) -> Vec<PlannedAction> {
+    let mut reasons: Vec<Option<String>> = vec![None; usages.len()];
+
+    // Newest first, so per-agent ranks count from the latest session
+    let mut order: Vec<usize> = (0..usages.len()).collect();
+    order.sort_by(|&a, &b| usages[b].record.started_at.cmp(&usages[a].record.started_at));
+
+    if let Some(days) = policy.max_age_days {
+        // `from_config` rejects ages too large to subtract; nothing is that old
+        let cutoff = days_before(now, days).unwrap_or(DateTime::<Utc>::MIN_UTC);
+        for &i in &order {
+            let record = &usages[i].record;
+            if !policy.protects(record) && record.started_at < cutoff {
+                reasons[i] = Some(format!("older than {} days", days));
+            }
+        }
+    }
+
+    if let Some(keep) = policy.keep_last_per_agent {
+        let mut ranks: HashMap<&str, usize> = HashMap::new();
+        for &i in &order {
+            let record = &usages[i].record;
+            let mut within = record.agents.is_empty();
+            for agent in &record.agents {
+                let rank = ranks.entry(agent.as_str()).or_default();
+                within |= *rank < keep;
+                *rank += 1;
+            }
+            if !within && !policy.protects(record) && reasons[i].is_none() {
+                reasons[i] = Some(format!("beyond the newest {} for {}", keep, record.agents.join(", ")));
+            }
+        }
+    }
+
+    if let Some(max_bytes) = policy.max_total_bytes {
+        let mut total: u64 = usages.iter().zip(&reasons).filter(|(_, r)| r.is_none()).map(|(u, _)| u.bytes).sum();
+        for &i in order.iter().rev() {
+            if total <= max_bytes {
+                break;
+            }
+            if reasons[i].is_none() && !policy.protects(&usages[i].record) {
+                reasons[i] = Some("over the total size limit".to_string());
+                total -= usages[i].bytes;
+            }
+        }
+    }
+
+    usages.into_iter()
+        .zip(reasons)
+        .filter_map(|(usage, reason)| match reason {
+            Some(reason) => Some(PlannedAction { usage, action: RetentionAction::Remove, reason }),
+            None if policy.compress_archived
+                && usage.record.status == SessionStatus::Archived
+                && usage.record.archive_path.is_none()
+                && !usage.packable.is_empty() =>
+                Some(PlannedAction { usage, action: RetentionAction::Compress, reason: "archived".to_string() }),
+            None => None,
+        })
+        .collect()
+}
+
+/// Pack the files of a session into `<store>/<id>/archive.tar.gz`, named relative
+/// to the CI repository, and delete them. Returns the tarball and the bytes reclaimed.
+pub fn compress(usage: &SessionUsage, store: &SessionStore, ci_root: &Path) -> Result<(PathBuf, u64)> {
+    let root = ci_root.canonicalize().unwrap_or_else(|_| ci_root.to_path_buf());
+    let session_dir = store.session_dir(&usage.record.id);
+    fs::create_dir_all(&session_dir)?;
+    let archive_path = session_dir.join(ARCHIVE_FILE);
+
+    let packed_bytes: u64 = usage.packable.iter().map(|p| disk_usage(p)).sum();
+    let file = fs::File::create(&archive_path)
+        .with_context(|| format!("Failed to create {}", archive_path.display()))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    for path in &usage.packable {
+        let name = path.strip_prefix(&root).unwrap_or(path);
+        if path.is_dir() {
+            archive.append_dir_all(name, path)?;
+        } else {
+            archive.append_path_with_name(path, name)?;
+        }
+    }
+    archive.into_inner()?.finish()?;
+
+    for path in &usage.packable {
+        remove_path(path)?;
+    }
+    Ok((archive_path.clone(), packed_bytes.saturating_sub(disk_usage(&archive_path))))
+}
+
+/// A removed session waiting in the trash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    pub title: String,
+    pub trashed_at: DateTime<Utc>,
+    pub bytes: u64,
+
+    /// Name of each file inside the entry with its original location
+    pub files: Vec<(String, PathBuf)>,
+}
+
+/// Removed sessions, one directory per session with a manifest of where its files came from
+pub struct Trash {
+    dir: PathBuf,
+}
+
+impl Trash {
+    pub fn new(store: &SessionStore) -> Self {
+        Trash { dir: store.trash_dir() }
+    }
+
+    /// Move a session's files and record into the trash
+    pub fn put(&self, usage: &SessionUsage, store: &SessionStore) -> Result<TrashEntry> {
+        let entry_dir = self.dir.join(&usage.record.id);
+        let files_dir = entry_dir.join(TRASH_FILES);
+        fs::create_dir_all(&files_dir)
+            .with_context(|| format!("Failed to create trash directory {}", files_dir.display()))?;
+
+        let originals: Vec<PathBuf> = usage.paths.iter().cloned()
+            .chain(std::iter::once(store.record_path(&usage.record.id)))
+            .collect();
+        let entry = TrashEntry {
+            id: usage.record.id.clone(),
+            title: usage.record.title(),
+            trashed_at: Utc::now(),
+            bytes: usage.bytes,
+            files: originals.into_iter()
+                .enumerate()
+                .map(|(index, path)| (format!("{}_{}", index, file_name(&path)), path))
+                .collect(),
+        };
+        // The manifest goes first so a failed move can still be restored
+        fs::write(entry_dir.join(TRASH_MANIFEST), serde_json::to_string_pretty(&entry)?)?;
+
+        for (name, original) in &entry.files {
+            move_path(original, &files_dir.join(name))?;
+        }
+        Ok(entry)
+    }
+
+    /// Trashed sessions, oldest first
+    pub fn entries(&self) -> Vec<TrashEntry> {
+        let mut entries: Vec<TrashEntry> = fs::read_dir(&self.dir).into_iter()
+            .flat_map(|entries| entries.flatten())
+            .filter_map(|entry| fs::read_to_string(entry.path().join(TRASH_MANIFEST)).ok())
+            .filter_map(|content| serde_json::from_str(&content).ok())
+            .collect();
+        entries.sort_by_key(|entry| entry.trashed_at);
+        entries
+    }
+
+    /// Move a trashed session (by id or unique prefix) back to where it was
+    pub fn restore(&self, id: &str) -> Result<TrashEntry> {
+        let matches: Vec<TrashEntry> = self.entries().into_iter().filter(|e| e.id.starts_with(id)).collect();
+        let entry = match matches.len() {
+            0 => return Err(anyhow!("Session '{}' is not in the trash", id)),
+            1 => matches.into_iter().next().unwrap(),
+            n => return Err(anyhow!("Session id '{}' is ambiguous ({} trashed sessions match)", id, n)),
+        };
+
+        if let Some((_, taken)) = entry.files.iter().find(|(_, original)| original.exists()) {
+            return Err(anyhow!("Cannot restore session {}: {} exists again", entry.id, taken.display()));
+        }
+        let entry_dir = self.dir.join(&entry.id);
+        for (name, original) in &entry.files {
+            let trashed = entry_dir.join(TRASH_FILES).join(name);
+            if !trashed.exists() {
+                continue;
+            }
+            if let Some(parent) = original.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            move_path(&trashed, original)?;
+        }
+        fs::remove_dir_all(&entry_dir)?;
+        Ok(entry)
+    }
+
+    /// Delete the sessions trashed more than `days` days before `now`
+    pub fn purge_expired(&self, days: u64, now: DateTime<Utc>) -> Result<Vec<TrashEntry>> {
+        let cutoff = days_before(now, days)?;
+        let expired: Vec<TrashEntry> = self.entries().into_iter().filter(|e| e.trashed_at < cutoff).collect();
+        for entry in &expired {
+            fs::remove_dir_all(self.dir.join(&entry.id))
+                .with_context(|| format!("Failed to purge trashed session {}", entry.id))?;
+        }
+        Ok(expired)
+    }
+}
+
+/// Bytes used by a file or directory tree
+pub fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path).into_iter()
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| disk_usage(&entry.path()))
+        .sum()
+}
+
+/// Rename, falling back to copy and delete across file systems
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_path(from, to).with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+    remove_path(from)
+}
+
+fn copy_path(from: &Path, to: &Path) -> Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)?.flatten() {
+            copy_path(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+    .with_context(|| format!("Failed to remove {}", path.display()))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::session_store::SessionKind;
+    use flate2::read::GzDecoder;
+    use tempfile::TempDir;
+
+    fn usage(agent: &str, days_ago: i64, bytes: u64) -> SessionUsage {
+        let mut record = SessionRecord::new(SessionKind::Agent, vec![agent.to_string()]);
+        record.started_at = Utc::now() - Duration::days(days_ago);
+        record.status = SessionStatus::Completed;
+        SessionUsage { record, paths: Vec::new(), packable: vec![PathBuf::from("memory.md")], bytes }
+    }
+
+    #[test]
+    fn test_plan_applies_limits_and_protections() {
+        let policy = RetentionPolicy::from_config(&RetentionConfig {
+            keep_last_per_agent: Some(2),
+            max_total_size: Some("1KB".to_string()),
+            max_age_days: Some(90),
+            compress_archived: true,
+            ..Default::default()
+        }).unwrap();
+        let oversized = RetentionConfig { trash_days: u64::MAX, ..Default::default() };
+        assert!(RetentionPolicy::from_config(&oversized).unwrap_err().to_string().contains("trash_days"));
+
+        let mut usages = vec![
+            usage("Athena", 1, 300),
+            usage("Athena", 2, 300),
+            usage("Athena", 3, 100),
+            usage("Athena", 5, 50),
+            usage("Tester", 4, 500),
+            usage("Tester", 120, 10),
+            usage("Tester", 150, 10),
+        ];
+        usages[2].record.tags = vec!["release".to_string()];
+        usages[6].record.status = SessionStatus::Archived;
+        let ids: Vec<String> = usages.iter().map(|u| u.record.id.clone()).collect();
+
+        let actions = plan(usages, &policy, Utc::now());
+        let summary: Vec<(usize, RetentionAction, &str)> = actions.iter()
+            .map(|a| (ids.iter().position(|id| id == &a.usage.record.id).unwrap(), a.action, a.reason.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            (3, RetentionAction::Remove, "beyond the newest 2 for Athena"),
+            (4, RetentionAction::Remove, "over the total size limit"),
+            (5, RetentionAction::Remove, "older than 90 days"),
+            (6, RetentionAction::Compress, "archived"),
+        ]);
+
+        assert_eq!(parse_size("1.5MB").unwrap(), 1572864);
+        assert_eq!(parse_size("2048").unwrap(), 2048);
+        assert!(parse_size("lots").is_err());
+    }
+
+    #[test]
+    fn test_trash_restore_and_compress() {
+        let temp_dir = TempDir::new().unwrap();
+        let ci_root = temp_dir.path();
+        let store = SessionStore::new(ci_root);
+        let agents_dir = ci_root.join("AGENTS/Athena");
+        fs::create_dir_all(&agents_dir).unwrap();
+
+        let memory = agents_dir.join("working_1.md");
+        fs::write(&memory, "memory").unwrap();
+        let mut record = SessionRecord::new(SessionKind::Agent, vec!["Athena".to_string()]);
+        record.memory_path = Some(memory.clone());
+        // Files outside a session of their own are never touched
+        record.output_path = Some(ci_root.join("AGENTS"));
+        store.save(&record).unwrap();
+
+        let measured = SessionUsage::measure(record.clone(), &store, ci_root);
+        assert_eq!(measured.paths, vec![memory.canonicalize().unwrap()]);
+
+        let trash = Trash::new(&store);
+        trash.put(&measured, &store).unwrap();
+        assert!(!memory.exists() && store.load(&record.id).is_err());
+        assert_eq!(trash.entries().len(), 1);
+        assert!(trash.purge_expired(7, Utc::now()).unwrap().is_empty());
+
+        trash.restore(&record.id[..6]).unwrap();
+        assert_eq!(fs::read_to_string(&memory).unwrap(), "memory");
+        assert_eq!(store.load(&record.id).unwrap().id, record.id);
+        assert!(trash.entries().is_empty());
+
+        let measured = SessionUsage::measure(record.clone(), &store, ci_root);
+        let (archive_path, _) = compress(&measured, &store, ci_root).unwrap();
+        assert!(!memory.exists());
+        let names: Vec<String> = tar::Archive::new(GzDecoder::new(fs::File::open(&archive_path).unwrap()))
+            .entries().unwrap()
+            .map(|e| e.unwrap().path().unwrap().display().to_string())
+            .collect();
+        assert_eq!(names, vec!["AGENTS/Athena/working_1.md"]);
+
+        trash.put(&SessionUsage::measure(record, &store, ci_root), &store).unwrap();
+        assert!(trash.purge_expired(u64::MAX, Utc::now()).is_err());
+        assert_eq!(trash.purge_expired(7, Utc::now() + Duration::days(8)).unwrap().len(), 1);
+        assert!(trash.entries().is_empty());
+    }
+}
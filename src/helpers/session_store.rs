@@ -0,0 +1,744 @@
+//! Unified session store
+//!
+//! Every kind of session — agents launched by `ci load` and `ci task`,
+//! parallel sessions, sessions created with `ci session create` and
+//! topology commit sessions — is recorded here with one schema:
+//!
+//! ```text
+//! <CI root>/.ci-sessions/
+//!   <id>.json     SessionRecord
+//!   <id>/         files produced by the session (transcripts, reports, archive)
+//!   .migrated     marker written once legacy records were imported
+//!   .trash/       sessions removed by `ci session cleanup`, restorable for a while
+//! ```
+//!
+//! The legacy files (`AGENTS/<Agent>/sessions/*.json`, `AGENTS/*_session.json`,
+//! `AGENTS/<Agent>/Sessions/<name>/metadata.json`, parallel coordination
+//! directories and `.ci-topology/commit_history.json`) are still written by
+//! their subsystems; each record points back to its origin through `source`.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::helpers::agent_stats::AgentSession;
+use crate::helpers::agent_supervisor::{InstanceStatus, SupervisorState};
+use crate::helpers::path::get_ci_root;
+use crate::helpers::session_provenance::{GitProvenance, GitSnapshot};
+use crate::topology::SessionHistory;
+
+/// Directory of the store inside the CI repository
+pub const STORE_DIR: &str = ".ci-sessions";
+
+/// Version of the `SessionRecord` schema
+pub const SCHEMA_VERSION: u32 = 1;
+
+const MIGRATED_MARKER: &str = ".migrated";
+
+const TRASH_DIR: &str = ".trash";
+
+/// What launched a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionKind {
+    /// One agent loaded with `ci load` or `ci task`
+    Agent,
+    /// Several agents combined into one session
+    MultiAgent,
+    /// Instances launched with `ci load --parallel`
+    Parallel,
+    /// `ci task --headless`
+    Headless,
+    /// Created with `ci session create`
+    Manual,
+    /// A `ci topologist` commit session
+    Topology,
+}
+
+impl SessionKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionKind::Agent => "agent",
+            SessionKind::MultiAgent => "multi-agent",
+            SessionKind::Parallel => "parallel",
+            SessionKind::Headless => "headless",
+            SessionKind::Manual => "manual",
+            SessionKind::Topology => "topology",
+        }
+    }
+}
+
+/// Lifecycle state of a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionStatus {
+    Active,
+    Completed,
+    Failed,
+    Archived,
+    /// Ended at an unknown time: imported without an end time, or handed to an
+    /// assistant started by hand
+    Unknown,
+}
+
+impl SessionStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionStatus::Active => "active",
+            SessionStatus::Completed => "completed",
+            SessionStatus::Failed => "failed",
+            SessionStatus::Archived => "archived",
+            SessionStatus::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for SessionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+impl FromStr for SessionStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "active" => Ok(SessionStatus::Active),
+            "completed" => Ok(SessionStatus::Completed),
+            "failed" => Ok(SessionStatus::Failed),
+            "archived" => Ok(SessionStatus::Archived),
+            "unknown" => Ok(SessionStatus::Unknown),
+            _ => Err(format!("Unknown session status '{}' (use active, completed, failed, archived or unknown)", value)),
+        }
+    }
+}
+
+/// How a parallel session ran its instances (`ci load --parallel` options)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParallelSettings {
+    #[serde(default)]
+    pub wait: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub restart_on_failure: bool,
+    #[serde(default)]
+    pub max_restarts: u32,
+    #[serde(default)]
+    pub worktrees: bool,
+    /// Strategy as given to `--shard-by`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_by: Option<String>,
+}
+
+/// One session, whatever subsystem started it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub schema_version: u32,
+    pub id: String,
+    pub kind: SessionKind,
+    pub status: SessionStatus,
+
+    /// Agents taking part (agent names, not instance names)
+    pub agents: Vec<String>,
+
+    /// Project directory the session worked in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<PathBuf>,
+
+    pub started_at: DateTime<Utc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+
+    /// Human-readable name (manual session name, parallel session id)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Launched with permission prompts bypassed (`--allow`, `--autonomous`)
+    #[serde(default)]
+    pub bypass_permissions: bool,
+
+    /// Session this one was resumed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resumed_from: Option<String>,
+
+    /// Options of a parallel session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parallel: Option<ParallelSettings>,
+
+    /// `ci` process that launched the session and waits for it to end
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launcher_pid: Option<u32>,
+
+    /// Memory file or directory handed to the assistant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_path: Option<PathBuf>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_path: Option<PathBuf>,
+
+    /// Plain-text transcript recorded with `ci load --record`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript_path: Option<PathBuf>,
+
+    /// Gzipped tarball the session's files were packed into by `ci session cleanup`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<PathBuf>,
+
+    /// Commits made during the session, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commits: Vec<String>,
+
+    /// Repository state at start and end of the session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitProvenance>,
+
+    /// Legacy record this one mirrors: a file or directory, with `#<key>` for
+    /// entries inside a shared file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl SessionRecord {
+    pub fn new(kind: SessionKind, agents: Vec<String>) -> Self {
+        SessionRecord {
+            schema_version: SCHEMA_VERSION,
+            id: new_id(),
+            kind,
+            status: SessionStatus::Active,
+            agents,
+            project: None,
+            started_at: Utc::now(),
+            ended_at: None,
+            name: None,
+            task: None,
+            context: None,
+            description: None,
+            tags: Vec::new(),
+            bypass_permissions: false,
+            resumed_from: None,
+            parallel: None,
+            launcher_pid: None,
+            memory_path: None,
+            output_path: None,
+            transcript_path: None,
+            archive_path: None,
+            commits: Vec::new(),
+            git: None,
+            source: None,
+        }
+    }
+
+    /// Mark the session finished now, taking the end snapshot of its repository
+    pub fn finish(&mut self, success: bool) {
+        self.ended_at = Some(Utc::now());
+        self.status = if success { SessionStatus::Completed } else { SessionStatus::Failed };
+        self.finish_git();
+    }
+
+    /// Take the end snapshot of the session's repository unless it was taken already
+    pub fn finish_git(&mut self) {
+        if let Some(git) = self.git.as_mut().filter(|g| g.end.is_none()) {
+            self.commits = git.finish();
+        }
+    }
+
+    /// Work out the commits of a session that ended without an end snapshot from
+    /// its end time, unless that was done already
+    pub fn finish_git_at_end_time(&mut self) {
+        let Some(ended_at) = self.ended_at else { return };
+        if let Some(git) = self.git.as_mut().filter(|g| g.end.is_none() && g.commit_range.is_none()) {
+            self.commits = git.finish_at(ended_at);
+        }
+    }
+
+    /// Whether the session may still be running, so its commits reach up to `HEAD`
+    pub fn is_running(&self) -> bool {
+        self.status == SessionStatus::Active && self.ended_at.is_none()
+    }
+
+    pub fn duration_secs(&self) -> Option<u64> {
+        u64::try_from((self.ended_at? - self.started_at).num_seconds()).ok()
+    }
+
+    /// Task, name or description, whichever describes the session best
+    pub fn title(&self) -> String {
+        self.task.clone()
+            .or_else(|| self.name.clone())
+            .or_else(|| self.description.clone().filter(|d| !d.is_empty()))
+            .unwrap_or_else(|| format!("{} session", self.kind.label()))
+    }
+}
+
+/// Filters for `SessionStore::query`; unset fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct SessionQuery {
+    /// Substring of an agent name
+    pub agent: Option<String>,
+
+    /// Project directory, or a substring of it
+    pub project: Option<String>,
+    pub status: Option<SessionStatus>,
+    pub kind: Option<SessionKind>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub tag: Option<String>,
+
+    /// Newest N sessions only
+    pub limit: Option<usize>,
+}
+
+impl SessionQuery {
+    pub fn matches(&self, record: &SessionRecord) -> bool {
+        let lower = |s: &str| s.to_lowercase();
+        self.agent.as_ref().is_none_or(|agent| record.agents.iter().any(|a| lower(a).contains(&lower(agent))))
+            && self.project.as_ref().is_none_or(|project| record.project.as_ref().is_some_and(|p| {
+                let wanted = Path::new(project).canonicalize().ok();
+                wanted.is_some_and(|w| p == &w) || p.to_string_lossy().contains(project.as_str())
+            }))
+            && self.status.is_none_or(|status| record.status == status)
+            && self.kind.is_none_or(|kind| record.kind == kind)
+            && self.since.is_none_or(|since| record.started_at >= since)
+            && self.until.is_none_or(|until| record.started_at <= until)
+            && self.tag.as_ref().is_none_or(|tag| record.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+    }
+}
+
+/// What a migration imported
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+    pub imported: Vec<SessionRecord>,
+    pub skipped: usize,
+}
+
+/// Directory of session records
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    /// Store of the CI repository at `ci_root`
+    pub fn new(ci_root: &Path) -> Self {
+        SessionStore { dir: ci_root.join(STORE_DIR) }
+    }
+
+    /// Store of the configured CI repository
+    pub fn open() -> Result<Self> {
+        Ok(Self::new(&get_ci_root()?))
+    }
+
+    pub fn save(&self, record: &SessionRecord) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create session store {}", self.dir.display()))?;
+        let path = self.record_path(&record.id);
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, serde_json::to_string_pretty(record)?)?;
+        fs::rename(&temp, &path)
+            .with_context(|| format!("Failed to write session record {}", path.display()))
+    }
+
+    /// Directory for files produced by a session
+    pub fn session_dir(&self, id: &str) -> PathBuf {
+        self.dir.join(id)
+    }
+
+    /// File holding the record of a session
+    pub fn record_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    /// Where `ci session cleanup` keeps removed sessions until they expire
+    pub fn trash_dir(&self) -> PathBuf {
+        self.dir.join(TRASH_DIR)
+    }
+
+    /// Record by id or unique id prefix
+    pub fn load(&self, id: &str) -> Result<SessionRecord> {
+        let exact = self.record_path(id);
+        if exact.exists() {
+            return read_record(&exact);
+        }
+
+        let matches: Vec<SessionRecord> = self.all()?.into_iter().filter(|r| r.id.starts_with(id)).collect();
+        match matches.len() {
+            0 => Err(anyhow!("Session '{}' not found", id)),
+            1 => Ok(matches.into_iter().next().unwrap()),
+            n => Err(anyhow!("Session id '{}' is ambiguous ({} sessions match)", id, n)),
+        }
+    }
+
+    /// Apply `change` to a stored record and save it
+    pub fn update(&self, id: &str, change: impl FnOnce(&mut SessionRecord)) -> Result<SessionRecord> {
+        let mut record = self.load(id)?;
+        change(&mut record);
+        self.save(&record)?;
+        Ok(record)
+    }
+
+    /// Every readable record, newest first. Unreadable records are skipped with a warning.
+    pub fn all(&self) -> Result<Vec<SessionRecord>> {
+        let mut records = Vec::new();
+        if let Ok(entries) = fs::read_dir(&self.dir) {
+            for entry in entries.flatten() {
+                if entry.path().extension().is_some_and(|ext| ext == "json") {
+                    match read_record(&entry.path()) {
+                        Ok(record) => records.push(record),
+                        Err(e) => eprintln!("{} Skipping session record: {:#}", "!".yellow().bold(), e),
+                    }
+                }
+            }
+        }
+        records.sort_by(|a, b| b.started_at.cmp(&a.started_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(records)
+    }
+
+    pub fn query(&self, query: &SessionQuery) -> Result<Vec<SessionRecord>> {
+        let mut records: Vec<SessionRecord> = self.all()?.into_iter().filter(|r| query.matches(r)).collect();
+        if let Some(limit) = query.limit {
+            records.truncate(limit);
+        }
+        Ok(records)
+    }
+
+    pub fn find_by_source(&self, source: &str) -> Result<Option<SessionRecord>> {
+        Ok(self.all()?.into_iter().find(|r| r.source.as_deref() == Some(source)))
+    }
+
+    /// Whether legacy records were imported already
+    pub fn is_migrated(&self) -> bool {
+        self.dir.join(MIGRATED_MARKER).exists()
+    }
+
+    /// Import legacy records under `ci_root` and the topology history of
+    /// `project_dir`. Records already imported (same `source`) are skipped,
+    /// so running it again only picks up what is new.
+    pub fn migrate(&self, ci_root: &Path, project_dir: Option<&Path>) -> Result<MigrationReport> {
+        let existing: std::collections::HashSet<String> = self.all()?.into_iter().filter_map(|r| r.source).collect();
+        let mut report = MigrationReport::default();
+
+        let mut candidates = legacy_records(&ci_root.join("AGENTS"));
+        if let Some(project) = project_dir {
+            candidates.extend(topology_records(project));
+        }
+
+        for record in candidates {
+            if record.source.as_ref().is_some_and(|s| existing.contains(s)) {
+                report.skipped += 1;
+                continue;
+            }
+            self.save(&record)?;
+            report.imported.push(record);
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(MIGRATED_MARKER), Utc::now().to_rfc3339())?;
+        Ok(report)
+    }
+}
+
+/// Mirror a topology commit session into the store, creating its record on first sight
+pub fn sync_topology_session(session: &SessionHistory, history_file: &Path) -> Result<()> {
+    let store = SessionStore::open()?;
+    let project = history_file.parent().and_then(Path::parent).map(absolute);
+    let source = format!("{}#{}", absolute(history_file).display(), session.session_id);
+
+    let mut record = store.find_by_source(&source)?
+        .unwrap_or_else(|| topology_record(session, project.clone(), source));
+    record.commits = session.phases.iter().map(|p| p.commit_hash.clone()).collect();
+    record.ended_at = session.completed;
+    if session.completed.is_some() && record.status == SessionStatus::Active {
+        record.status = SessionStatus::Completed;
+    }
+    store.save(&record)
+}
+
+/// Objective of a task assignment built by `ci task` or `ci load --task`
+pub fn task_objective(context: &str) -> Option<String> {
+    context.lines()
+        .find_map(|line| line.strip_prefix("**OBJECTIVE**: "))
+        .map(|objective| objective.trim().to_string())
+}
+
+/// Absolute form of a path, without requiring it to exist
+pub fn absolute(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| {
+        std::env::current_dir().map(|cwd| cwd.join(path)).unwrap_or_else(|_| path.to_path_buf())
+    })
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..12].to_string()
+}
+
+fn read_record(path: &Path) -> Result<SessionRecord> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read session record {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse session record {}", path.display()))
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+/// Records for the session files `ci load` and `ci session create` leave under `AGENTS/`
+fn legacy_records(agents_dir: &Path) -> Vec<SessionRecord> {
+    let mut records = Vec::new();
+    let Ok(entries) = fs::read_dir(agents_dir) else {
+        return records;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if path.is_file() && name.ends_with("_session.json") {
+            records.extend(agent_session_record(&path, SessionKind::MultiAgent));
+        } else if path.is_dir() && name.starts_with("parallel_session_") {
+            records.extend(parallel_record(&path));
+        } else if path.is_dir() {
+            if let Ok(files) = fs::read_dir(path.join("sessions")) {
+                for file in files.flatten().filter(|f| f.path().extension().is_some_and(|ext| ext == "json")) {
+                    records.extend(agent_session_record(&file.path(), SessionKind::Agent));
+                }
+            }
+            if let Ok(dirs) = fs::read_dir(path.join("Sessions")) {
+                for dir in dirs.flatten().filter(|d| d.path().is_dir()) {
+                    records.push(manual_record(&name, &dir.path()));
+                }
+            }
+        }
+    }
+    records
+}
+
+fn agent_session_record(path: &Path, kind: SessionKind) -> Option<SessionRecord> {
+    let session: AgentSession = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    let headless = session.output_path.as_deref().is_some_and(|p| p.ends_with("output.log"));
+
+    let mut record = SessionRecord::new(if headless { SessionKind::Headless } else { kind }, session.agent_names());
+    record.started_at = session.started_at()?;
+    record.ended_at = session.end_time.as_deref().and_then(parse_time);
+    record.status = if record.ended_at.is_some() { SessionStatus::Completed } else { SessionStatus::Unknown };
+    record.task = session.context.as_deref().and_then(task_objective);
+    record.context = session.context;
+    record.output_path = session.output_path.map(PathBuf::from);
+    if kind == SessionKind::MultiAgent {
+        record.memory_path = record.output_path.take();
+    }
+    record.source = Some(absolute(path).display().to_string());
+    Some(record)
+}
+
+fn parallel_record(coordination_dir: &Path) -> Option<SessionRecord> {
+    let state = SupervisorState::load(coordination_dir).ok()?;
+    let mut agents: Vec<String> = state.instances.iter().map(|i| i.agent_name.clone()).collect();
+    agents.sort();
+    agents.dedup();
+
+    let mut record = SessionRecord::new(SessionKind::Parallel, agents);
+    record.name = Some(state.session_id.clone());
+    record.task = Some(state.task.clone());
+    record.started_at = state.started_at;
+    record.project = state.repo_root.clone();
+    record.memory_path = Some(absolute(coordination_dir));
+    record.status = parallel_status(&state);
+    if let Some(repo_root) = &state.repo_root {
+        record.git = Some(GitProvenance {
+            repo_root: repo_root.clone(),
+            start: Some(GitSnapshot {
+                head: state.base_commit.clone(),
+                branch: None,
+                dirty: false,
+                taken_at: state.started_at,
+            }),
+            ..Default::default()
+        });
+    }
+    if record.status != SessionStatus::Active {
+        record.ended_at = state.instances.iter().filter_map(|i| i.ended_at).max();
+        record.finish_git_at_end_time();
+    }
+    record.source = Some(absolute(coordination_dir).display().to_string());
+    Some(record)
+}
+
+/// Overall status of a parallel session from its instances
+pub fn parallel_status(state: &SupervisorState) -> SessionStatus {
+    if state.instances.iter().any(|i| i.status == InstanceStatus::Running) {
+        SessionStatus::Active
+    } else if state.instances.iter().any(|i| matches!(i.status, InstanceStatus::Failed | InstanceStatus::TimedOut)) {
+        SessionStatus::Failed
+    } else {
+        SessionStatus::Completed
+    }
+}
+
+fn manual_record(agent_name: &str, session_dir: &Path) -> SessionRecord {
+    #[derive(Deserialize)]
+    struct SessionMetadata {
+        session_name: String,
+        created_at: String,
+        description: String,
+        status: String,
+        tags: Vec<String>,
+    }
+
+    let metadata = fs::read_to_string(session_dir.join("metadata.json")).ok()
+        .and_then(|content| serde_json::from_str::<SessionMetadata>(&content).ok());
+
+    let mut record = SessionRecord::new(SessionKind::Manual, vec![agent_name.to_string()]);
+    record.memory_path = Some(absolute(session_dir));
+    record.source = Some(absolute(session_dir).display().to_string());
+    match metadata {
+        Some(metadata) => {
+            record.name = Some(metadata.session_name);
+            record.started_at = parse_time(&metadata.created_at).unwrap_or(record.started_at);
+            record.description = Some(metadata.description).filter(|d| !d.is_empty());
+            record.status = metadata.status.parse().unwrap_or(SessionStatus::Completed);
+            record.tags = metadata.tags;
+        },
+        None => {
+            record.name = session_dir.file_name().map(|n| n.to_string_lossy().to_string());
+            record.status = SessionStatus::Completed;
+            record.tags = vec!["legacy".to_string()];
+            if let Ok(created) = fs::metadata(session_dir).and_then(|m| m.created()) {
+                record.started_at = DateTime::<Utc>::from(created);
+            }
+        },
+    }
+    record
+}
+
+fn topology_records(project_dir: &Path) -> Vec<SessionRecord> {
+    #[derive(Deserialize)]
+    struct CommitHistory {
+        sessions: Vec<SessionHistory>,
+    }
+
+    let history_file = project_dir.join(".ci-topology").join("commit_history.json");
+    let Some(history) = fs::read_to_string(&history_file).ok()
+        .and_then(|content| serde_json::from_str::<CommitHistory>(&content).ok()) else {
+        return Vec::new();
+    };
+
+    history.sessions.iter()
+        .map(|session| {
+            let source = format!("{}#{}", absolute(&history_file).display(), session.session_id);
+            let mut record = topology_record(session, Some(absolute(project_dir)), source);
+            record.commits = session.phases.iter().map(|p| p.commit_hash.clone()).collect();
+            record.ended_at = session.completed;
+            record.status = if session.completed.is_some() { SessionStatus::Completed } else { SessionStatus::Active };
+            record
+        })
+        .collect()
+}
+
+fn topology_record(session: &SessionHistory, project: Option<PathBuf>, source: String) -> SessionRecord {
+    let mut record = SessionRecord::new(SessionKind::Topology, vec!["Topologist".to_string()]);
+    record.name = Some(session.session_id.clone());
+    record.started_at = session.started;
+    record.project = project;
+    record.source = Some(source);
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = SessionStore::new(temp_dir.path());
+
+        let mut first = SessionRecord::new(SessionKind::Agent, vec!["Athena".to_string()]);
+        first.started_at = parse_time("2024-05-01T10:00:00Z").unwrap();
+        first.tags = vec!["release".to_string()];
+        first.project = Some(PathBuf::from("/work/api"));
+        first.finish(true);
+        store.save(&first).unwrap();
+
+        let second = SessionRecord::new(SessionKind::Parallel, vec!["Athena".to_string(), "Tester".to_string()]);
+        store.save(&second).unwrap();
+
+        let query = |q: SessionQuery| store.query(&q).unwrap().into_iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(query(SessionQuery::default()), vec![second.id.clone(), first.id.clone()]);
+        assert_eq!(query(SessionQuery { agent: Some("test".into()), ..Default::default() }), vec![second.id.clone()]);
+        assert_eq!(query(SessionQuery { status: Some(SessionStatus::Completed), ..Default::default() }), vec![first.id.clone()]);
+        assert_eq!(query(SessionQuery { tag: Some("Release".into()), ..Default::default() }), vec![first.id.clone()]);
+        assert_eq!(query(SessionQuery { project: Some("api".into()), ..Default::default() }), vec![first.id.clone()]);
+        assert_eq!(query(SessionQuery { until: parse_time("2024-06-01T00:00:00Z"), ..Default::default() }), vec![first.id.clone()]);
+        assert_eq!(query(SessionQuery { limit: Some(1), ..Default::default() }).len(), 1);
+
+        assert_eq!(store.load(&first.id[..6]).unwrap().id, first.id);
+        assert!(store.load("zzzz").is_err());
+    }
+
+    #[test]
+    fn test_migration_imports_every_legacy_shape_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let ci_root = temp_dir.path().join("ci");
+        let agents = ci_root.join("AGENTS");
+        write(&agents.join("Athena/sessions/1.json"),
+            r#"{"agent_name":"Athena","start_time":"2024-05-01T10:00:00Z","context":null,"end_time":"2024-05-01T10:30:00Z","output_path":null}"#);
+        write(&agents.join("team_session.json"),
+            r#"{"agent_name":"MultiAgent[Athena,Tester]","start_time":"2024-05-02T10:00:00Z","context":"api","end_time":null,"output_path":"/tmp/combined.md"}"#);
+        write(&agents.join("Athena/Sessions/planning/metadata.json"),
+            r#"{"session_name":"planning","agent_name":"Athena","created_at":"2024-04-01T09:00:00Z","description":"Q2","status":"archived","tags":["plan"]}"#);
+
+        let project = temp_dir.path().join("project");
+        write(&project.join(".ci-topology/commit_history.json"),
+            r#"{"sessions":[{"session_id":"s1","started":"2024-05-03T10:00:00Z","completed":null,"phases":[{"phase":1,"commit_hash":"abc","files_count":2,"size_change":10,"category":"Unknown","executed_at":"2024-05-03T10:05:00Z"}],"total_planned_phases":null,"total_impact":null}]}"#);
+
+        let store = SessionStore::new(&ci_root);
+        assert!(!store.is_migrated());
+        let report = store.migrate(&ci_root, Some(&project)).unwrap();
+        assert_eq!(report.imported.len(), 4);
+        assert!(store.is_migrated());
+
+        let all = store.all().unwrap();
+        let multi = all.iter().find(|r| r.kind == SessionKind::MultiAgent).unwrap();
+        assert_eq!(multi.agents, vec!["Athena", "Tester"]);
+        assert_eq!(multi.memory_path, Some(PathBuf::from("/tmp/combined.md")));
+        // No end time recorded, so the import cannot claim it completed
+        assert_eq!(multi.status, SessionStatus::Unknown);
+        assert_eq!(all.iter().find(|r| r.kind == SessionKind::Agent).unwrap().status, SessionStatus::Completed);
+        let manual = all.iter().find(|r| r.kind == SessionKind::Manual).unwrap();
+        assert_eq!((manual.status, manual.tags.clone()), (SessionStatus::Archived, vec!["plan".to_string()]));
+        let topology = all.iter().find(|r| r.kind == SessionKind::Topology).unwrap();
+        assert_eq!((topology.status, topology.commits.clone()), (SessionStatus::Active, vec!["abc".to_string()]));
+
+        // A corrupt record does not hide the others
+        write(&ci_root.join(STORE_DIR).join("broken.json"), "{");
+        assert_eq!(store.all().unwrap().len(), 4);
+
+        let again = store.migrate(&ci_root, Some(&project)).unwrap();
+        assert_eq!((again.imported.len(), again.skipped), (0, 4));
+    }
+}
@@ -0,0 +1,374 @@
+//! Work sharding for multi-instance agent runs
+//!
+//! Splits a repository (or a user-provided list of paths) into balanced shards,
+//! one per agent instance, so parallel instances do not have to negotiate who
+//! works on what. Files are categorized with the topology `FileCategorizer`;
+//! its size estimates are the weights that get balanced.
+//!
+//! - `files`: every file is a unit
+//! - `dirs`: files grouped by their directory
+//! - `modules`: source files grouped by top-level module (`src/commands`),
+//!   everything else by category (documentation, configuration, ...)
+//! - `list:<file>`: one unit per line of the file (files or directories, `#` comments);
+//!   files the list does not cover are reported as leftovers
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::helpers::git::{git, git_value};
+use crate::topology::{FileCategorizer, FileCategory};
+
+/// Session file holding the full shard plan
+pub const SHARDS_FILE: &str = "shards.json";
+
+/// Directories never worth sharding when the repository is not under git
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", "dist", "build"];
+
+/// How work is split between instances
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShardStrategy {
+    Files,
+    Dirs,
+    Modules,
+    List(PathBuf),
+}
+
+impl FromStr for ShardStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "files" => Ok(ShardStrategy::Files),
+            "dirs" => Ok(ShardStrategy::Dirs),
+            "modules" => Ok(ShardStrategy::Modules),
+            _ => match value.strip_prefix("list:") {
+                Some(path) if !path.is_empty() => Ok(ShardStrategy::List(PathBuf::from(path))),
+                _ => Err(format!("Unknown shard strategy '{}' (use files, dirs, modules or list:<file>)", value)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ShardStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShardStrategy::Files => write!(f, "files"),
+            ShardStrategy::Dirs => write!(f, "dirs"),
+            ShardStrategy::Modules => write!(f, "modules"),
+            ShardStrategy::List(path) => write!(f, "list:{}", path.display()),
+        }
+    }
+}
+
+/// A piece of work that is assigned as a whole
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkUnit {
+    /// Path or group name shown to the agent
+    pub label: String,
+    pub files: Vec<String>,
+    pub weight: usize,
+}
+
+/// Work assigned to one instance
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Shard {
+    pub units: Vec<WorkUnit>,
+    pub weight: usize,
+}
+
+impl Shard {
+    pub fn file_count(&self) -> usize {
+        self.units.iter().map(|u| u.files.len()).sum()
+    }
+
+    /// Assignment section for an instance's session memory
+    pub fn assignment(&self, index: usize, total: usize, strategy: &str) -> String {
+        let mut text = format!("**YOUR SHARD** ({} of {}, split by {}):\n", index + 1, total, strategy);
+        if self.units.is_empty() {
+            text.push_str("- No paths were assigned to you. Review the other instances' work and help where they post blockers.\n\n");
+            return text;
+        }
+
+        text.push_str("You own the following paths. Leave paths outside your shard to the other instances.\n");
+        for unit in &self.units {
+            if unit.files.len() == 1 && unit.files[0] == unit.label {
+                text.push_str(&format!("- {}\n", unit.label));
+            } else {
+                text.push_str(&format!("- {} ({} files)\n", unit.label, unit.files.len()));
+                // Group names like "documentation" do not say where the files are
+                if !unit.files.iter().all(|f| f.starts_with(&unit.label)) {
+                    for file in &unit.files {
+                        text.push_str(&format!("  - {}\n", file));
+                    }
+                }
+            }
+        }
+        text.push('\n');
+        text
+    }
+
+    /// One-line summary for the coordination file
+    pub fn summary(&self) -> String {
+        if self.units.is_empty() {
+            return "no paths assigned".to_string();
+        }
+        let labels: Vec<&str> = self.units.iter().map(|u| u.label.as_str()).take(5).collect();
+        let more = self.units.len().saturating_sub(labels.len());
+        let files = self.file_count();
+        format!("{} file{}: {}{}",
+            files,
+            if files == 1 { "" } else { "s" },
+            labels.join(", "),
+            if more > 0 { format!(" and {} more", more) } else { String::new() }
+        )
+    }
+}
+
+/// A path left out of every shard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Leftover {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Balanced split of the work between instances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardPlan {
+    pub strategy: String,
+    pub root: PathBuf,
+    pub shards: Vec<Shard>,
+    pub leftovers: Vec<Leftover>,
+}
+
+impl ShardPlan {
+    pub fn load(coordination_dir: &Path) -> Result<Self> {
+        let path = coordination_dir.join(SHARDS_FILE);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self, coordination_dir: &Path) -> Result<()> {
+        fs::write(coordination_dir.join(SHARDS_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Split the repository containing `dir` into `count` shards, ignoring anything under `excluded`
+pub fn plan_shards(dir: &Path, strategy: &ShardStrategy, count: usize, excluded: &[PathBuf]) -> Result<ShardPlan> {
+    if count == 0 {
+        return Err(anyhow!("Cannot shard work between zero instances"));
+    }
+
+    let root = git_value(dir, &["rev-parse", "--show-toplevel"])
+        .map(PathBuf::from)
+        .unwrap_or_else(|| dir.to_path_buf());
+    // Session data (coordination files, agent memories) is not work to split
+    let excluded: Vec<PathBuf> = excluded.iter()
+        .filter_map(|path| path.canonicalize().ok())
+        .collect();
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.clone());
+    let files: Vec<String> = list_files(&root)?
+        .into_iter()
+        .filter(|file| !excluded.iter().any(|dir| canonical_root.join(file).starts_with(dir)))
+        .collect();
+
+    let categorizer = FileCategorizer::new();
+    let analysis = categorizer.analyze_files(files);
+
+    let mut leftovers = Vec::new();
+    let mut work_files = Vec::new();
+    for file in analysis.files {
+        match file.category {
+            FileCategory::BuildArtifacts => leftovers.push(Leftover { path: file.path, reason: "build artifact".to_string() }),
+            FileCategory::MediaAssets => leftovers.push(Leftover { path: file.path, reason: "media asset".to_string() }),
+            _ => work_files.push(file),
+        }
+    }
+
+    let mut units: BTreeMap<String, WorkUnit> = BTreeMap::new();
+    let mut add = |label: String, path: &str, weight: usize| {
+        let unit = units.entry(label.clone()).or_insert_with(|| WorkUnit { label, files: Vec::new(), weight: 0 });
+        unit.files.push(path.to_string());
+        unit.weight += weight;
+    };
+
+    match strategy {
+        ShardStrategy::Files => {
+            for file in &work_files {
+                add(file.path.clone(), &file.path, file.estimated_size);
+            }
+        }
+        ShardStrategy::Dirs => {
+            for file in &work_files {
+                let dir = Path::new(&file.path).parent()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .filter(|p| !p.is_empty())
+                    .unwrap_or_else(|| ".".to_string());
+                add(dir, &file.path, file.estimated_size);
+            }
+        }
+        ShardStrategy::Modules => {
+            for file in &work_files {
+                let label = if file.category == FileCategory::SourceCode {
+                    module_of(&file.path)
+                } else {
+                    file.grouping_hint.clone()
+                };
+                add(label, &file.path, file.estimated_size);
+            }
+        }
+        ShardStrategy::List(list_path) => {
+            let list = fs::read_to_string(list_path)
+                .with_context(|| format!("Failed to read shard list {}", list_path.display()))?;
+            let mut listed = vec![false; work_files.len()];
+            for entry in list.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+                let entry = entry.trim_end_matches('/');
+                let matching: Vec<usize> = (0..work_files.len())
+                    .filter(|&i| work_files[i].path == entry || work_files[i].path.starts_with(&format!("{}/", entry)))
+                    .collect();
+                if matching.is_empty() {
+                    leftovers.push(Leftover { path: entry.to_string(), reason: "not found in repository".to_string() });
+                }
+                // A file under two listed paths goes to the first one
+                for i in matching {
+                    if !std::mem::replace(&mut listed[i], true) {
+                        add(entry.to_string(), &work_files[i].path, work_files[i].estimated_size);
+                    }
+                }
+            }
+            for (file, _) in work_files.iter().zip(&listed).filter(|(_, listed)| !**listed) {
+                leftovers.push(Leftover { path: file.path.clone(), reason: "not in shard list".to_string() });
+            }
+        }
+    }
+
+    Ok(ShardPlan {
+        strategy: strategy.to_string(),
+        root,
+        shards: balance(units.into_values().collect(), count),
+        leftovers,
+    })
+}
+
+/// Longest-processing-time-first: heaviest unit to the lightest shard
+fn balance(mut units: Vec<WorkUnit>, count: usize) -> Vec<Shard> {
+    units.sort_by(|a, b| b.weight.cmp(&a.weight).then_with(|| a.label.cmp(&b.label)));
+
+    let mut shards = vec![Shard::default(); count];
+    for unit in units {
+        let lightest = shards.iter_mut()
+            .min_by_key(|s| s.weight)
+            .unwrap();
+        lightest.weight += unit.weight;
+        lightest.units.push(unit);
+    }
+
+    for shard in &mut shards {
+        shard.units.sort_by(|a, b| a.label.cmp(&b.label));
+    }
+    shards
+}
+
+/// Top-level module of a source file: `src/commands/visualize/mod.rs` -> `src/commands`
+fn module_of(path: &str) -> String {
+    let parts: Vec<&str> = path.split('/').collect();
+    match parts.as_slice() {
+        [single] => single.to_string(),
+        [root, file] if file.contains('.') => root.to_string(),
+        [root, module, ..] => format!("{}/{}", root, module),
+        _ => path.to_string(),
+    }
+}
+
+/// Tracked and untracked (not ignored) files relative to `root`
+fn list_files(root: &Path) -> Result<Vec<String>> {
+    if let Ok(output) = git(root, &["ls-files", "--cached", "--others", "--exclude-standard"]) {
+        return Ok(output.lines().filter(|l| !l.is_empty()).map(str::to_string).collect());
+    }
+
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)?.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_str()) {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                files.push(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        for path in [
+            "README.md", "Cargo.toml", "logo.png",
+            "src/main.rs", "src/commands/a.rs", "src/commands/b.rs", "src/helpers/c.rs",
+            "docs/guide.md",
+        ] {
+            let path = temp_dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "x").unwrap();
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn test_modules_are_balanced_and_media_left_over() {
+        let temp_dir = setup();
+        let plan = plan_shards(temp_dir.path(), &ShardStrategy::Modules, 2, &[temp_dir.path().join("docs")]).unwrap();
+
+        let labels: Vec<Vec<&str>> = plan.shards.iter()
+            .map(|s| s.units.iter().map(|u| u.label.as_str()).collect())
+            .collect();
+        assert!(labels.concat().contains(&"src/commands"));
+        assert_eq!(plan.shards.iter().map(Shard::file_count).sum::<usize>(), 6);
+        let weights: Vec<usize> = plan.shards.iter().map(|s| s.weight).collect();
+        assert!(weights[0].abs_diff(weights[1]) <= 400, "unbalanced: {:?}", weights);
+
+        assert_eq!(plan.leftovers.len(), 1);
+        assert_eq!(plan.leftovers[0].path, "logo.png");
+    }
+
+    #[test]
+    fn test_list_strategy_reports_missing_entries() {
+        let temp_dir = setup();
+        let list = temp_dir.path().join("shards.txt");
+        fs::write(&list, "# areas\nsrc/commands/\ndocs\nmissing/dir\n").unwrap();
+
+        let strategy: ShardStrategy = format!("list:{}", list.display()).parse().unwrap();
+        let plan = plan_shards(temp_dir.path(), &strategy, 3, &[]).unwrap();
+
+        let assigned: usize = plan.shards.iter().map(Shard::file_count).sum();
+        assert_eq!(assigned, 3);
+        assert!(plan.shards.iter().any(|s| s.units.is_empty()));
+        assert!(plan.leftovers.iter().any(|l| l.path == "missing/dir"));
+        let unlisted: Vec<&str> = plan.leftovers.iter()
+            .filter(|l| l.reason == "not in shard list")
+            .map(|l| l.path.as_str())
+            .collect();
+        assert_eq!(unlisted.len(), 5);
+        assert!(unlisted.contains(&"src/helpers/c.rs") && unlisted.contains(&"shards.txt"));
+        assert!(plan.shards[0].assignment(0, 3, &plan.strategy).contains("YOUR SHARD"));
+
+        assert!("bogus".parse::<ShardStrategy>().is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! Session transcript capture
+//!
+//! Runs an assistant under `script(1)` so it still owns a terminal while
+//! everything it prints is teed into a raw log. Once the session ends the raw
+//! log is cleaned of escape sequences, carriage-return redraws and backspaces
+//! and kept as a plain-text transcript.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+
+use crate::helpers::agent_supervisor::LaunchSpec;
+use crate::helpers::ansi::strip_ansi_codes;
+
+/// Timestamped transcript file inside `dir`
+pub fn transcript_path(dir: &Path) -> PathBuf {
+    dir.join(format!("transcript_{}.log", Utc::now().format("%Y%m%d_%H%M%S")))
+}
+
+/// Whether `script(1)` is installed
+pub fn is_supported() -> bool {
+    Command::new("which")
+        .arg("script")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Run `spec` in the foreground through a pseudo-terminal and write the cleaned
+/// transcript of its output to `transcript`
+pub fn run_recorded(spec: &LaunchSpec, transcript: &Path) -> Result<ExitStatus> {
+    if let Some(parent) = transcript.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create transcript directory {}", parent.display()))?;
+    }
+    let raw_log = transcript.with_extension("raw");
+
+    let mut cmd = script_command(spec, &raw_log);
+    for (key, value) in &spec.envs {
+        cmd.env(key, value);
+    }
+    if let Some(cwd) = &spec.cwd {
+        cmd.current_dir(cwd);
+    }
+    if let Some(stdin_file) = &spec.stdin_file {
+        cmd.stdin(fs::File::open(stdin_file)?);
+    }
+    let status = cmd.status().context("Failed to start script(1) for transcript capture")?;
+
+    let raw = String::from_utf8_lossy(&fs::read(&raw_log).unwrap_or_default()).to_string();
+    let recorded: Vec<&str> = raw.lines()
+        .filter(|line| !line.starts_with("Script started on") && !line.starts_with("Script done on"))
+        .collect();
+    fs::write(transcript, clean_transcript(&recorded.join("\n")))
+        .with_context(|| format!("Failed to write transcript {}", transcript.display()))?;
+    let _ = fs::remove_file(&raw_log);
+
+    Ok(status)
+}
+
+/// util-linux and BSD `script` take the command differently
+fn script_command(spec: &LaunchSpec, raw_log: &Path) -> Command {
+    let mut cmd = Command::new("script");
+    if cfg!(target_os = "linux") {
+        let command_line = std::iter::once(spec.program.as_str())
+            .chain(spec.args.iter().map(String::as_str))
+            .map(shell_quote)
+            .collect::<Vec<_>>()
+            .join(" ");
+        cmd.args(["-q", "-f", "-e", "-c", &command_line]).arg(raw_log);
+    } else {
+        cmd.arg("-q").arg(raw_log).arg(&spec.program).args(&spec.args);
+    }
+    cmd
+}
+
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,@%+".contains(c)) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Plain text of a terminal recording: escape sequences removed, lines redrawn
+/// with `\r` reduced to their final content and backspaces applied
+pub fn clean_transcript(raw: &str) -> String {
+    let text = strip_ansi_codes(raw);
+    let mut out = String::with_capacity(text.len());
+
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let mut current = String::new();
+        for segment in line.split('\r') {
+            let mut rewritten: Vec<char> = Vec::new();
+            for ch in segment.chars() {
+                match ch {
+                    '\x08' => { rewritten.pop(); },
+                    c if c.is_control() && c != '\t' => {},
+                    c => rewritten.push(c),
+                }
+            }
+            // A carriage return overwrites the start of the line
+            let tail: String = current.chars().skip(rewritten.len()).collect();
+            current = rewritten.into_iter().collect::<String>() + &tail;
+        }
+        out.push_str(current.trim_end());
+        out.push('\n');
+    }
+
+    out.trim_end_matches('\n').to_string() + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_clean_transcript_removes_terminal_noise() {
+        let raw = "\x1b[?25lWorking 10%\rWorking 100%\r\nabd\x08c\r\n\x1b]0;t\x1b\\bye\r\n";
+        assert_eq!(clean_transcript(raw), "Working 100%\nabc\nbye\n");
+    }
+
+    #[test]
+    fn test_run_recorded_writes_transcript() {
+        if !is_supported() {
+            return;
+        }
+        let temp_dir = TempDir::new().unwrap();
+        let transcript = temp_dir.path().join("session").join("transcript.log");
+
+        let mut spec = LaunchSpec::new("sh");
+        spec.args = vec!["-c".to_string(), "printf '\\033[31mred\\033[0m it'\"'\"'s\\n'; exit 3".to_string()];
+        let status = run_recorded(&spec, &transcript).unwrap();
+
+        assert_eq!(status.code(), Some(3));
+        assert_eq!(fs::read_to_string(&transcript).unwrap().trim(), "red it's");
+        assert!(!transcript.with_extension("raw").exists());
+    }
+}
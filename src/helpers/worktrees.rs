@@ -0,0 +1,335 @@
+//! Per-instance git worktrees for parallel agent sessions
+//!
+//! With `ci load ... --parallel --worktrees` every agent instance works in its
+//! own `git worktree` on its own branch, so instances cannot clobber each
+//! other's edits. Worktrees live inside the repository's git directory, where
+//! they never show up as untracked files:
+//!
+//! ```text
+//! <repo>/.git/ci-worktrees/<session_id>/<instance>/   worktree
+//! ci/<session_id>/<instance>                          branch
+//! AGENTS/<session_id>/worktrees.json                  WorktreeSession
+//! ```
+//!
+//! `ci load merge <session_id>` reviews each branch against the commit the
+//! session started from, then merges or discards it and removes the worktree.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::helpers::git::git;
+
+/// Session file listing the worktrees of a parallel session
+pub const WORKTREES_FILE: &str = "worktrees.json";
+
+/// Directory inside the git common dir holding session worktrees
+const WORKTREES_DIR: &str = "ci-worktrees";
+
+/// Review state of an instance's worktree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorktreeStatus {
+    Active,
+    Merged,
+    Discarded,
+}
+
+/// Worktree and branch of one agent instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceWorktree {
+    /// Instance display name, e.g. "Documentor (Instance-2)"
+    pub instance: String,
+
+    /// File-safe instance name used in paths and branch names
+    pub name: String,
+
+    pub branch: String,
+    pub path: PathBuf,
+    pub status: WorktreeStatus,
+}
+
+/// What an instance's branch holds compared to the session base
+#[derive(Debug, Clone, Default)]
+pub struct WorktreeSummary {
+    pub commits: usize,
+    pub files_changed: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+
+    /// The worktree has uncommitted changes
+    pub dirty: bool,
+}
+
+/// Worktrees created for one parallel session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorktreeSession {
+    pub session_id: String,
+    pub repo_root: PathBuf,
+
+    /// Commit every instance branch starts from
+    pub base_commit: String,
+
+    /// Branch checked out in the main worktree when the session started
+    pub base_branch: Option<String>,
+
+    pub created_at: DateTime<Utc>,
+    pub worktrees: Vec<InstanceWorktree>,
+}
+
+impl WorktreeSession {
+    /// Start a worktree session for the repository containing `repo_dir`
+    pub fn create(session_id: &str, repo_dir: &Path) -> Result<Self> {
+        let repo_root = git(repo_dir, &["rev-parse", "--show-toplevel"])
+            .map_err(|_| anyhow!("--worktrees requires a git repository ({} is not inside one)", repo_dir.display()))?;
+        let repo_root = PathBuf::from(repo_root.trim());
+        let base_commit = git(&repo_root, &["rev-parse", "HEAD"])
+            .map_err(|_| anyhow!("--worktrees requires at least one commit in {}", repo_root.display()))?
+            .trim()
+            .to_string();
+        let base_branch = git(&repo_root, &["symbolic-ref", "--short", "-q", "HEAD"])
+            .ok()
+            .map(|b| b.trim().to_string())
+            .filter(|b| !b.is_empty());
+
+        Ok(WorktreeSession {
+            session_id: session_id.to_string(),
+            repo_root,
+            base_commit,
+            base_branch,
+            created_at: Utc::now(),
+            worktrees: Vec::new(),
+        })
+    }
+
+    /// Create the worktree and branch of an instance
+    pub fn add(&mut self, instance: &str, name: &str) -> Result<InstanceWorktree> {
+        let common_dir = git(&self.repo_root, &["rev-parse", "--git-common-dir"])?;
+        let common_dir = self.repo_root.join(common_dir.trim());
+        let path = common_dir.join(WORKTREES_DIR).join(&self.session_id).join(name);
+        let branch = format!("ci/{}/{}", self.session_id, name);
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        git(&self.repo_root, &["worktree", "add", "-q", "-b", &branch, &path.to_string_lossy(), &self.base_commit])
+            .with_context(|| format!("Failed to create worktree for {}", instance))?;
+
+        let worktree = InstanceWorktree {
+            instance: instance.to_string(),
+            name: name.to_string(),
+            branch,
+            path,
+            status: WorktreeStatus::Active,
+        };
+        self.worktrees.push(worktree.clone());
+        Ok(worktree)
+    }
+
+    pub fn load(coordination_dir: &Path) -> Result<Self> {
+        let path = coordination_dir.join(WORKTREES_FILE);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("No worktrees recorded for this session ({} missing)", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, coordination_dir: &Path) -> Result<()> {
+        fs::write(coordination_dir.join(WORKTREES_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Commits and changes of an instance relative to the session base
+    pub fn summary(&self, worktree: &InstanceWorktree) -> Result<WorktreeSummary> {
+        let mut summary = WorktreeSummary::default();
+
+        let count = git(&self.repo_root, &["rev-list", "--count", &format!("{}..{}", self.base_commit, worktree.branch)])?;
+        summary.commits = count.trim().parse().unwrap_or(0);
+
+        if worktree.path.exists() {
+            summary.dirty = !git(&worktree.path, &["status", "--porcelain"])?.trim().is_empty();
+        }
+
+        // Committed and uncommitted changes together when the worktree still exists
+        let numstat = if worktree.path.exists() {
+            let mut numstat = git(&worktree.path, &["diff", "--numstat", &self.base_commit])?;
+            for file in untracked_files(&worktree.path)? {
+                numstat.push_str(&diff_new_file(&worktree.path, &file, "--numstat")?);
+            }
+            numstat
+        } else {
+            git(&self.repo_root, &["diff", "--numstat", &self.base_commit, &worktree.branch])?
+        };
+        for line in numstat.lines() {
+            let mut fields = line.split('\t');
+            summary.insertions += fields.next().and_then(|n| n.parse::<usize>().ok()).unwrap_or(0);
+            summary.deletions += fields.next().and_then(|n| n.parse::<usize>().ok()).unwrap_or(0);
+            summary.files_changed += 1;
+        }
+
+        Ok(summary)
+    }
+
+    /// Diff of an instance's work (including uncommitted changes) against the session base
+    pub fn diff(&self, worktree: &InstanceWorktree) -> Result<String> {
+        if worktree.path.exists() {
+            let mut diff = git(&worktree.path, &["diff", "--stat", "--patch", &self.base_commit])?;
+            for file in untracked_files(&worktree.path)? {
+                diff.push_str(&diff_new_file(&worktree.path, &file, "--patch")?);
+            }
+            Ok(diff)
+        } else {
+            git(&self.repo_root, &["diff", "--stat", "--patch", &self.base_commit, &worktree.branch])
+        }
+    }
+
+    /// Merge an instance's branch into the main worktree, committing leftover
+    /// worktree changes first. Returns whether leftover changes were committed.
+    pub fn merge(&mut self, index: usize) -> Result<bool> {
+        let worktree = self.worktrees[index].clone();
+
+        if let Some(base_branch) = &self.base_branch {
+            let current = git(&self.repo_root, &["symbolic-ref", "--short", "-q", "HEAD"]).unwrap_or_default();
+            if current.trim() != base_branch {
+                return Err(anyhow!(
+                    "{} is not on {}, the branch this session started from\nCheck out {} there, then run 'ci load merge {}' again",
+                    self.repo_root.display(), base_branch, base_branch, self.session_id
+                ));
+            }
+        }
+
+        let mut committed_leftovers = false;
+        if worktree.path.exists() && !git(&worktree.path, &["status", "--porcelain"])?.trim().is_empty() {
+            git(&worktree.path, &["add", "-A"])?;
+            git(&worktree.path, &["commit", "-q", "-m", &format!("{}: uncommitted work from {}", worktree.instance, self.session_id)])?;
+            committed_leftovers = true;
+        }
+
+        let message = format!("Merge {} from {}", worktree.instance, self.session_id);
+        if let Err(e) = git(&self.repo_root, &["merge", "--no-edit", "-m", &message, &worktree.branch]) {
+            return Err(anyhow!(
+                "Merging {} failed: {}\nResolve the conflicts (or run 'git merge --abort') in {}, then run 'ci load merge {}' again",
+                worktree.branch, e, self.repo_root.display(), self.session_id
+            ));
+        }
+
+        self.remove_worktree(index)?;
+        let _ = git(&self.repo_root, &["branch", "-d", &worktree.branch]);
+        self.worktrees[index].status = WorktreeStatus::Merged;
+        Ok(committed_leftovers)
+    }
+
+    /// Throw away an instance's worktree and branch
+    pub fn discard(&mut self, index: usize) -> Result<()> {
+        self.remove_worktree(index)?;
+        git(&self.repo_root, &["branch", "-D", &self.worktrees[index].branch])?;
+        self.worktrees[index].status = WorktreeStatus::Discarded;
+        Ok(())
+    }
+
+    /// Remove the session's worktree directory once no instance is active
+    pub fn cleanup(&self) -> Result<bool> {
+        if self.worktrees.iter().any(|w| w.status == WorktreeStatus::Active) {
+            return Ok(false);
+        }
+        if let Some(session_dir) = self.worktrees.first().and_then(|w| w.path.parent()) {
+            if session_dir.exists() && fs::read_dir(session_dir)?.next().is_none() {
+                fs::remove_dir(session_dir)?;
+            }
+        }
+        let _ = git(&self.repo_root, &["worktree", "prune"]);
+        Ok(true)
+    }
+
+    fn remove_worktree(&self, index: usize) -> Result<()> {
+        let path = &self.worktrees[index].path;
+        if path.exists() {
+            git(&self.repo_root, &["worktree", "remove", "--force", &path.to_string_lossy()])?;
+        }
+        Ok(())
+    }
+}
+
+/// Untracked, non-ignored files of a worktree, which `git diff <base>` leaves out
+fn untracked_files(worktree: &Path) -> Result<Vec<String>> {
+    let output = git(worktree, &["ls-files", "--others", "--exclude-standard", "-z"])?;
+    Ok(output.split('\0').filter(|f| !f.is_empty()).map(str::to_string).collect())
+}
+
+/// Diff of an untracked file as if newly added, without touching the index
+fn diff_new_file(worktree: &Path, file: &str, format: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["diff", "--no-index", format, "--", "/dev/null", file])
+        .current_dir(worktree)
+        .output()
+        .with_context(|| format!("Failed to diff {}", file))?;
+
+    // --no-index exits with 1 when the files differ
+    if output.status.code() != Some(0) && output.status.code() != Some(1) {
+        return Err(anyhow!("git diff --no-index {} failed: {}", file, String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::git::{commit_all, init_test_repo};
+    use tempfile::TempDir;
+
+    fn setup_repo() -> (TempDir, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        init_test_repo(&repo);
+        fs::write(repo.join("README.md"), "base\n").unwrap();
+        commit_all(&repo, "base");
+        (temp_dir, repo)
+    }
+
+    #[test]
+    fn test_merge_and_discard_instances() {
+        let (temp_dir, repo) = setup_repo();
+        let mut session = WorktreeSession::create("parallel_session_1", &repo).unwrap();
+        let first = session.add("Documentor (Instance-1)", "Documentor_1").unwrap();
+        let second = session.add("Documentor (Instance-2)", "Documentor_2").unwrap();
+        assert_eq!(first.branch, "ci/parallel_session_1/Documentor_1");
+
+        // One instance commits, leaves an uncommitted file; the other only edits
+        fs::write(first.path.join("api.md"), "api\n").unwrap();
+        commit_all(&first.path, "api docs");
+        fs::write(first.path.join("notes.md"), "notes\n").unwrap();
+        fs::write(second.path.join("README.md"), "clobbered\n").unwrap();
+
+        let summary = session.summary(&first).unwrap();
+        assert_eq!(summary.commits, 1);
+        assert_eq!(summary.files_changed, 2);
+        assert!(summary.dirty);
+        assert!(session.diff(&second).unwrap().contains("+clobbered"));
+        assert!(session.diff(&first).unwrap().contains("+notes"));
+        // Listing leaves the index alone
+        assert!(git(&first.path, &["diff", "--cached", "--name-only"]).unwrap().trim().is_empty());
+
+        // Merging refuses while the main worktree is on another branch
+        git(&repo, &["checkout", "-q", "-b", "elsewhere"]).unwrap();
+        assert!(session.merge(0).is_err());
+        git(&repo, &["checkout", "-q", "-"]).unwrap();
+
+        session.save(temp_dir.path()).unwrap();
+        let mut session = WorktreeSession::load(temp_dir.path()).unwrap();
+
+        assert!(session.merge(0).unwrap());
+        assert!(repo.join("api.md").exists() && repo.join("notes.md").exists());
+        assert!(!first.path.exists());
+
+        session.discard(1).unwrap();
+        assert_eq!(fs::read_to_string(repo.join("README.md")).unwrap(), "base\n");
+        assert!(git(&repo, &["branch", "--list", "ci/*"]).unwrap().trim().is_empty());
+        assert!(session.cleanup().unwrap());
+    }
+
+    #[test]
+    fn test_requires_git_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(WorktreeSession::create("s", temp_dir.path()).is_err());
+    }
+}
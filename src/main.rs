@@ -30,6 +30,7 @@ mod commands {
     pub mod ls;
     pub mod web;
     pub mod brain;
+    pub mod coord;
     pub mod docs;
 }
 
@@ -230,6 +231,7 @@ enum Commands {
     /// Examples:
     ///   ci load Athena                                    # Load single agent
     ///   ci load Athena ProjectArchitect                   # Load multiple agents as a team
+    ///   ci load Athena ProjectArchitect --budget 50000    # Trim the combined memory to ~50k tokens
     ///   ci load CLIA Tester DevOps                        # Load specialized agent team
     ///   ci load Athena --allow                            # Load with permission bypass mode
     ///   ci load Researcher --autonomous                   # Load for autonomous research operations
@@ -237,7 +239,19 @@ enum Commands {
     ///   ci load Researcher -t "Research topic"           # Load agent with task (interactive mode)
     ///   ci load Analyst Documentor --parallel -a -t "Document services" # Parallel agents with distinct sessions
     ///   ci load Documentor*7 --parallel -a -t "Document 7 modules"     # 7 Documentor instances working in parallel
+    ///   ci load Documentor*5 --parallel -a -t "..." --wait --timeout 3600 # Supervise instances until they finish
+    ///   ci load status parallel_session_1700000000        # Show per-instance status of a parallel session
+    ///   ci load Coder*3 --parallel -a -t "..." --worktrees # One git worktree and branch per instance
+    ///   ci load merge parallel_session_1700000000         # Review, merge or discard instance branches
+    ///   ci load report parallel_session_1700000000 --format html # Combined report of a parallel session
+    ///   ci load Documentor*4 --parallel -a -t "..." --shard-by modules # Split the repo between instances
+    ///   ci load --for "document the HTTP layer"          # Pick agents by capability and load them
+    ///   ci load Athena --record                           # Keep a transcript of the session
+    #[command(args_conflicts_with_subcommands = true)]
     Load {
+        #[command(subcommand)]
+        command: Option<LoadCommands>,
+        
         /// Agent name(s) - multiple agents can be specified as separate arguments
         /// Each agent name should match an available agent directory or entry in AGENTS.md
         agents: Vec<String>,
@@ -266,6 +280,63 @@ enum Commands {
         /// Launch agents in parallel sessions (separate Claude Code instances)
         #[arg(long)]
         parallel: bool,
+        
+        /// Wait for all parallel instances to exit and collect their exit codes
+        #[arg(long, requires = "parallel")]
+        wait: bool,
+        
+        /// Overall timeout in seconds when waiting for parallel instances
+        #[arg(long, requires = "wait")]
+        timeout: Option<u64>,
+        
+        /// Relaunch parallel instances that exit with a non-zero status (requires --wait)
+        #[arg(long, requires = "wait")]
+        restart_on_failure: bool,
+        
+        /// Maximum restarts per instance when --restart-on-failure is set
+        #[arg(long, default_value_t = commands::intelligence::DEFAULT_MAX_RESTARTS, requires = "restart_on_failure")]
+        max_restarts: u32,
+        
+        /// Give each parallel instance its own git worktree and branch (merge with `ci load merge`)
+        #[arg(long, requires = "parallel")]
+        worktrees: bool,
+        
+        /// Split the repository between parallel instances: files, dirs, modules or list:<file>
+        #[arg(long, value_name = "STRATEGY", requires = "parallel")]
+        shard_by: Option<helpers::sharding::ShardStrategy>,
+        
+        /// Token budget for the composed memory; lower-priority sections are dropped to fit
+        #[arg(long)]
+        budget: Option<usize>,
+        
+        /// Record a plain-text transcript of the session into the session store
+        #[arg(long, conflicts_with = "parallel")]
+        record: bool,
+        
+        /// Choose agents for a task description by capability instead of naming them (teams of more than one need --parallel)
+        #[arg(long = "for", value_name = "TASK", conflicts_with = "agents")]
+        for_task: Option<String>,
+        
+        /// Number of ranked agents to show with --for (also the maximum team size)
+        #[arg(long, default_value = "3", requires = "for_task")]
+        top: usize,
+    },
+    
+    /// Exchange coordination messages within a parallel session
+    /// 
+    /// Every instance of a `ci load --parallel` session has an inbox and outbox of typed
+    /// messages (claim, progress, finding, blocker, handoff, done). The board summarises
+    /// who claimed what, latest progress and open blockers.
+    /// 
+    /// Examples:
+    ///   ci coord send parallel_session_1700000000 claim "Parser module" --from Coder_1 --path src/parser
+    ///   ci coord send parallel_session_1700000000 blocker "Build is red" --from Coder_2
+    ///   ci coord send parallel_session_1700000000 progress "Build fixed" --from Coder_1 --resolves 1a2b3c4d
+    ///   ci coord read parallel_session_1700000000 --as Coder_2
+    ///   ci coord board parallel_session_1700000000
+    Coord {
+        #[command(subcommand)]
+        command: CoordCommands,
     },
     
     /// Start a Claude Code session with adaptive memory from CLAUDE.adaptation.md
@@ -307,9 +378,12 @@ enum Commands {
     
     /// Manage ideas, concepts, and inspirations
     Idea {
-        /// Subcommand (list, add, view, update, delete, categories, tags)
+        /// Subcommand (list, add, view, update, delete, categories, tags, migrate-store, link, unlink, graph, rank, views, move, sync)
         subcommand: String,
         
+        /// Idea IDs or unique ID prefixes (for link, unlink, move)
+        ids: Vec<String>,
+        
         /// Idea title (for add, update)
         #[arg(short, long)]
         title: Option<String>,
@@ -323,7 +397,7 @@ enum Commands {
         category: Option<String>,
         
         /// Comma-separated tags (for add, update)
-        #[arg(short, long)]
+        #[arg(long)]
         tags: Option<String>,
         
         /// Idea ID (for view, update, delete)
@@ -341,6 +415,69 @@ enum Commands {
         /// Filter for list operation
         #[arg(short, long)]
         filter: Option<String>,
+        
+        /// Target store backend for migrate-store (json, markdown) or store for move (global, project)
+        #[arg(long)]
+        to: Option<String>,
+        
+        /// Link kind for link, unlink (depends-on, duplicates, relates)
+        #[arg(long)]
+        kind: Option<String>,
+        
+        /// Output format for graph (tree, mermaid, dot, html) or rank (table, csv, json)
+        #[arg(long)]
+        format: Option<String>,
+        
+        /// Write the graph or ranked list to a file instead of the terminal
+        #[arg(short, long)]
+        output: Option<String>,
+        
+        /// Color theme of the HTML graph
+        #[arg(long, value_enum)]
+        theme: Option<VisualizationTheme>,
+        
+        /// People or events reached per period (for add, update)
+        #[arg(long)]
+        reach: Option<f64>,
+        
+        /// Impact: 0.25 minimal, 0.5 low, 1 medium, 2 high, 3 massive (for add, update)
+        #[arg(long)]
+        impact: Option<f64>,
+        
+        /// Confidence in percent (for add, update)
+        #[arg(long)]
+        confidence: Option<f64>,
+        
+        /// Effort in person-months (for add, update)
+        #[arg(long)]
+        effort: Option<f64>,
+        
+        /// Scoring model for rank (rice, ice)
+        #[arg(long)]
+        model: Option<String>,
+        
+        /// Apply a saved filter view (for list, rank)
+        #[arg(long)]
+        view: Option<String>,
+        
+        /// Save the given filters as a named view (for list, rank)
+        #[arg(long)]
+        save_view: Option<String>,
+        
+        /// Show at most this many ranked ideas
+        #[arg(long)]
+        limit: Option<usize>,
+        
+        /// List the project and the global store side by side
+        #[arg(long)]
+        all_stores: bool,
+        
+        /// Show what sync would change without writing
+        #[arg(long)]
+        dry_run: bool,
+        /// Let sync pull every global idea, not only those shared with this project
+        #[arg(long)]
+        all: bool,
     },
     
     //
@@ -737,6 +874,7 @@ enum Commands {
     ///   ci task "Analyze codebase security" SecurityExpert -a
     ///   ci task "Document API endpoints" Documentor --output docs.md
     ///   ci task "Debug performance issues" CLIA --context backend
+    ///   ci task "Update dependencies" Maintainer -a --headless --timeout 30m --json
     Task {
         /// Task description - what you want the agent to accomplish
         description: String,
@@ -759,6 +897,68 @@ enum Commands {
         /// Memory path override
         #[arg(short = 'f', long)]
         path: Option<PathBuf>,
+        
+        /// Run without an interactive session, logging output under the session directory
+        #[arg(long)]
+        headless: bool,
+        
+        /// Stop a headless run after this long (e.g. 90, 30s, 15m, 2h)
+        #[arg(long, requires = "headless", value_parser = helpers::headless::parse_timeout)]
+        timeout: Option<std::time::Duration>,
+        
+        /// Print the headless result as JSON
+        #[arg(long, requires = "headless")]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum LoadCommands {
+    /// Show per-instance status of a parallel session (lists recent sessions without an id)
+    Status {
+        /// Parallel session id (e.g. parallel_session_1700000000)
+        session_id: Option<String>,
+    },
+    
+    /// Review, merge or discard the instance branches of a --worktrees session
+    ///
+    /// Without flags, asks per instance whether to merge, discard, view the diff or skip.
+    Merge {
+        /// Parallel session id
+        session_id: String,
+        
+        /// Only this instance (name like Documentor_2, display name or branch)
+        instance: Option<String>,
+        
+        /// Only list instance branches with their changes
+        #[arg(long, conflicts_with_all = ["diff", "merge", "discard"])]
+        list: bool,
+        
+        /// Show each instance's diff against the session base
+        #[arg(long, conflicts_with_all = ["merge", "discard"])]
+        diff: bool,
+        
+        /// Merge without asking
+        #[arg(long, conflicts_with = "discard")]
+        merge: bool,
+        
+        /// Discard without asking
+        #[arg(long)]
+        discard: bool,
+    },
+    
+    /// Write a final report of a parallel session: per-instance outputs, changes and open blockers
+    Report {
+        /// Parallel session id
+        session_id: String,
+        
+        /// Report format: md or html
+        #[arg(long, default_value = "md", value_parser = clap::value_parser!(helpers::parallel_report::ReportFormat))]
+        format: helpers::parallel_report::ReportFormat,
+        
+        /// Output file (defaults to report.<format> in the coordination directory; - for stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 }
 
@@ -860,14 +1060,26 @@ enum AgentCommands {
     },
     
     /// Create a new agent
+    ///
+    /// Examples:
+    ///   ci agent create Scout --template researcher --var domain=payments
+    ///   ci agent create Scout -t researcher --vars-file scout.toml
     Create {
         /// Name of the new agent
         agent_name: String,
         
-        /// Create from template
+        /// Create from template (default: basic)
         #[arg(short, long)]
         template: Option<String>,
         
+        /// Set a template variable (repeatable)
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = helpers::agent_templates::parse_var)]
+        vars: Vec<(String, String)>,
+        
+        /// Read template variables from a TOML file
+        #[arg(long)]
+        vars_file: Option<String>,
+        
         /// Enable the agent after creation
         #[arg(long)]
         enable: bool,
@@ -904,13 +1116,31 @@ enum AgentCommands {
         free: bool,
     },
     
-    /// Create an agent from a template
+    /// Manage agent templates, or create an agent from a template
+    ///
+    /// Examples:
+    ///   ci agent template list
+    ///   ci agent template show researcher
+    ///   ci agent template new triage --from reviewer
+    ///   ci agent template researcher Scout --var domain=payments
+    #[command(args_conflicts_with_subcommands = true, arg_required_else_help = true)]
     Template {
+        #[command(subcommand)]
+        command: Option<AgentTemplateCommands>,
+        
         /// Name of the template
-        template_name: String,
+        template_name: Option<String>,
         
         /// Name for the new agent
         agent_name: Option<String>,
+        
+        /// Set a template variable (repeatable)
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = helpers::agent_templates::parse_var)]
+        vars: Vec<(String, String)>,
+        
+        /// Read template variables from a TOML file
+        #[arg(long)]
+        vars_file: Option<String>,
     },
     
     /// Deploy CI tool globally with latest changes
@@ -938,6 +1168,187 @@ enum AgentCommands {
         /// Name of the agent to activate in voice mode
         agent_name: String,
     },
+    
+    /// Validate agent directories and their agent.toml manifests
+    /// 
+    /// Examples:
+    ///   ci agent validate Athena     # Validate a single agent
+    ///   ci agent validate --all      # Validate every agent
+    Validate {
+        /// Name of the agent to validate
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        agent_name: Option<String>,
+        
+        /// Validate every agent
+        #[arg(long)]
+        all: bool,
+    },
+    
+    /// Print an agent's memory with extends/mixins resolved
+    ///
+    /// Examples:
+    ///   ci agent render Athena              # Show the fully inherited memory
+    ///   ci agent render Athena > out.md     # Save it (chain is printed to stderr)
+    Render {
+        /// Name of the agent to render
+        agent_name: String,
+    },
+    
+    /// Rank agents by how well their capabilities match a task
+    ///
+    /// Examples:
+    ///   ci agent suggest "document the HTTP layer"
+    ///   ci agent suggest "profile slow queries" --top 3
+    Suggest {
+        /// Task description
+        #[arg(required = true, num_args = 1..)]
+        task: Vec<String>,
+        
+        /// Number of agents to show
+        #[arg(long, default_value = "5")]
+        top: usize,
+    },
+    
+    /// Show agent usage statistics from recorded sessions
+    ///
+    /// Examples:
+    ///   ci agent stats                  # All recorded sessions
+    ///   ci agent stats --since 7d       # Sessions from the last week
+    ///   ci agent stats --since 2024-05-01 --json
+    Stats {
+        /// Only count sessions started after a date (YYYY-MM-DD) or age (12h, 7d, 4w)
+        #[arg(long, value_parser = helpers::agent_stats::parse_since)]
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        
+        /// Print statistics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Export an agent as a portable bundle (tar.gz with manifest and checksums)
+    ///
+    /// Examples:
+    ///   ci agent export Athena                      # Writes ./Athena.ciagent.tar.gz
+    ///   ci agent export Athena -o /tmp/athena.tar.gz --include-sessions
+    Export {
+        /// Name of the agent to export
+        agent_name: String,
+        
+        /// Bundle path (default: ./<Name>.ciagent.tar.gz)
+        #[arg(short, long)]
+        output: Option<String>,
+        
+        /// Include the agent's Sessions directory
+        #[arg(long)]
+        include_sessions: bool,
+    },
+    
+    /// Import an agent bundle and register it in AGENTS.md
+    ///
+    /// Examples:
+    ///   ci agent import Athena.ciagent.tar.gz
+    ///   ci agent import Athena.ciagent.tar.gz --on-conflict merge
+    ///   ci agent import Athena.ciagent.tar.gz --as Pallas
+    Import {
+        /// Path to the bundle
+        bundle: String,
+        
+        /// What to do when the agent already exists
+        #[arg(long, value_parser = ["rename", "overwrite", "merge"])]
+        on_conflict: Option<String>,
+        
+        /// Install the agent under a different name
+        #[arg(long = "as")]
+        as_name: Option<String>,
+        
+        /// Import even if the bundled agent fails validation
+        #[arg(long)]
+        force: bool,
+    },
+    
+    /// Inspect and restore versions of an agent's memory files
+    ///
+    /// Snapshots are recorded on 'ci load' and when sessions end.
+    ///
+    /// Examples:
+    ///   ci agent memory log Athena              # List snapshots
+    ///   ci agent memory diff Athena             # Latest snapshot vs working copy
+    ///   ci agent memory diff Athena 3f2a 9c1d   # Between two snapshots
+    ///   ci agent memory revert Athena 3f2a      # Restore a snapshot
+    Memory {
+        #[command(subcommand)]
+        command: AgentMemoryCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum AgentTemplateCommands {
+    /// List built-in and user templates
+    List,
+    
+    /// Show a template's variables and files
+    Show {
+        /// Name of the template
+        template_name: String,
+    },
+    
+    /// Create a user template to customize
+    New {
+        /// Name of the new template
+        template_name: String,
+        
+        /// Template to start from
+        #[arg(long, default_value = "basic")]
+        from: String,
+        
+        /// Template description
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AgentMemoryCommands {
+    /// List recorded memory snapshots
+    Log {
+        /// Name of the agent
+        agent_name: String,
+        
+        /// Show only the most recent N snapshots
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+    
+    /// Show a unified diff between snapshots (default: latest vs working copy)
+    Diff {
+        /// Name of the agent
+        agent_name: String,
+        
+        /// Snapshot to diff from
+        from: Option<String>,
+        
+        /// Snapshot to diff to (default: working copy)
+        to: Option<String>,
+    },
+    
+    /// Restore the memory files of a snapshot
+    Revert {
+        /// Name of the agent
+        agent_name: String,
+        
+        /// Snapshot id (or unique prefix)
+        snapshot: String,
+        
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    
+    /// Record the current memory files now
+    Snapshot {
+        /// Name of the agent
+        agent_name: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -1196,6 +1607,22 @@ enum SessionCommands {
         /// Show only recent sessions
         #[arg(short, long, default_value = "10")]
         recent: String,
+        
+        /// Filter sessions by project directory ('.' for the current one)
+        #[arg(short, long, value_name = "PATH")]
+        project: Option<String>,
+        
+        /// Only sessions started since a date (YYYY-MM-DD) or age (12h, 7d, 4w)
+        #[arg(long, value_name = "WHEN")]
+        since: Option<String>,
+        
+        /// Only sessions started before a date (YYYY-MM-DD) or age (12h, 7d, 4w)
+        #[arg(long, value_name = "WHEN")]
+        until: Option<String>,
+        
+        /// Filter sessions by tag
+        #[arg(long)]
+        tag: Option<String>,
     },
     
     /// Create a new session
@@ -1233,7 +1660,47 @@ enum SessionCommands {
         session_name: String,
     },
     
-    /// Clean up old sessions
+    /// Show the commits and diff stat produced during a session
+    Changes {
+        /// Session id (or a unique prefix) from 'ci session list'
+        session_id: String,
+    },
+    
+    /// Export a session report with agents, task, timing, memory excerpt,
+    /// coordination files and git changes
+    Export {
+        /// Session id (or a unique prefix) from 'ci session list'
+        session_id: String,
+        
+        /// Report format: md, html or json
+        #[arg(long, default_value = "md", value_parser = clap::value_parser!(helpers::session_export::ExportFormat))]
+        format: helpers::session_export::ExportFormat,
+        
+        /// Output file (defaults to report.<format> in the session's store directory; - for stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        
+        /// Theme of HTML reports
+        #[arg(long, value_enum, default_value = "light")]
+        theme: VisualizationTheme,
+    },
+    
+    /// Import existing session records into the session store
+    Migrate,
+    
+    /// Relaunch a previous session with its agents, task, permission mode and
+    /// working directory, starting from a summary of what it did
+    Resume {
+        /// Session id (or a unique prefix) from 'ci session list'
+        session_id: String,
+        
+        /// Record a plain-text transcript of the resumed session
+        #[arg(long)]
+        record: bool,
+    },
+    
+    /// Archive old sessions and apply the retention policy of .ci-config.json
+    /// (sessions.retention); removed sessions stay in the trash for a while
     Cleanup {
         /// Archive sessions older than N days
         #[arg(short, long, default_value = "30")]
@@ -1242,6 +1709,69 @@ enum SessionCommands {
         /// Show what would be cleaned up without making changes
         #[arg(long)]
         dry_run: bool,
+        
+        /// Move a session removed by an earlier cleanup back out of the trash
+        #[arg(long, value_name = "SESSION_ID")]
+        restore: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CoordCommands {
+    /// Send a message to one instance or broadcast it to all others
+    Send {
+        /// Parallel session id (e.g. parallel_session_1700000000)
+        session_id: String,
+        
+        /// Message kind: claim, progress, finding, blocker, handoff or done
+        kind: helpers::coordination::MessageKind,
+        
+        /// Message text
+        message: String,
+        
+        /// Sending instance (e.g. Coder_2); people use the default
+        #[arg(long, default_value = helpers::coordination::HUMAN_SENDER)]
+        from: String,
+        
+        /// Recipient instance (broadcast when omitted)
+        #[arg(long)]
+        to: Option<String>,
+        
+        /// Path claimed, handed off or reported on (repeatable)
+        #[arg(short, long = "path")]
+        paths: Vec<String>,
+        
+        /// Id of the blocker this message resolves
+        #[arg(long)]
+        resolves: Option<String>,
+    },
+    
+    /// Show an instance's inbox and mark the messages read
+    Read {
+        /// Parallel session id
+        session_id: String,
+        
+        /// Instance whose inbox to read (e.g. Coder_2, or human)
+        #[arg(long = "as", default_value = helpers::coordination::HUMAN_SENDER)]
+        instance: String,
+        
+        /// Include messages already read
+        #[arg(long)]
+        all: bool,
+        
+        /// Print messages as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Show claims, progress and open blockers of the session
+    Board {
+        /// Parallel session id
+        session_id: String,
+        
+        /// Print the board as JSON
+        #[arg(long)]
+        json: bool,
     },
 }
 
@@ -1369,6 +1899,8 @@ fn get_colored_command_help() -> String {
     help_text.push_str("\n");
     help_text.push_str(&format!("  {:<12} {}", "load".cyan(), "Start a Claude Code session with specified agent(s) loaded".cyan()));
     help_text.push_str("\n");
+    help_text.push_str(&format!("  {:<12} {}", "coord".cyan(), "Exchange coordination messages within a parallel session".cyan()));
+    help_text.push_str("\n");
     help_text.push_str(&format!("  {:<12} {}", "projects".cyan(), "List projects integrated with Collaborative Intelligence".cyan()));
     help_text.push_str("\n");
     help_text.push_str(&format!("  {:<12} {}", "idea".cyan(), "Manage ideas, concepts, and inspirations".cyan()));
@@ -1431,6 +1963,20 @@ fn print_help_with_categories() {
 // }
 
 /// Handle agent commands
+/// Rebuild `--var` / `--vars-file` arguments for the agents command parser
+fn template_var_args(vars: &[(String, String)], vars_file: &Option<String>) -> Vec<String> {
+    let mut args = Vec::new();
+    for (key, value) in vars {
+        args.push("--var".to_string());
+        args.push(format!("{}={}", key, value));
+    }
+    if let Some(path) = vars_file {
+        args.push("--vars-file".to_string());
+        args.push(path.clone());
+    }
+    args
+}
+
 async fn handle_agent_command(command: &AgentCommands) -> anyhow::Result<()> {
     match command {
         AgentCommands::List { enabled_only, verbose } => {
@@ -1452,12 +1998,14 @@ async fn handle_agent_command(command: &AgentCommands) -> anyhow::Result<()> {
                 .unwrap_or_else(|_| clap::ArgMatches::default());
             commands::agents::execute(&args)
         },
-        AgentCommands::Create { agent_name, template, enable } => {
+        AgentCommands::Create { agent_name, template, vars, vars_file, enable } => {
             let mut cmd = commands::agents::create_command();
+            let var_args = template_var_args(vars, vars_file);
             let mut args_vec = vec!["agent", "create", agent_name];
             if let Some(tmpl) = template {
                 args_vec.extend(vec!["--template", tmpl]);
             }
+            args_vec.extend(var_args.iter().map(String::as_str));
             if *enable {
                 args_vec.push("--enable");
             }
@@ -1497,11 +2045,24 @@ async fn handle_agent_command(command: &AgentCommands) -> anyhow::Result<()> {
                 .unwrap_or_else(|_| clap::ArgMatches::default());
             commands::agents::execute(&args)
         },
-        AgentCommands::Template { template_name, agent_name } => {
-            let mut cmd = commands::agents::create_command();
-            let mut args_vec = vec!["agent", "template", template_name];
-            if let Some(name) = agent_name {
-                args_vec.push(name);
+        AgentCommands::Template { command, template_name, agent_name, vars, vars_file } => {
+            let cmd = commands::agents::create_command();
+            let var_args = template_var_args(vars, vars_file);
+            let mut args_vec = vec!["agent", "template"];
+            match command {
+                Some(AgentTemplateCommands::List) => args_vec.push("list"),
+                Some(AgentTemplateCommands::Show { template_name }) => args_vec.extend(["show", template_name.as_str()]),
+                Some(AgentTemplateCommands::New { template_name, from, description }) => {
+                    args_vec.extend(["new", template_name.as_str(), "--from", from.as_str()]);
+                    if let Some(description) = description {
+                        args_vec.extend(["--description", description.as_str()]);
+                    }
+                },
+                None => {
+                    args_vec.extend(template_name.as_deref());
+                    args_vec.extend(agent_name.as_deref());
+                    args_vec.extend(var_args.iter().map(String::as_str));
+                },
             }
             let args = cmd.try_get_matches_from(args_vec)
                 .unwrap_or_else(|_| clap::ArgMatches::default());
@@ -1538,33 +2099,166 @@ async fn handle_agent_command(command: &AgentCommands) -> anyhow::Result<()> {
                 .unwrap_or_else(|_| clap::ArgMatches::default());
             commands::agents::execute(&args)
         },
+        AgentCommands::Validate { agent_name, all } => {
+            let cmd = commands::agents::create_command();
+            let mut args_vec = vec!["agent", "validate"];
+            if let Some(name) = agent_name {
+                args_vec.push(name);
+            }
+            if *all {
+                args_vec.push("--all");
+            }
+            let args = cmd.try_get_matches_from(args_vec)
+                .unwrap_or_else(|_| clap::ArgMatches::default());
+            commands::agents::execute(&args)
+        },
+        AgentCommands::Render { agent_name } => {
+            let cmd = commands::agents::create_command();
+            let args = cmd.try_get_matches_from(vec!["agent", "render", agent_name])
+                .unwrap_or_else(|_| clap::ArgMatches::default());
+            commands::agents::execute(&args)
+        },
+        AgentCommands::Suggest { task, top } => {
+            let cmd = commands::agents::create_command();
+            let top_str = top.to_string();
+            let mut args_vec = vec!["agent", "suggest"];
+            args_vec.extend(task.iter().map(String::as_str));
+            args_vec.extend(["--top", top_str.as_str()]);
+            let args = cmd.try_get_matches_from(args_vec)
+                .unwrap_or_else(|_| clap::ArgMatches::default());
+            commands::agents::execute(&args)
+        },
+        AgentCommands::Stats { since, json } => {
+            let cmd = commands::agents::create_command();
+            let since_str = since.map(|since| since.to_rfc3339());
+            let mut args_vec = vec!["agent", "stats"];
+            if let Some(since) = &since_str {
+                args_vec.extend(["--since", since.as_str()]);
+            }
+            if *json {
+                args_vec.push("--json");
+            }
+            let args = cmd.try_get_matches_from(args_vec)
+                .unwrap_or_else(|_| clap::ArgMatches::default());
+            commands::agents::execute(&args)
+        },
+        AgentCommands::Export { agent_name, output, include_sessions } => {
+            let cmd = commands::agents::create_command();
+            let mut args_vec = vec!["agent", "export", agent_name.as_str()];
+            if let Some(output) = output {
+                args_vec.extend(["--output", output.as_str()]);
+            }
+            if *include_sessions {
+                args_vec.push("--include-sessions");
+            }
+            let args = cmd.try_get_matches_from(args_vec)
+                .unwrap_or_else(|_| clap::ArgMatches::default());
+            commands::agents::execute(&args)
+        },
+        AgentCommands::Import { bundle, on_conflict, as_name, force } => {
+            let cmd = commands::agents::create_command();
+            let mut args_vec = vec!["agent", "import", bundle.as_str()];
+            if let Some(strategy) = on_conflict {
+                args_vec.extend(["--on-conflict", strategy.as_str()]);
+            }
+            if let Some(name) = as_name {
+                args_vec.extend(["--as", name.as_str()]);
+            }
+            if *force {
+                args_vec.push("--force");
+            }
+            let args = cmd.try_get_matches_from(args_vec)
+                .unwrap_or_else(|_| clap::ArgMatches::default());
+            commands::agents::execute(&args)
+        },
+        AgentCommands::Memory { command } => {
+            let cmd = commands::agents::create_command();
+            let limit_str;
+            let args_vec = match command {
+                AgentMemoryCommands::Log { agent_name, limit } => {
+                    let mut args_vec = vec!["agent", "memory", "log", agent_name.as_str()];
+                    if let Some(limit) = limit {
+                        limit_str = limit.to_string();
+                        args_vec.extend(["--limit", limit_str.as_str()]);
+                    }
+                    args_vec
+                },
+                AgentMemoryCommands::Diff { agent_name, from, to } => {
+                    let mut args_vec = vec!["agent", "memory", "diff", agent_name.as_str()];
+                    args_vec.extend(from.as_deref());
+                    args_vec.extend(to.as_deref());
+                    args_vec
+                },
+                AgentMemoryCommands::Revert { agent_name, snapshot, yes } => {
+                    let mut args_vec = vec!["agent", "memory", "revert", agent_name.as_str(), snapshot.as_str()];
+                    if *yes {
+                        args_vec.push("--yes");
+                    }
+                    args_vec
+                },
+                AgentMemoryCommands::Snapshot { agent_name } => {
+                    vec!["agent", "memory", "snapshot", agent_name.as_str()]
+                },
+            };
+            let args = cmd.try_get_matches_from(args_vec)
+                .unwrap_or_else(|_| clap::ArgMatches::default());
+            commands::agents::execute(&args)
+        },
     }
 }
 
 /// Handle session commands
-async fn handle_session_command(command: &SessionCommands) -> anyhow::Result<()> {
+async fn handle_session_command(command: &SessionCommands, config: &config::Config) -> anyhow::Result<()> {
+    let mut args_vec = vec!["session"];
     match command {
-        SessionCommands::List { agent: _, status: _, recent: _ } => {
-            let args = clap::ArgMatches::default();
-            commands::session::execute(&args)
+        SessionCommands::Resume { session_id, record } => {
+            return commands::intelligence::resume_session(session_id, *record, config).await;
+        },
+        SessionCommands::Export { session_id, format, output, theme } => {
+            return commands::session::export_session(session_id, *format, output.as_deref(), theme.clone()).await;
+        },
+        SessionCommands::List { agent, status, recent, project, since, until, tag } => {
+            args_vec.extend(["list", "--recent", recent]);
+            for (flag, value) in [("--agent", agent), ("--status", status), ("--project", project), ("--since", since), ("--until", until), ("--tag", tag)] {
+                if let Some(value) = value {
+                    args_vec.extend([flag, value]);
+                }
+            }
+        },
+        SessionCommands::Create { agent_name, session_name, description, tags } => {
+            args_vec.extend(["create", agent_name, session_name]);
+            if let Some(description) = description {
+                args_vec.extend(["--description", description]);
+            }
+            if let Some(tags) = tags {
+                args_vec.extend(["--tags", tags]);
+            }
         },
-        SessionCommands::Create { agent_name: _, session_name: _, description: _, tags: _ } => {
-            let args = clap::ArgMatches::default();
-            commands::session::execute(&args)
+        SessionCommands::Info { agent_name, session_name } => {
+            args_vec.extend(["info", agent_name, session_name]);
         },
-        SessionCommands::Info { agent_name: _, session_name: _ } => {
-            let args = clap::ArgMatches::default();
-            commands::session::execute(&args)
+        SessionCommands::Archive { agent_name, session_name } => {
+            args_vec.extend(["archive", agent_name, session_name]);
         },
-        SessionCommands::Archive { agent_name: _, session_name: _ } => {
-            let args = clap::ArgMatches::default();
-            commands::session::execute(&args)
+        SessionCommands::Changes { session_id } => {
+            args_vec.extend(["changes", session_id]);
         },
-        SessionCommands::Cleanup { days: _, dry_run: _ } => {
-            let args = clap::ArgMatches::default();
-            commands::session::execute(&args)
+        SessionCommands::Migrate => {
+            args_vec.push("migrate");
+        },
+        SessionCommands::Cleanup { days, dry_run, restore } => {
+            args_vec.extend(["cleanup", "--days", days]);
+            if *dry_run {
+                args_vec.push("--dry-run");
+            }
+            if let Some(restore) = restore {
+                args_vec.extend(["--restore", restore]);
+            }
         },
     }
+    
+    let args = commands::session::create_command().try_get_matches_from(args_vec)?;
+    commands::session::execute(&args)
 }
 
 /// Handle the legacy command for managing legacy command compatibility
@@ -2015,6 +2709,7 @@ async fn main() -> anyhow::Result<()> {
         Some(Commands::Agent { .. }) => true,
         Some(Commands::Agents) => true,
         Some(Commands::Load { .. }) => true, // Skip for load commands to eliminate verbose output
+        Some(Commands::Coord { .. }) => true, // Called by agents mid-session
         _ => false,
     };
     
@@ -2030,6 +2725,7 @@ async fn main() -> anyhow::Result<()> {
                 Commands::Agents => "agents", 
                 Commands::Agent { .. } => "agent",
                 Commands::Load { .. } => "load",
+                Commands::Coord { .. } => "coord",
                 Commands::Adapt { .. } => "adapt",
                 Commands::Projects => "projects",
                 Commands::Idea { .. } => "idea",
@@ -2111,10 +2807,46 @@ async fn main() -> anyhow::Result<()> {
         Commands::Agent { command } => {
             handle_agent_command(&command).await
         },
-        Commands::Load { agents, context, path, prompt, allow, task, parallel } => {
+        Commands::Load { command: Some(load_command), .. } => {
+            match load_command {
+                LoadCommands::Status { session_id } => {
+                    commands::intelligence::load_status(session_id.as_deref(), &config).await
+                },
+                LoadCommands::Merge { session_id, instance, list, diff, merge, discard } => {
+                    use commands::intelligence::MergeMode;
+                    let mode = match (list, diff, merge, discard) {
+                        (true, ..) => MergeMode::List,
+                        (_, true, ..) => MergeMode::Diff,
+                        (_, _, true, _) => MergeMode::Merge,
+                        (.., true) => MergeMode::Discard,
+                        _ => MergeMode::Interactive,
+                    };
+                    commands::intelligence::load_merge(&session_id, instance.as_deref(), mode, &config).await
+                },
+                LoadCommands::Report { session_id, format, output } => {
+                    commands::intelligence::load_report(&session_id, format, output.as_deref(), &config).await
+                },
+            }
+        },
+        Commands::Load { command: None, agents, context, path, prompt, allow, task, parallel, wait, timeout, restart_on_failure, max_restarts, worktrees, shard_by, budget, record, for_task, top } => {
             // Invert the prompt flag - default is auto-launch (true), --prompt makes it false
             let auto_yes = !prompt;
-            commands::intelligence::load_agents_with_task(&agents, context.as_deref(), path.as_deref(), auto_yes, allow, task.as_deref(), parallel, &config).await
+            let parallel_options = commands::intelligence::ParallelOptions {
+                wait,
+                timeout_secs: timeout,
+                restart_on_failure,
+                max_restarts,
+                worktrees,
+                shard_by,
+            };
+            let load_options = commands::intelligence::LoadOptions { budget, record, ..Default::default() };
+            match for_task {
+                Some(description) => match commands::intelligence::select_agents_for_task(&description, top, &config)? {
+                    Some(selected) => commands::intelligence::load_agents_with_task(&selected, context.as_deref(), path.as_deref(), auto_yes, allow, &load_options, Some(task.as_deref().unwrap_or(&description)), parallel, &parallel_options, &config).await,
+                    None => Ok(()),
+                },
+                None => commands::intelligence::load_agents_with_task(&agents, context.as_deref(), path.as_deref(), auto_yes, allow, &load_options, task.as_deref(), parallel, &parallel_options, &config).await,
+            }
         },
         Commands::Adapt { path } => {
             commands::intelligence::adapt_session(&path, &config).await
@@ -2125,19 +2857,35 @@ async fn main() -> anyhow::Result<()> {
         Commands::Docs { command } => {
             commands::docs::handle_docs_command(&command, &config).await
         },
-        Commands::Idea { subcommand, title, description, category, tags, id, status, priority, filter } => {
-            commands::idea::idea(
-                &subcommand,
-                title.as_deref(),
-                description.as_deref(),
-                category.as_deref(),
-                tags.as_deref(),
-                id.as_deref(),
-                status.as_deref(),
-                priority.as_deref(),
-                filter.as_deref(),
-                &config
-            ).await
+        Commands::Idea {
+            subcommand, ids, title, description, category, tags, id, status, priority, filter, to, kind, format, output, theme,
+            reach, impact, confidence, effort, model, view, save_view, limit, all_stores, dry_run, all,
+        } => {
+            let options = commands::idea::IdeaOptions {
+                ids: &ids,
+                title: title.as_deref(),
+                description: description.as_deref(),
+                category: category.as_deref(),
+                tags: tags.as_deref(),
+                id: id.as_deref(),
+                status: status.as_deref(),
+                priority: priority.as_deref(),
+                filter: filter.as_deref(),
+                to: to.as_deref(),
+                kind: kind.as_deref(),
+                format: format.as_deref(),
+                output: output.as_deref(),
+                theme,
+                scores: commands::idea::ScoreInputs { reach, impact, confidence, effort },
+                model: model.as_deref(),
+                view: view.as_deref(),
+                save_view: save_view.as_deref(),
+                limit,
+                all_stores,
+                dry_run,
+                all,
+            };
+            commands::idea::idea(&subcommand, options, &config).await
         },
         
         // Source Control Commands
@@ -2204,10 +2952,10 @@ async fn main() -> anyhow::Result<()> {
                     );
                     
                     // Launch the agent team
-                    commands::intelligence::load_agents(&agent_list, Some(&task_context), None, true, autonomous, &config).await
+                    commands::intelligence::load_agents(&agent_list, Some(&task_context), None, true, autonomous, &Default::default(), &config).await
                 } else {
                     // Just autonomous mode without task mode
-                    commands::intelligence::load_agents(&agent_list, None, None, true, autonomous, &config).await
+                    commands::intelligence::load_agents(&agent_list, None, None, true, autonomous, &Default::default(), &config).await
                 }
             } else {
                 // Regular init mode
@@ -2325,7 +3073,7 @@ async fn main() -> anyhow::Result<()> {
             commands::topology::topology(&topology_command, &config).await
         },
         Commands::Session { command } => {
-            handle_session_command(&command).await
+            handle_session_command(&command, &config).await
         },
         Commands::Visualize { view } => {
             commands::visualize::handle_visualization_command(&view, &config).await
@@ -2351,8 +3099,20 @@ async fn main() -> anyhow::Result<()> {
         Commands::Brain { command } => {
             commands::brain::handle_brain_command(&command, &config).await
         },
-        Commands::Task { description, agent, autonomous, context, output, path } => {
-            commands::intelligence::execute_task(&description, &agent, autonomous, context.as_deref(), output.as_deref(), path.as_deref(), &config).await
+        Commands::Coord { command } => {
+            commands::coord::handle_coord_command(&command, &config).await
+        },
+        Commands::Task { description, agent, autonomous, context, output, path, headless, timeout, json } => {
+            if headless {
+                let options = commands::intelligence::HeadlessTaskOptions {
+                    timeout,
+                    memory_path: path.as_deref(),
+                    json,
+                };
+                commands::intelligence::execute_task_headless(&description, &agent, autonomous, context.as_deref(), output.as_deref(), &options, &config).await
+            } else {
+                commands::intelligence::execute_task(&description, &agent, autonomous, context.as_deref(), output.as_deref(), path.as_deref(), &config).await
+            }
         },
     };
     
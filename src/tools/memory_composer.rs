@@ -0,0 +1,381 @@
+//! Token-budget-aware memory composition
+//!
+//! Agent memories are split into markdown sections, identical sections shared
+//! between agents are emitted only once and, when a token budget is set, the
+//! lowest-priority sections are dropped until the result fits. The composer
+//! reports everything it removed so the user can see what the session lost.
+
+use colored::*;
+use std::collections::HashMap;
+
+/// Rough characters-per-token ratio used for estimates
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Heading keywords marking sections that should survive trimming as long as possible
+const HIGH_PRIORITY_KEYWORDS: &[&str] = &[
+    "identity", "role", "core", "principle", "mission", "responsibilit", "task", "directive",
+];
+
+/// Heading keywords marking sections that are trimmed first
+const LOW_PRIORITY_KEYWORDS: &[&str] = &[
+    "example", "history", "log", "archive", "changelog", "learning", "appendix", "notes", "session",
+];
+
+/// Estimate the number of tokens in a piece of text
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Trimming priority of a memory section (lower is dropped first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SectionPriority {
+    Low,
+    Normal,
+    High,
+    /// Never dropped (session headers, task context)
+    Pinned,
+}
+
+/// A markdown section belonging to one memory source
+#[derive(Debug, Clone)]
+struct Section {
+    source: usize,
+    heading: String,
+    text: String,
+    tokens: usize,
+    priority: SectionPriority,
+}
+
+/// A section that was removed from the composed memory
+#[derive(Debug, Clone)]
+pub struct RemovedSection {
+    /// Owner of the section (usually the agent name)
+    pub owner: String,
+
+    /// Section heading, or "(preamble)" for text before the first heading
+    pub heading: String,
+
+    /// Estimated tokens saved
+    pub tokens: usize,
+
+    /// Owner of the copy that was kept (deduplicated sections only)
+    pub kept_in: Option<String>,
+}
+
+/// Summary of what the composer did
+#[derive(Debug, Clone, Default)]
+pub struct CompositionReport {
+    pub budget: Option<usize>,
+    pub tokens_before: usize,
+    pub tokens_after: usize,
+    pub deduplicated: Vec<RemovedSection>,
+    pub dropped: Vec<RemovedSection>,
+}
+
+impl CompositionReport {
+    /// Whether the composed memory still exceeds the budget (pinned sections alone can do this)
+    pub fn over_budget(&self) -> bool {
+        self.budget.is_some_and(|budget| self.tokens_after > budget)
+    }
+
+    /// Print the report; stays quiet when nothing was removed and the budget holds
+    pub fn print(&self) {
+        if self.deduplicated.is_empty() && self.dropped.is_empty() && !self.over_budget() {
+            return;
+        }
+
+        let budget = self.budget
+            .map(|b| format!(" (budget {})", b))
+            .unwrap_or_default();
+        println!("📉 Memory composition: ~{} → ~{} tokens{}",
+            self.tokens_before, self.tokens_after, budget);
+
+        for section in &self.deduplicated {
+            println!("   {} Deduplicated {} from {} (kept in {}, ~{} tokens)",
+                "♻".cyan(),
+                section.heading.bold(),
+                section.owner,
+                section.kept_in.as_deref().unwrap_or("-"),
+                section.tokens
+            );
+        }
+
+        for section in &self.dropped {
+            println!("   {} Dropped {} from {} (~{} tokens)",
+                "✂".yellow(),
+                section.heading.bold(),
+                section.owner,
+                section.tokens
+            );
+        }
+
+        if self.over_budget() {
+            println!("   {} Pinned sections alone exceed the budget", "⚠".yellow());
+        }
+    }
+}
+
+/// Result of composing memory sources
+#[derive(Debug, Clone)]
+pub struct ComposedMemory {
+    pub content: String,
+    pub report: CompositionReport,
+}
+
+/// Combines agent memories into one document within an optional token budget
+#[derive(Debug, Clone, Default)]
+pub struct MemoryComposer {
+    budget: Option<usize>,
+    separator: String,
+    owners: Vec<String>,
+    sections: Vec<Section>,
+}
+
+impl MemoryComposer {
+    /// Create a composer; `budget` is the maximum estimated token count
+    pub fn new(budget: Option<usize>) -> Self {
+        MemoryComposer {
+            budget,
+            separator: "\n\n".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Text inserted between consecutive sources
+    pub fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Add a memory source that may be deduplicated and trimmed section by section
+    pub fn add_source(&mut self, owner: &str, content: &str) {
+        self.push_source(owner, content, false);
+    }
+
+    /// Add a memory source that is always kept in full
+    pub fn add_pinned(&mut self, owner: &str, content: &str) {
+        self.push_source(owner, content, true);
+    }
+
+    fn push_source(&mut self, owner: &str, content: &str, pinned: bool) {
+        let source = self.owners.len();
+        self.owners.push(owner.to_string());
+
+        for (index, (heading, text)) in split_sections(content).into_iter().enumerate() {
+            let priority = if pinned {
+                SectionPriority::Pinned
+            } else if index == 0 {
+                // The opening section carries the agent's identity
+                SectionPriority::High
+            } else {
+                classify_heading(&heading)
+            };
+
+            self.sections.push(Section {
+                source,
+                tokens: estimate_tokens(&text),
+                heading,
+                text,
+                priority,
+            });
+        }
+    }
+
+    /// Compose all sources into one document
+    pub fn compose(&self) -> ComposedMemory {
+        let mut report = CompositionReport {
+            budget: self.budget,
+            tokens_before: self.sections.iter().map(|s| s.tokens).sum(),
+            ..Default::default()
+        };
+
+        let mut kept = vec![true; self.sections.len()];
+
+        // Deduplicate identical sections, keeping the first occurrence
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for (index, section) in self.sections.iter().enumerate() {
+            if section.priority == SectionPriority::Pinned {
+                continue;
+            }
+            let key = normalize(&section.text);
+            if key.is_empty() {
+                continue;
+            }
+            match seen.get(&key) {
+                Some(&first) => {
+                    kept[index] = false;
+                    report.deduplicated.push(self.removed(section, Some(self.owners[self.sections[first].source].clone())));
+                }
+                None => {
+                    seen.insert(key, index);
+                }
+            }
+        }
+
+        // Trim lowest-priority sections, later sections first, until within budget
+        if let Some(budget) = self.budget {
+            let mut total: usize = self.kept_tokens(&kept);
+            let mut candidates: Vec<usize> = (0..self.sections.len())
+                .filter(|&i| kept[i] && self.sections[i].priority != SectionPriority::Pinned)
+                .collect();
+            candidates.sort_by_key(|&i| (self.sections[i].priority, std::cmp::Reverse(i)));
+
+            for index in candidates {
+                if total <= budget {
+                    break;
+                }
+                kept[index] = false;
+                total -= self.sections[index].tokens;
+                report.dropped.push(self.removed(&self.sections[index], None));
+            }
+        }
+
+        let content = self.render(&kept);
+        report.tokens_after = self.kept_tokens(&kept);
+
+        ComposedMemory { content, report }
+    }
+
+    fn kept_tokens(&self, kept: &[bool]) -> usize {
+        self.sections.iter()
+            .zip(kept)
+            .filter(|(_, &k)| k)
+            .map(|(s, _)| s.tokens)
+            .sum()
+    }
+
+    fn removed(&self, section: &Section, kept_in: Option<String>) -> RemovedSection {
+        RemovedSection {
+            owner: self.owners[section.source].clone(),
+            heading: if section.heading.is_empty() {
+                "(preamble)".to_string()
+            } else {
+                section.heading.clone()
+            },
+            tokens: section.tokens,
+            kept_in,
+        }
+    }
+
+    fn render(&self, kept: &[bool]) -> String {
+        let mut output = String::new();
+        let mut current_source = None;
+
+        for (section, _) in self.sections.iter().zip(kept).filter(|(_, &k)| k) {
+            if current_source != Some(section.source) {
+                if current_source.is_some() {
+                    output.push_str(&self.separator);
+                }
+                current_source = Some(section.source);
+            }
+            output.push_str(&section.text);
+        }
+
+        output.trim_end().to_string() + "\n"
+    }
+}
+
+/// Split markdown into (heading, text) sections at `#`, `##` and `###` headings outside code fences.
+/// A heading directly followed by another heading stays with it, so parents travel with their first child.
+fn split_sections(content: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+    let mut heading = String::new();
+    let mut text = String::new();
+    let mut has_body = false;
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+        }
+
+        let level = trimmed.chars().take_while(|&c| c == '#').count();
+        let is_heading = !in_fence && (1..=3).contains(&level) && trimmed[level..].starts_with(' ');
+
+        if is_heading && has_body {
+            sections.push((std::mem::take(&mut heading), std::mem::take(&mut text)));
+            has_body = false;
+        }
+        if is_heading && heading.is_empty() {
+            heading = trimmed.trim_start_matches('#').trim().to_string();
+        } else if !trimmed.is_empty() && !is_heading {
+            has_body = true;
+        }
+
+        text.push_str(line);
+        text.push('\n');
+    }
+
+    if !text.trim().is_empty() {
+        sections.push((heading, text));
+    }
+
+    sections
+}
+
+fn classify_heading(heading: &str) -> SectionPriority {
+    let heading = heading.to_lowercase();
+    if HIGH_PRIORITY_KEYWORDS.iter().any(|k| heading.contains(k)) {
+        SectionPriority::High
+    } else if LOW_PRIORITY_KEYWORDS.iter().any(|k| heading.contains(k)) {
+        SectionPriority::Low
+    } else {
+        SectionPriority::Normal
+    }
+}
+
+/// Normalize section text for duplicate detection
+fn normalize(text: &str) -> String {
+    text.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHARED: &str = "## Communication Protocol\nAlways sign messages.\n";
+
+    #[test]
+    fn test_shared_sections_are_deduplicated() {
+        let mut composer = MemoryComposer::new(None);
+        composer.add_source("Athena", &format!("# Athena\nArchitect.\n\n{}", SHARED));
+        composer.add_source("Hermes", &format!("# Hermes\nMessenger.\n\n{}", SHARED));
+
+        let composed = composer.compose();
+        assert_eq!(composed.content.matches("Communication Protocol").count(), 1);
+        assert_eq!(composed.report.deduplicated.len(), 1);
+        assert_eq!(composed.report.deduplicated[0].owner, "Hermes");
+        assert_eq!(composed.report.deduplicated[0].kept_in.as_deref(), Some("Athena"));
+    }
+
+    #[test]
+    fn test_budget_drops_low_priority_sections_first() {
+        let filler = "word ".repeat(200);
+        let mut composer = MemoryComposer::new(Some(400));
+        composer.add_pinned("Task", "# Task\nShip the release.\n");
+        composer.add_source("Athena", &format!(
+            "# Athena\nArchitect.\n\n## Core Principles\n{0}\n\n## Tools\n{0}\n\n## Examples\n{0}\n",
+            filler
+        ));
+
+        let composed = composer.compose();
+        let dropped: Vec<_> = composed.report.dropped.iter().map(|s| s.heading.as_str()).collect();
+        assert_eq!(dropped, vec!["Examples", "Tools"]);
+        assert!(composed.content.contains("Ship the release."));
+        assert!(composed.content.contains("## Core Principles"));
+        assert!(composed.report.tokens_after <= 400);
+    }
+
+    #[test]
+    fn test_headings_inside_code_fences_do_not_split() {
+        let sections = split_sections("# Title\n```\n# not a heading\n```\n## Next\nbody\n");
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].0, "Title");
+        assert_eq!(sections[1].0, "Next");
+    }
+}
@@ -0,0 +1,321 @@
+//! Agent memory inheritance
+//!
+//! An agent memory file may start with a frontmatter block declaring a parent
+//! and optional mixins:
+//!
+//! ```markdown
+//! ---
+//! extends: BaseEngineer
+//! mixins: [SignatureRules, GitProtocol]
+//! ---
+//! # Athena
+//! ...
+//! ```
+//!
+//! Resolution is recursive: the parent is resolved first, mixins are layered on
+//! top in order and the agent's own memory comes last. `##` sections with the
+//! same heading replace the inherited section in place, new sections are
+//! appended. Like `DirectiveProcessor::file_stack`, a stack of the agents being
+//! resolved guards against circular inheritance.
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::helpers::agent_manifest::{default_memory_file, AgentMetadata};
+
+/// Directory under AGENTS/ holding memory fragments that are not agents themselves
+pub const MIXINS_DIR: &str = "_mixins";
+
+/// Inheritance declared in a memory file's frontmatter
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryHeader {
+    pub extends: Option<String>,
+    pub mixins: Vec<String>,
+}
+
+impl MemoryHeader {
+    pub fn is_empty(&self) -> bool {
+        self.extends.is_none() && self.mixins.is_empty()
+    }
+}
+
+/// Resolves `extends`/`mixins` declarations into one memory document
+pub struct MemoryResolver {
+    /// AGENTS directory used to look up parents and mixins
+    agents_dir: PathBuf,
+
+    /// Stack of agents being resolved to prevent circular inheritance
+    name_stack: Vec<String>,
+
+    /// Every memory merged so far, in merge order
+    chain: Vec<String>,
+}
+
+impl MemoryResolver {
+    pub fn new(agents_dir: &Path) -> Self {
+        MemoryResolver {
+            agents_dir: agents_dir.to_path_buf(),
+            name_stack: Vec::new(),
+            chain: Vec::new(),
+        }
+    }
+
+    /// Memories merged by the last resolution, parents first
+    pub fn chain(&self) -> &[String] {
+        &self.chain
+    }
+
+    /// Find and resolve the memory of an agent or mixin by name
+    pub fn resolve_agent(&mut self, name: &str) -> Result<String> {
+        let path = self.memory_file(name)
+            .ok_or_else(|| anyhow!("No memory found for '{}' (looked in AGENTS/{0}/ and AGENTS/{}/)", name, MIXINS_DIR))?;
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read memory file: {}", path.display()))?;
+
+        self.resolve_content(name, &content)
+    }
+
+    /// Resolve memory content belonging to `name`.
+    /// Content without an inheritance header is returned unchanged.
+    pub fn resolve_content(&mut self, name: &str, content: &str) -> Result<String> {
+        if self.name_stack.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            let mut cycle = self.name_stack.clone();
+            cycle.push(name.to_string());
+            return Err(anyhow!("Circular memory inheritance detected: {}", cycle.join(" -> ")));
+        }
+
+        let (header, body) = parse_header(content);
+        if header.is_empty() {
+            self.chain.push(name.to_string());
+            return Ok(content.to_string());
+        }
+
+        self.name_stack.push(name.to_string());
+        let result = self.resolve_layers(&header, body);
+        self.name_stack.pop();
+
+        let merged = result?;
+        self.chain.push(name.to_string());
+        Ok(merged)
+    }
+
+    fn resolve_layers(&mut self, header: &MemoryHeader, body: &str) -> Result<String> {
+        let mut document = MemoryDocument::default();
+
+        if let Some(parent) = &header.extends {
+            let parent_memory = self.resolve_agent(parent)
+                .with_context(|| format!("Failed to resolve parent '{}'", parent))?;
+            document = MemoryDocument::parse(&parent_memory);
+        }
+
+        for mixin in &header.mixins {
+            let mixin_memory = self.resolve_agent(mixin)
+                .with_context(|| format!("Failed to resolve mixin '{}'", mixin))?;
+            document.merge(MemoryDocument::parse(&mixin_memory));
+        }
+
+        document.merge(MemoryDocument::parse(body));
+        Ok(document.render())
+    }
+
+    fn memory_file(&self, name: &str) -> Option<PathBuf> {
+        let agent_dir = self.agents_dir.join(name);
+        if let Ok(Some(manifest)) = AgentMetadata::from_manifest(&agent_dir) {
+            let path = PathBuf::from(&manifest.memory_path);
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+
+        default_memory_file(&agent_dir, name).or_else(|| {
+            let mixin = self.agents_dir.join(MIXINS_DIR).join(format!("{}.md", name));
+            mixin.is_file().then_some(mixin)
+        })
+    }
+}
+
+/// Split a memory file into its inheritance header and the remaining body
+pub fn parse_header(content: &str) -> (MemoryHeader, &str) {
+    let mut header = MemoryHeader::default();
+
+    let Some(rest) = content.strip_prefix("---\n").or_else(|| content.strip_prefix("---\r\n")) else {
+        return (header, content);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (header, content);
+    };
+
+    let frontmatter = &rest[..end];
+    let body = rest[end + 4..].trim_start_matches(['\r', '\n']);
+    let mut in_mixin_list = false;
+
+    for line in frontmatter.lines() {
+        let trimmed = line.trim();
+
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            if in_mixin_list {
+                header.mixins.push(unquote(item));
+            }
+            continue;
+        }
+        in_mixin_list = false;
+
+        if let Some(value) = trimmed.strip_prefix("extends:") {
+            let value = unquote(value);
+            if !value.is_empty() {
+                header.extends = Some(value);
+            }
+        } else if let Some(value) = trimmed.strip_prefix("mixins:") {
+            let value = value.trim();
+            if value.is_empty() {
+                in_mixin_list = true;
+            } else {
+                header.mixins.extend(
+                    value.trim_start_matches('[')
+                        .trim_end_matches(']')
+                        .split(',')
+                        .map(unquote)
+                        .filter(|m| !m.is_empty())
+                );
+            }
+        }
+    }
+
+    if header.is_empty() {
+        // Frontmatter without inheritance keys belongs to the memory itself
+        return (header, content);
+    }
+
+    (header, body)
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches(|c| c == '"' || c == '\'').to_string()
+}
+
+/// A memory split at `##` headings so sections can be overridden by heading
+#[derive(Debug, Clone, Default)]
+struct MemoryDocument {
+    preamble: String,
+    sections: Vec<(String, String)>,
+}
+
+impl MemoryDocument {
+    fn parse(content: &str) -> Self {
+        let mut document = MemoryDocument::default();
+        let mut current: Option<(String, String)> = None;
+        let mut in_fence = false;
+
+        for line in content.lines() {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+            }
+
+            if !in_fence && line.starts_with("## ") {
+                if let Some(section) = current.take() {
+                    document.sections.push(section);
+                }
+                current = Some((line[3..].trim().to_lowercase(), String::new()));
+            }
+
+            let target = match current.as_mut() {
+                Some((_, text)) => text,
+                None => &mut document.preamble,
+            };
+            target.push_str(line);
+            target.push('\n');
+        }
+
+        if let Some(section) = current {
+            document.sections.push(section);
+        }
+
+        document
+    }
+
+    fn merge(&mut self, overlay: MemoryDocument) {
+        if !overlay.preamble.trim().is_empty() {
+            self.preamble = overlay.preamble;
+        }
+
+        for (key, text) in overlay.sections {
+            match self.sections.iter_mut().find(|(k, _)| *k == key) {
+                Some(existing) => existing.1 = text,
+                None => self.sections.push((key, text)),
+            }
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut output = self.preamble.trim_end().to_string();
+
+        for (_, text) in &self.sections {
+            if !output.is_empty() {
+                output.push_str("\n\n");
+            }
+            output.push_str(text.trim_end());
+        }
+
+        output.push('\n');
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_agent(agents_dir: &Path, name: &str, content: &str) {
+        let dir = agents_dir.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(format!("{}.md", name)), content).unwrap();
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let (header, body) = parse_header("---\nextends: Base\nmixins:\n  - A\n  - \"B\"\n---\n# Agent\n");
+        assert_eq!(header.extends.as_deref(), Some("Base"));
+        assert_eq!(header.mixins, vec!["A", "B"]);
+        assert_eq!(body, "# Agent\n");
+
+        let (header, _) = parse_header("---\nmixins: [A, B]\n---\nbody");
+        assert_eq!(header.mixins, vec!["A", "B"]);
+
+        let plain = "---\ntitle: Notes\n---\nbody";
+        assert_eq!(parse_header(plain), (MemoryHeader::default(), plain));
+    }
+
+    #[test]
+    fn test_resolve_extends_and_mixins() {
+        let temp_dir = TempDir::new().unwrap();
+        let agents = temp_dir.path();
+        write_agent(agents, "Base", "# Base\n\n## Protocol\nbase protocol\n\n## Tools\nbase tools\n");
+        fs::create_dir_all(agents.join(MIXINS_DIR)).unwrap();
+        fs::write(agents.join(MIXINS_DIR).join("Signature.md"), "## Signature\nsign everything\n").unwrap();
+        write_agent(agents, "Athena", "---\nextends: Base\nmixins: [Signature]\n---\n# Athena\n\n## Tools\nathena tools\n");
+
+        let mut resolver = MemoryResolver::new(agents);
+        let memory = resolver.resolve_agent("Athena").unwrap();
+
+        assert!(memory.starts_with("# Athena"));
+        assert!(memory.contains("base protocol"));
+        assert!(memory.contains("sign everything"));
+        assert!(memory.contains("athena tools"));
+        assert!(!memory.contains("base tools"));
+        assert!(memory.find("## Protocol").unwrap() < memory.find("## Tools").unwrap());
+        assert_eq!(resolver.chain(), ["Base", "Signature", "Athena"]);
+    }
+
+    #[test]
+    fn test_circular_inheritance_is_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let agents = temp_dir.path();
+        write_agent(agents, "A", "---\nextends: B\n---\n# A\n");
+        write_agent(agents, "B", "---\nmixins: [A]\n---\n# B\n");
+
+        let error = MemoryResolver::new(agents).resolve_agent("A").unwrap_err();
+        assert!(format!("{:#}", error).contains("A -> B -> A"));
+    }
+}
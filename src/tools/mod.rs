@@ -10,6 +10,8 @@ pub mod standardization;
 pub mod import_standardization;
 pub mod command_standardization;
 pub mod todo_standardization;
+pub mod memory_composer;
+pub mod memory_inheritance;
 
 // Re-export commonly used tools
 pub use command_generator::{generate_command, process_instant_command};
@@ -19,4 +21,6 @@ pub use ci_migration::{detect_ci_integration, migrate_to_cir};
 pub use standardization::{StandardizationEngine, initialize_standardization, quick_standardization_check};
 pub use import_standardization::{standardize_all_imports, ImportStandardization};
 pub use command_standardization::{standardize_all_commands, CommandStandardization};
-pub use todo_standardization::TodoStandardization;
\ No newline at end of file
+pub use todo_standardization::TodoStandardization;
+pub use memory_composer::{MemoryComposer, ComposedMemory, CompositionReport};
+pub use memory_inheritance::MemoryResolver;
\ No newline at end of file
@@ -7,6 +7,8 @@ use std::path::{Path, PathBuf};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+use crate::helpers::session_store;
+
 const METADATA_DIR: &str = ".ci-topology";
 const CONFIG_FILE: &str = "config.json";
 const HISTORY_FILE: &str = "commit_history.json";
@@ -275,7 +277,14 @@ impl MetadataManager {
     fn save_commit_history(&self, history: &CommitHistoryData) -> Result<(), Box<dyn std::error::Error>> {
         let history_path = self.metadata_dir.join(HISTORY_FILE);
         let history_json = serde_json::to_string_pretty(history)?;
-        fs::write(history_path, history_json)?;
+        fs::write(&history_path, history_json)?;
+
+        // Mirror the session being worked on into the CI session store
+        if let Some(session) = history.sessions.last() {
+            if let Err(e) = session_store::sync_topology_session(session, &history_path) {
+                eprintln!("⚠️  Commit session not recorded in the session store: {}", e);
+            }
+        }
         Ok(())
     }
 